@@ -2,13 +2,20 @@ use std::collections::BTreeMap;
 use std::fmt;
 use std::fmt::Debug;
 use std::time::{Duration, SystemTime};
-use chrono::{DateTime, Local, TimeDelta};
+use chrono::{DateTime, Local, TimeDelta, Utc};
 use crate::arbitrage_manager::arbitrage_manager::ArbitrageManager;
+use crate::arbitrage_manager::price_move_latency::LatencyStats;
+use crate::exchange::connection_stats::EConnectionState;
+use crate::exchange::market_stats::MarketStats;
 use crate::exchange::order_book::{TPrice};
+use crate::exchange::trade_tape::Trade;
 use colored::Colorize;
+use ordered_float::OrderedFloat;
 
 const MAX_ORDERBOOK_TIME_GAP: TimeDelta = TimeDelta::milliseconds(500);
 const MAX_CURRENT_TIME_GAP: Duration = Duration::from_millis(300);
+/// Window over which the recent traded volume is reported
+const RECENT_VOLUME_WINDOW: TimeDelta = TimeDelta::seconds(60);
 
 impl ArbitrageManager {
     pub(crate) async fn analyze_opportunities(&self) {
@@ -25,6 +32,10 @@ impl ArbitrageManager {
         let mut best_ask: Option<(&String, &TPrice)> = None;
 
         for (exchange, prices) in &self.best_prices {
+            // The order book of a disconnected exchange can not be trusted
+            if self.connection_stats.get(exchange).is_some_and(|stats| stats.state != EConnectionState::Connected) {
+                continue;
+            }
             if best_bid.is_none() || prices.best_bid > *best_bid.unwrap().1 {
                 best_bid = Some((exchange, &prices.best_bid));
             }
@@ -80,48 +91,134 @@ impl ArbitrageManager {
             return None;
         }
 
-        let mut total_volume = 0.0;
-        let mut total_profit = 0.0;
+        // *** Round prices and volume to what both exchanges accept ***
+        let buy_rules = buy_exchange.get_instrument_rules().await;
+        let sell_rules = sell_exchange.get_instrument_rules().await;
+        let asks = rounded_levels(&buy_orderbook.asks, |price| buy_rules.as_ref().map_or(price, |rules| rules.round_price_up(price)));
+        let bids = rounded_levels(&sell_orderbook.bids, |price| sell_rules.as_ref().map_or(price, |rules| rules.round_price_down(price)));
 
-        let mut ask_volumes: BTreeMap<TPrice, f64> = buy_orderbook.asks.clone();
-        let mut bid_volumes: BTreeMap<TPrice, f64> = sell_orderbook.bids.clone();
+        // Rounding to the ticks may close the spread
+        let buy_price = asks.first_key_value()?.0.into_inner();
+        let sell_price = bids.last_key_value()?.0.into_inner();
+        if buy_price >= sell_price {
+            return None;
+        }
 
-        while let (Some((&ask_price, ask_volume)), Some((&bid_price, bid_volume))) = (ask_volumes.iter().next(), bid_volumes.iter().next_back()) {
-            if ask_price >= bid_price {
-                break;
-            }
+        let (max_volume, _) = match_order_books(&asks, &bids, f64::INFINITY);
+        let mut volume = max_volume;
+        for rules in [&buy_rules, &sell_rules].into_iter().flatten() {
+            volume = rules.round_qty(volume);
+        }
 
-            let volume = ask_volume.min(*bid_volume);
-            let profit = (bid_price.into_inner() - ask_price.into_inner()) * volume;
-
-            total_volume += volume;
-            total_profit += profit;
-
-            if *ask_volume > *bid_volume {
-                ask_volumes.insert(ask_price, ask_volume - bid_volume);
-                bid_volumes.remove(&bid_price);
-            } else if *ask_volume < *bid_volume {
-                bid_volumes.insert(bid_price, bid_volume - ask_volume);
-                ask_volumes.remove(&ask_price);
-            } else {
-                ask_volumes.remove(&ask_price);
-                bid_volumes.remove(&bid_price);
-            }
+        // *** Drop opportunities below the minimum order size of either exchange ***
+        if volume <= 0.0
+            || buy_rules.is_some_and(|rules| !rules.is_order_allowed(volume, buy_price))
+            || sell_rules.is_some_and(|rules| !rules.is_order_allowed(volume, sell_price)) {
+            // println!("Volume {} is below the minimum order size", volume);
+            return None;
         }
 
+        let (total_volume, total_profit) = match_order_books(&asks, &bids, volume);
+
         if total_volume > 0.0 {
+            let market_activity = self.get_market_activity(&opportunity.buy_exchange, &opportunity.sell_exchange).await;
+            let unconfirmed_exchanges = [(&opportunity.buy_exchange, &buy_orderbook), (&opportunity.sell_exchange, &sell_orderbook)]
+                .into_iter()
+                .filter(|(_, orderbook)| !orderbook.confirmed)
+                .map(|(exchange, _)| exchange.clone())
+                .collect();
             Some(ConfirmedArbitrageOpportunity {
                 buy_exchange: opportunity.buy_exchange,
                 sell_exchange: opportunity.sell_exchange,
-                buy_price: *buy_orderbook.asks.first_key_value()?.0,
-                sell_price: *sell_orderbook.bids.last_key_value()?.0,
+                buy_price: OrderedFloat(buy_price),
+                sell_price: OrderedFloat(sell_price),
                 volume: total_volume,
                 estimated_profit: total_profit,
+                market_activity,
+                unconfirmed_exchanges,
             })
         } else {
             None
         }
     }
+
+    /// Collects recent trades and market statistics of both exchanges and the price move latency between them
+    async fn get_market_activity(&self, buy_exchange: &String, sell_exchange: &String) -> MarketActivity {
+        let now = Utc::now();
+        let mut activity = MarketActivity::default();
+
+        for exchange_name in [buy_exchange, sell_exchange] {
+            let Some(exchange) = self.exchanges.get(exchange_name) else {
+                continue;
+            };
+            if let Some(stats) = exchange.get_market_stats().await {
+                activity.market_stats.push((exchange_name.clone(), stats));
+            }
+            let Some(trade_tape_ptr) = exchange.get_trade_tape() else {
+                continue;
+            };
+            let trade_tape = trade_tape_ptr.read().await;
+            *activity.recent_volume.get_or_insert(0.0) += trade_tape.realized_volume(RECENT_VOLUME_WINDOW, now);
+            if let Some(trade) = trade_tape.last_trade() {
+                if activity.last_trade.as_ref().is_none_or(|(_, last_trade)| trade.timestamp > last_trade.timestamp) {
+                    activity.last_trade = Some((exchange_name.clone(), trade.clone()));
+                }
+            }
+        }
+
+        for (leader, follower) in [(buy_exchange, sell_exchange), (sell_exchange, buy_exchange)] {
+            if let Some(stats) = self.price_move_latency.get_stats(leader, follower) {
+                activity.price_move_latency = Some((leader.clone(), follower.clone(), stats.clone()));
+                break;
+            }
+        }
+
+        activity
+    }
+}
+
+/// Levels with their prices rounded to the ticks of the exchange, levels rounded to the same price are merged
+fn rounded_levels(levels: &BTreeMap<TPrice, f64>, round_price: impl Fn(f64) -> f64) -> BTreeMap<TPrice, f64> {
+    let mut rounded: BTreeMap<TPrice, f64> = BTreeMap::new();
+    for (price, volume) in levels {
+        *rounded.entry(OrderedFloat(round_price(price.into_inner()))).or_insert(0.0) += volume;
+    }
+    rounded
+}
+
+/// Matches asks of the buy exchange against bids of the sell exchange while they cross
+/// Returns the matched volume (up to `volume_limit`) and the profit of buying and selling it
+fn match_order_books(asks: &BTreeMap<TPrice, f64>, bids: &BTreeMap<TPrice, f64>, volume_limit: f64) -> (f64, f64) {
+    let mut total_volume = 0.0;
+    let mut total_profit = 0.0;
+
+    let mut ask_volumes: BTreeMap<TPrice, f64> = asks.clone();
+    let mut bid_volumes: BTreeMap<TPrice, f64> = bids.clone();
+
+    while let (Some((&ask_price, ask_volume)), Some((&bid_price, bid_volume))) = (ask_volumes.iter().next(), bid_volumes.iter().next_back()) {
+        if ask_price >= bid_price || total_volume >= volume_limit {
+            break;
+        }
+
+        let volume = ask_volume.min(*bid_volume).min(volume_limit - total_volume);
+        let profit = (bid_price.into_inner() - ask_price.into_inner()) * volume;
+
+        total_volume += volume;
+        total_profit += profit;
+
+        if *ask_volume > *bid_volume {
+            ask_volumes.insert(ask_price, ask_volume - bid_volume);
+            bid_volumes.remove(&bid_price);
+        } else if *ask_volume < *bid_volume {
+            bid_volumes.insert(bid_price, bid_volume - ask_volume);
+            ask_volumes.remove(&ask_price);
+        } else {
+            ask_volumes.remove(&ask_price);
+            bid_volumes.remove(&bid_price);
+        }
+    }
+
+    (total_volume, total_profit)
 }
 
 #[derive(Debug)]
@@ -140,6 +237,22 @@ struct ConfirmedArbitrageOpportunity {
     sell_price: TPrice,
     volume: f64,
     estimated_profit: f64,
+    market_activity: MarketActivity,
+    /// Exchanges whose book relies on state which may still be rolled back
+    unconfirmed_exchanges: Vec<String>,
+}
+
+/// Recent trading activity on the exchanges of an opportunity
+#[derive(Debug, Default)]
+struct MarketActivity {
+    /// Volume traded over the recent volume window, if any exchange streams trades
+    recent_volume: Option<f64>,
+    /// The most recent trade and the exchange it was printed on
+    last_trade: Option<(String, Trade)>,
+    /// Latency of price moves on the follower exchange after trades on the leader exchange
+    price_move_latency: Option<(String, String, LatencyStats)>,
+    /// Market statistics of the exchanges that report them
+    market_stats: Vec<(String, MarketStats)>,
 }
 
 impl fmt::Display for ArbitrageOpportunity {
@@ -168,6 +281,75 @@ impl fmt::Display for ConfirmedArbitrageOpportunity {
         writeln!(f, "Volume: {:.8} units", self.volume.to_string().magenta())?;
         writeln!(f, "Estimated Profit: {:.8}", self.estimated_profit.to_string().green())?;
         writeln!(f, "Profit Percentage: {:.2}%", profit_percentage.to_string().green())?;
+        if !self.unconfirmed_exchanges.is_empty() {
+            writeln!(f, "{} {}", "Unconfirmed State:".red(), self.unconfirmed_exchanges.join(", ").blue())?;
+        }
+        if let Some(recent_volume) = self.market_activity.recent_volume {
+            writeln!(f, "Traded Volume ({}s): {} units", RECENT_VOLUME_WINDOW.num_seconds(), recent_volume.to_string().magenta())?;
+        }
+        if let Some((exchange, trade)) = &self.market_activity.last_trade {
+            let slot = trade.slot.map(|slot| format!(", slot {}", slot)).unwrap_or_default();
+            writeln!(f, "Last Trade: {} {:?} {} @ {} ({}{})",
+                     exchange.blue(),
+                     trade.side,
+                     trade.size.to_string().magenta(),
+                     trade.price.to_string().yellow(),
+                     trade.timestamp.with_timezone(&Local).format("%H:%M:%S%.3f").to_string().cyan(),
+                     slot
+            )?;
+        }
+        if let Some((leader, follower, stats)) = &self.market_activity.price_move_latency {
+            writeln!(f, "Price Move Latency ({} after {} trades): avg {} ms, last {} ms ({} samples)",
+                     follower.blue(),
+                     leader.blue(),
+                     stats.mean().num_milliseconds().to_string().cyan(),
+                     stats.last.num_milliseconds().to_string().cyan(),
+                     stats.samples
+            )?;
+        }
+        for (exchange, stats) in &self.market_activity.market_stats {
+            if let Some(last_px) = stats.last_px {
+                write!(f, "Last Price ({}): {}", exchange.blue(), last_px.to_string().yellow())?;
+                if let Some(hour_sigma) = stats.hour_sigma {
+                    write!(f, " | Hour Sigma: {}", hour_sigma.to_string().cyan())?;
+                }
+                writeln!(f)?;
+            }
+        }
         writeln!(f, "{}", "=================================".green())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::exchange::instrument_rules::InstrumentRules;
+    use super::*;
+
+    fn levels(levels: &[(f64, f64)]) -> BTreeMap<TPrice, f64> {
+        levels.iter().map(|(price, volume)| (OrderedFloat(*price), *volume)).collect()
+    }
+
+    fn rules(tick_size: f64) -> InstrumentRules {
+        InstrumentRules { tick_size, lot_size: 0.001, min_qty: 0.0, min_notional: 0.0 }
+    }
+
+    #[test]
+    fn test_rounded_prices_no_longer_cross() {
+        let asks = rounded_levels(&levels(&[(100.001, 1.0)]), |price| rules(0.01).round_price_up(price));
+        let bids = rounded_levels(&levels(&[(100.009, 1.0)]), |price| rules(0.01).round_price_down(price));
+
+        assert_eq!(match_order_books(&asks, &bids, f64::INFINITY), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_profit_is_computed_at_rounded_prices() {
+        let asks = rounded_levels(&levels(&[(100.001, 1.0), (100.004, 1.0)]), |price| rules(0.01).round_price_up(price));
+        let bids = rounded_levels(&levels(&[(100.529, 3.0)]), |price| rules(0.01).round_price_down(price));
+
+        // Both asks are rounded to 100.01 and merged
+        assert_eq!(asks, levels(&[(100.01, 2.0)]));
+        let (volume, profit) = match_order_books(&asks, &bids, f64::INFINITY);
+        assert_eq!(volume, 2.0);
+        assert!((profit - 1.02).abs() < 1e-9);
+    }
+}
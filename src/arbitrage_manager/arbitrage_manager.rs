@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 use std::sync::Arc;
+use crate::arbitrage_manager::price_move_latency::PriceMoveLatency;
+use crate::exchange::connection_stats::ConnectionStats;
 use crate::exchange::exchange::Exchange;
 use crate::exchange::exchange_update::{BestPrices};
 use crate::trading_pair::ETradingPair;
@@ -10,6 +12,10 @@ pub struct ArbitrageManager {
     pub(super) exchanges: HashMap<String, Arc<dyn Exchange>>,
     /// Cache best prices for each exchange
     pub(super) best_prices: HashMap<String, BestPrices>,
+    /// Latest connection statistics for each exchange
+    pub(super) connection_stats: HashMap<String, ConnectionStats>,
+    /// Latency of best price moves relative to trades printed on other exchanges
+    pub(super) price_move_latency: PriceMoveLatency,
     /// The trading pair for which the arbitrage manager is managing exchanges
     pub(super) trading_pair: ETradingPair,
 }
@@ -19,6 +25,8 @@ impl ArbitrageManager {
         ArbitrageManager {
             exchanges: HashMap::new(),
             best_prices: HashMap::new(),
+            connection_stats: HashMap::new(),
+            price_move_latency: PriceMoveLatency::default(),
             trading_pair,
         }
     }
@@ -1,4 +1,5 @@
 pub mod arbitrage_manager;
 pub mod analyze_opportunities;
+pub mod price_move_latency;
 mod add_exchange;
-mod run;
\ No newline at end of file
+mod run;
@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+use chrono::{DateTime, TimeDelta, Utc};
+
+/// Aggregated latency samples between a trade print on one exchange and a price move on another
+#[derive(Clone, Debug, Default)]
+pub struct LatencyStats {
+    pub samples: u64,
+    pub last: TimeDelta,
+    pub min: TimeDelta,
+    pub max: TimeDelta,
+    total: TimeDelta,
+}
+
+impl LatencyStats {
+    fn record(&mut self, latency: TimeDelta) {
+        if self.samples == 0 || latency < self.min {
+            self.min = latency;
+        }
+        if self.samples == 0 || latency > self.max {
+            self.max = latency;
+        }
+        self.samples += 1;
+        self.last = latency;
+        self.total += latency;
+    }
+
+    /// Returns the average latency over all samples
+    pub fn mean(&self) -> TimeDelta {
+        if self.samples == 0 {
+            return TimeDelta::zero();
+        }
+        self.total / self.samples as i32
+    }
+}
+
+/// Measures how long it takes for an exchange (follower) to move its best prices
+/// after a trade has been printed on another exchange (leader)
+/// Both are timed by the exchanges where they report an update time (DEXnow by the slot time), so delivery
+/// delays to the manager are left out. Raydium and Orca books are timed when the update is received,
+/// which includes the delivery delay
+#[derive(Default)]
+pub struct PriceMoveLatency {
+    /// Time of the most recent trade for each exchange
+    last_trade_time: HashMap<String, DateTime<Utc>>,
+    /// Time of the most recent best price move for each exchange
+    last_move_time: HashMap<String, DateTime<Utc>>,
+    /// Latency statistics keyed by (leader, follower)
+    stats: HashMap<(String, String), LatencyStats>,
+}
+
+impl PriceMoveLatency {
+    /// Registers a trade printed on the given exchange
+    pub fn record_trade(&mut self, exchange: &str, trade_time: DateTime<Utc>) {
+        self.last_trade_time.insert(exchange.to_string(), trade_time);
+    }
+
+    /// Registers a best price move on the given exchange and measures the latency
+    /// relative to every trade printed elsewhere since the previous move
+    pub fn record_price_move(&mut self, exchange: &str, move_time: DateTime<Utc>) {
+        let previous_move = self.last_move_time.insert(exchange.to_string(), move_time);
+        for (leader, trade_time) in &self.last_trade_time {
+            if leader == exchange || *trade_time > move_time {
+                continue;
+            }
+            if previous_move.is_some_and(|previous_move| *trade_time <= previous_move) {
+                continue;
+            }
+            self.stats
+                .entry((leader.clone(), exchange.to_string()))
+                .or_default()
+                .record(move_time.signed_duration_since(*trade_time));
+        }
+    }
+
+    /// Returns latency statistics of `follower` price moves after `leader` trades
+    pub fn get_stats(&self, leader: &str, follower: &str) -> Option<&LatencyStats> {
+        self.stats.get(&(leader.to_string(), follower.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use super::*;
+
+    fn at(ms: i64) -> DateTime<Utc> {
+        Utc.timestamp_millis_opt(ms).unwrap()
+    }
+
+    #[test]
+    fn test_price_move_latency_measures_first_move_after_trade() {
+        let mut latency = PriceMoveLatency::default();
+        latency.record_trade("Bybit", at(1_000));
+        latency.record_price_move("DEXnow", at(1_400));
+        // The second move is not a reaction to the same trade
+        latency.record_price_move("DEXnow", at(1_900));
+
+        let stats = latency.get_stats("Bybit", "DEXnow").unwrap();
+        assert_eq!(stats.samples, 1);
+        assert_eq!(stats.last, TimeDelta::milliseconds(400));
+        assert!(latency.get_stats("DEXnow", "Bybit").is_none());
+    }
+
+    #[test]
+    fn test_price_move_latency_mean() {
+        let mut latency = PriceMoveLatency::default();
+        latency.record_trade("Bybit", at(1_000));
+        latency.record_price_move("DEXnow", at(1_200));
+        latency.record_trade("Bybit", at(2_000));
+        latency.record_price_move("DEXnow", at(2_600));
+
+        let stats = latency.get_stats("Bybit", "DEXnow").unwrap();
+        assert_eq!(stats.samples, 2);
+        assert_eq!(stats.mean(), TimeDelta::milliseconds(400));
+        assert_eq!(stats.min, TimeDelta::milliseconds(200));
+        assert_eq!(stats.max, TimeDelta::milliseconds(600));
+    }
+}
@@ -1,5 +1,7 @@
 use tokio::sync::mpsc;
 use crate::arbitrage_manager::arbitrage_manager::ArbitrageManager;
+use crate::exchange::connection_stats::{ConnectionStats, EConnectionState};
+use crate::exchange::exchange_update::EUpdatePayload;
 
 const MESSAGE_BUFFER_SIZE: usize = 100;
 
@@ -19,8 +21,42 @@ impl ArbitrageManager {
         }
 
         while let Some(update) = rx.recv().await {
-            self.best_prices.insert(update.exchange_name, update.best_prices);
-            self.analyze_opportunities().await;
+            match update.payload {
+                EUpdatePayload::BestPrices(best_prices) => {
+                    let price_moved = self.best_prices.get(&update.exchange_name)
+                        .is_none_or(|previous| {
+                            previous.best_bid != best_prices.best_bid || previous.best_ask != best_prices.best_ask
+                        });
+                    if price_moved {
+                        self.price_move_latency.record_price_move(&update.exchange_name, best_prices.timestamp);
+                    }
+                    self.best_prices.insert(update.exchange_name, best_prices);
+                    self.analyze_opportunities().await;
+                }
+                EUpdatePayload::Trade(trade) => {
+                    self.price_move_latency.record_trade(&update.exchange_name, trade.timestamp);
+                }
+                EUpdatePayload::ConnectionStats(stats) => {
+                    self.update_connection_stats(update.exchange_name, stats);
+                }
+            }
         }
     }
-}
\ No newline at end of file
+
+    fn update_connection_stats(&mut self, exchange_name: String, stats: ConnectionStats) {
+        let previous_state = self.connection_stats.get(&exchange_name).map(|previous| previous.state);
+        if previous_state != Some(stats.state) {
+            println!("[INFO][Manager] {} connection state: {:?} (attempts: {}, consecutive failures: {}, last error: {})",
+                     exchange_name,
+                     stats.state,
+                     stats.attempts,
+                     stats.consecutive_failures,
+                     stats.last_error.as_deref().unwrap_or("none"));
+        }
+        if stats.state == EConnectionState::Failed {
+            eprintln!("[ERROR][Manager] {} has failed and is excluded from the analysis", exchange_name);
+            self.best_prices.remove(&exchange_name);
+        }
+        self.connection_stats.insert(exchange_name, stats);
+    }
+}
@@ -0,0 +1,63 @@
+use std::env;
+use std::sync::Arc;
+use async_trait::async_trait;
+use tokio::sync::mpsc::Sender;
+use tokio::sync::{Mutex, RwLock};
+use crate::exchange::binance::depth_synchronizer::DepthSynchronizer;
+use crate::exchange::exchange::Exchange;
+use crate::exchange::exchange_update::ExchangeUpdate;
+use crate::exchange::order_book::OrderBook;
+use crate::trading_pair::ETradingPair;
+
+pub struct BinanceExchange {
+    pub(crate) name: String,
+    pub(crate) orderbook: Arc<RwLock<OrderBook>>,
+    pub(crate) synchronizer: Mutex<DepthSynchronizer>,
+    /// Base URL of the WebSocket streams
+    pub(crate) ws_url: String,
+    /// Base URL of the REST API
+    pub(crate) rest_url: String,
+}
+
+impl BinanceExchange {
+    pub(crate) fn with_urls(ws_url: String, rest_url: String) -> Self {
+        BinanceExchange {
+            name: "Binance".to_string(),
+            orderbook: Arc::new(RwLock::new(OrderBook::new())),
+            synchronizer: Mutex::new(DepthSynchronizer::default()),
+            ws_url,
+            rest_url,
+        }
+    }
+
+    /// Returns the Binance symbol of the trading pair
+    pub(crate) fn symbol(trading_pair: &ETradingPair) -> String {
+        match trading_pair {
+            ETradingPair::BtcUsdc => "BTCUSDC".to_string(),
+            ETradingPair::SolUsdc => "SOLUSDC".to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl Exchange for BinanceExchange {
+    fn new() -> Self {
+        BinanceExchange::with_urls(
+            env::var("BINANCE_WS_URL").expect("BINANCE_WS_URL must be set"),
+            env::var("BINANCE_REST_URL").expect("BINANCE_REST_URL must be set"),
+        )
+    }
+
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    async fn start(&self, trading_pair: ETradingPair, update_sender: Sender<ExchangeUpdate>) {
+        println!("[INFO][Binance] Starting exchange websocket...");
+        self.connect_and_listen(&trading_pair, &update_sender).await;
+    }
+
+    fn get_order_book(&self) -> Arc<RwLock<OrderBook>> {
+        self.orderbook.clone()
+    }
+}
@@ -0,0 +1,244 @@
+use std::time::{Duration, Instant};
+use async_trait::async_trait;
+use tokio::sync::mpsc::Sender;
+use tokio::sync::MutexGuard;
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::protocol::Message;
+use crate::exchange::binance::binance_exchange::BinanceExchange;
+use crate::exchange::binance::depth_synchronizer::{DepthSynchronizer, EDepthSyncAction};
+use crate::exchange::binance::rest_depth_snapshot::DepthSnapshot;
+use crate::exchange::binance::ws_depth_update::DepthUpdate;
+use crate::exchange::exchange_update::ExchangeUpdate;
+use crate::exchange::reconnect_policy::ReconnectPolicy;
+use crate::exchange::ws_connection::ws_connection_manager::run_ws_connection;
+use crate::exchange::ws_connection::ws_protocol::{EHeartbeat, EWsMessage, WsProtocol};
+use crate::trading_pair::ETradingPair;
+
+/// Pause before the next snapshot request after a failed synchronization
+const SNAPSHOT_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Binance diff depth stream protocol: the stream is selected by the URL and kept alive with ping frames
+struct BinanceWsProtocol<'a> {
+    exchange: &'a BinanceExchange,
+    symbol: String,
+    update_sender: &'a Sender<ExchangeUpdate>,
+    /// No snapshot is requested before this moment
+    snapshot_retry_after: std::sync::Mutex<Option<Instant>>,
+    /// Snapshot being loaded while the stream events are buffered
+    snapshot_request: std::sync::Mutex<Option<JoinHandle<Result<DepthSnapshot, String>>>>,
+}
+
+#[async_trait]
+impl WsProtocol for BinanceWsProtocol<'_> {
+    fn name(&self) -> String {
+        self.exchange.name.clone()
+    }
+
+    fn url(&self) -> String {
+        format!("{}/ws/{}@depth@100ms", self.exchange.ws_url.trim_end_matches('/'), self.symbol.to_lowercase())
+    }
+
+    async fn on_connecting(&self) -> Result<(), Box<dyn std::error::Error>> {
+        // Events missed while disconnected can not be recovered, a new snapshot is required
+        self.exchange.synchronizer.lock().await.reset();
+        *self.snapshot_retry_after.lock().unwrap() = None;
+        if let Some(request) = self.snapshot_request.lock().unwrap().take() {
+            request.abort();
+        }
+        Ok(())
+    }
+
+    fn subscribe_messages(&self) -> Result<Vec<Message>, Box<dyn std::error::Error>> {
+        // The stream is subscribed through the URL
+        Ok(vec![])
+    }
+
+    fn heartbeat(&self) -> EHeartbeat {
+        EHeartbeat::PingFrame
+    }
+
+    async fn handle_text(&self, text: &str) -> EWsMessage {
+        let Ok(update) = serde_json::from_str::<DepthUpdate>(text) else {
+            return EWsMessage::Unknown;
+        };
+
+        let mut synchronizer = self.exchange.synchronizer.lock().await;
+        match synchronizer.push(update) {
+            EDepthSyncAction::Apply(update) => {
+                self.exchange.process_depth_update(self.update_sender, update).await;
+            }
+            EDepthSyncAction::Skip => {}
+            EDepthSyncAction::NeedSnapshot => {
+                self.synchronize(&mut synchronizer).await;
+            }
+            EDepthSyncAction::Gap { expected, received } => {
+                println!("[WARNING][Binance] Depth update gap: expected {}, received {}. Resynchronizing...", expected, received);
+                self.synchronize(&mut synchronizer).await;
+            }
+        }
+        EWsMessage::Handled
+    }
+}
+
+impl BinanceWsProtocol<'_> {
+    /// Request a snapshot, or apply the loaded one and the buffered events on top of it
+    /// The snapshot is loaded on its own task and picked up by a later event, the events keep being buffered meanwhile
+    async fn synchronize(&self, synchronizer: &mut MutexGuard<'_, DepthSynchronizer>) {
+        if synchronizer.is_synchronized() {
+            return;
+        }
+        let loaded = {
+            let mut request = self.snapshot_request.lock().unwrap();
+            if request.is_none() {
+                if !self.snapshot_retry_after.lock().unwrap().is_some_and(|retry_after| Instant::now() < retry_after) {
+                    *request = Some(self.exchange.spawn_depth_snapshot(&self.symbol));
+                }
+                return;
+            }
+            if !request.as_ref().is_some_and(|request| request.is_finished()) {
+                return;
+            }
+            request.take()
+        };
+        let Some(request) = loaded else {
+            return;
+        };
+
+        let snapshot = match request.await.map_err(|e| e.to_string()).and_then(|snapshot| snapshot) {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                eprintln!("[ERROR][Binance] Failed to load depth snapshot: {}", e);
+                self.delay_next_snapshot();
+                return;
+            }
+        };
+
+        match synchronizer.apply_snapshot(snapshot.last_update_id) {
+            Ok(updates) => {
+                println!("[INFO][Binance] Order book synchronized at update {}", snapshot.last_update_id);
+                *self.snapshot_retry_after.lock().unwrap() = None;
+                self.exchange.process_snapshot(self.update_sender, snapshot, updates).await;
+            }
+            Err(e) => {
+                println!("[WARNING][Binance] Depth snapshot rejected: {}", e);
+                self.delay_next_snapshot();
+            }
+        }
+    }
+
+    fn delay_next_snapshot(&self) {
+        *self.snapshot_retry_after.lock().unwrap() = Some(Instant::now() + SNAPSHOT_RETRY_DELAY);
+    }
+}
+
+impl BinanceExchange {
+    /// Maintain the diff depth stream until the reconnect budget is exhausted
+    pub async fn connect_and_listen(
+        &self,
+        trading_pair: &ETradingPair,
+        order_book_update_sender: &Sender<ExchangeUpdate>,
+    ) {
+        let protocol = BinanceWsProtocol {
+            exchange: self,
+            symbol: Self::symbol(trading_pair),
+            update_sender: order_book_update_sender,
+            snapshot_retry_after: std::sync::Mutex::new(None),
+            snapshot_request: std::sync::Mutex::new(None),
+        };
+        run_ws_connection(&protocol, ReconnectPolicy::default(), order_book_update_sender).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures_util::SinkExt;
+    use ordered_float::OrderedFloat;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use tokio::sync::mpsc;
+    use tokio_tungstenite::accept_async;
+    use super::*;
+
+    /// Serves the given snapshot bodies to consecutive HTTP requests
+    async fn spawn_snapshot_server(snapshots: Vec<&'static str>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            for body in snapshots {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut request = Vec::new();
+                let mut buffer = [0u8; 1024];
+                while !request.ends_with(b"\r\n\r\n") {
+                    let read = stream.read(&mut buffer).await.unwrap();
+                    request.extend_from_slice(&buffer[..read]);
+                }
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+        url
+    }
+
+    fn depth_event(first_update_id: u64, final_update_id: u64, bids: &str, asks: &str) -> String {
+        format!(
+            r#"{{"e":"depthUpdate","E":1724312000000,"s":"SOLUSDC","U":{},"u":{},"b":{},"a":{}}}"#,
+            first_update_id, final_update_id, bids, asks
+        )
+    }
+
+    #[tokio::test]
+    async fn test_depth_stream_synchronizes_and_recovers_from_gap() {
+        let rest_url = spawn_snapshot_server(vec![
+            r#"{"lastUpdateId":100,"bids":[["100.0","1.0"]],"asks":[["101.0","1.0"]]}"#,
+            r#"{"lastUpdateId":112,"bids":[["99.0","3.0"]],"asks":[["102.0","3.0"]]}"#,
+        ]).await;
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let ws_url = format!("ws://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws_stream = accept_async(stream).await.unwrap();
+            let events = [
+                // Straddles the first snapshot
+                depth_event(95, 105, r#"[["100.5","2.0"]]"#, "[]"),
+                depth_event(106, 107, r#"[["100.0","0.0"]]"#, "[]"),
+                // Events 108..109 are lost
+                depth_event(110, 111, r#"[["50.0","1.0"]]"#, "[]"),
+                depth_event(112, 113, r#"[["99.5","1.0"]]"#, r#"[["102.0","0.0"]]"#),
+            ];
+            for event in events {
+                ws_stream.send(Message::Text(event)).await.unwrap();
+                // Leaves the snapshots time to load, they are picked up by the next event
+                tokio::time::sleep(Duration::from_millis(200)).await;
+            }
+            ws_stream.close(None).await.unwrap();
+        });
+
+        let exchange = BinanceExchange::with_urls(ws_url, rest_url);
+        let (tx, _rx) = mpsc::channel(100);
+        let protocol = BinanceWsProtocol {
+            exchange: &exchange,
+            symbol: "SOLUSDC".to_string(),
+            update_sender: &tx,
+            snapshot_retry_after: std::sync::Mutex::new(None),
+            snapshot_request: std::sync::Mutex::new(None),
+        };
+        let policy = ReconnectPolicy { max_retries: 0, ..ReconnectPolicy::default() };
+        tokio::time::timeout(Duration::from_secs(5), run_ws_connection(&protocol, policy, &tx))
+            .await
+            .expect("Connection manager did not stop after the retry budget");
+
+        let orderbook = exchange.orderbook.read().await;
+        assert_eq!(orderbook.sequence, 113);
+        // The book was rebuilt from the second snapshot, the event before it was dropped
+        assert_eq!(orderbook.bids.get(&OrderedFloat(50.0)), None);
+        assert_eq!(orderbook.bids.get(&OrderedFloat(100.5)), None);
+        assert_eq!(orderbook.get_best_bid(), Some(OrderedFloat(99.5)));
+        assert_eq!(orderbook.asks.get(&OrderedFloat(102.0)), None);
+        assert_eq!(orderbook.get_best_ask(), None);
+    }
+}
@@ -0,0 +1,188 @@
+use thiserror::Error;
+use crate::exchange::binance::ws_depth_update::DepthUpdate;
+
+/// Maximum number of events buffered while waiting for a snapshot
+const MAX_BUFFERED_UPDATES: usize = 1000;
+
+/// What to do with a diff depth event
+#[derive(Debug)]
+pub enum EDepthSyncAction {
+    /// Apply the event to the local order book
+    Apply(DepthUpdate),
+    /// The event is older than the local order book and must be ignored
+    Skip,
+    /// The local order book is not synchronized, the event has been buffered and a snapshot is required
+    NeedSnapshot,
+    /// A gap in update IDs was detected, the order book has been reset and a snapshot is required
+    Gap { expected: u64, received: u64 },
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum DepthSyncError {
+    #[error("Snapshot {snapshot_update_id} is older than the first buffered event {first_update_id}")]
+    SnapshotTooOld { snapshot_update_id: u64, first_update_id: u64 },
+    #[error("Buffered events are not continuous: expected {expected}, received {received}")]
+    BufferGap { expected: u64, received: u64 },
+}
+
+/// Keeps a local order book in sync with the diff depth stream:
+/// events are buffered until a REST snapshot is applied, the first applied event must satisfy
+/// `U <= lastUpdateId + 1 <= u` and every following event must start right after the previous one
+#[derive(Default)]
+pub struct DepthSynchronizer {
+    /// Events received while the order book is not synchronized
+    buffer: Vec<DepthUpdate>,
+    /// Final update ID of the last applied snapshot or event, `None` while not synchronized
+    last_update_id: Option<u64>,
+    /// True until the first event after the snapshot has been applied
+    awaiting_first_event: bool,
+}
+
+impl DepthSynchronizer {
+    /// Forget the local state, e.g. after a reconnect
+    pub fn reset(&mut self) {
+        self.buffer.clear();
+        self.last_update_id = None;
+        self.awaiting_first_event = false;
+    }
+
+    pub fn is_synchronized(&self) -> bool {
+        self.last_update_id.is_some()
+    }
+
+    /// Process a diff depth event received from the stream
+    pub fn push(&mut self, update: DepthUpdate) -> EDepthSyncAction {
+        let Some(last_update_id) = self.last_update_id else {
+            self.buffer_update(update);
+            return EDepthSyncAction::NeedSnapshot;
+        };
+
+        if update.final_update_id <= last_update_id {
+            return EDepthSyncAction::Skip;
+        }
+
+        let expected = last_update_id + 1;
+        let is_continuous = if self.awaiting_first_event {
+            update.first_update_id <= expected
+        } else {
+            update.first_update_id == expected
+        };
+        if !is_continuous {
+            let received = update.first_update_id;
+            self.reset();
+            self.buffer_update(update);
+            return EDepthSyncAction::Gap { expected, received };
+        }
+
+        self.last_update_id = Some(update.final_update_id);
+        self.awaiting_first_event = false;
+        EDepthSyncAction::Apply(update)
+    }
+
+    /// Apply a REST snapshot and return the buffered events that must be applied on top of it
+    pub fn apply_snapshot(&mut self, snapshot_update_id: u64) -> Result<Vec<DepthUpdate>, DepthSyncError> {
+        self.buffer.retain(|update| update.final_update_id > snapshot_update_id);
+
+        if let Some(first) = self.buffer.first() {
+            if first.first_update_id > snapshot_update_id + 1 {
+                return Err(DepthSyncError::SnapshotTooOld {
+                    snapshot_update_id,
+                    first_update_id: first.first_update_id,
+                });
+            }
+        }
+        let gap = self.buffer.windows(2)
+            .find(|pair| pair[1].first_update_id != pair[0].final_update_id + 1)
+            .map(|pair| (pair[0].final_update_id + 1, pair[1].first_update_id));
+        if let Some((expected, received)) = gap {
+            self.reset();
+            return Err(DepthSyncError::BufferGap { expected, received });
+        }
+
+        let updates: Vec<DepthUpdate> = self.buffer.drain(..).collect();
+        self.last_update_id = Some(updates.last().map_or(snapshot_update_id, |update| update.final_update_id));
+        self.awaiting_first_event = updates.is_empty();
+        Ok(updates)
+    }
+
+    fn buffer_update(&mut self, update: DepthUpdate) {
+        if self.buffer.len() >= MAX_BUFFERED_UPDATES {
+            // Only events after the next snapshot matter, so the oldest ones can be dropped
+            self.buffer.remove(0);
+        }
+        self.buffer.push(update);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use super::*;
+
+    fn update(first_update_id: u64, final_update_id: u64) -> DepthUpdate {
+        DepthUpdate {
+            event_type: "depthUpdate".to_string(),
+            event_time: Utc::now(),
+            s: "SOLUSDC".to_string(),
+            first_update_id,
+            final_update_id,
+            b: vec![],
+            a: vec![],
+        }
+    }
+
+    #[test]
+    fn test_buffered_events_are_applied_after_snapshot() {
+        let mut synchronizer = DepthSynchronizer::default();
+        assert!(matches!(synchronizer.push(update(90, 100)), EDepthSyncAction::NeedSnapshot));
+        assert!(matches!(synchronizer.push(update(101, 104)), EDepthSyncAction::NeedSnapshot));
+        assert!(matches!(synchronizer.push(update(105, 106)), EDepthSyncAction::NeedSnapshot));
+
+        let updates = synchronizer.apply_snapshot(102).unwrap();
+        assert_eq!(updates.iter().map(|u| u.final_update_id).collect::<Vec<_>>(), vec![104, 106]);
+        assert!(synchronizer.is_synchronized());
+        assert!(matches!(synchronizer.push(update(107, 107)), EDepthSyncAction::Apply(_)));
+    }
+
+    #[test]
+    fn test_first_event_after_snapshot_must_cover_snapshot() {
+        let mut synchronizer = DepthSynchronizer::default();
+        synchronizer.push(update(90, 100));
+        assert!(synchronizer.apply_snapshot(100).unwrap().is_empty());
+
+        assert!(matches!(synchronizer.push(update(95, 100)), EDepthSyncAction::Skip));
+        assert!(matches!(synchronizer.push(update(99, 103)), EDepthSyncAction::Apply(_)));
+        assert!(matches!(synchronizer.push(update(104, 104)), EDepthSyncAction::Apply(_)));
+    }
+
+    #[test]
+    fn test_snapshot_older_than_buffer_is_rejected() {
+        let mut synchronizer = DepthSynchronizer::default();
+        synchronizer.push(update(110, 120));
+
+        assert_eq!(
+            synchronizer.apply_snapshot(100).unwrap_err(),
+            DepthSyncError::SnapshotTooOld { snapshot_update_id: 100, first_update_id: 110 }
+        );
+        assert!(!synchronizer.is_synchronized());
+        assert!(synchronizer.apply_snapshot(115).is_ok());
+    }
+
+    #[test]
+    fn test_gap_resets_synchronization() {
+        let mut synchronizer = DepthSynchronizer::default();
+        synchronizer.push(update(101, 101));
+        synchronizer.apply_snapshot(100).unwrap();
+
+        match synchronizer.push(update(105, 106)) {
+            EDepthSyncAction::Gap { expected, received } => {
+                assert_eq!(expected, 102);
+                assert_eq!(received, 105);
+            }
+            action => panic!("Expected a gap, got {:?}", action),
+        }
+        assert!(!synchronizer.is_synchronized());
+        // The event that revealed the gap is kept for the next snapshot
+        assert_eq!(synchronizer.apply_snapshot(105).unwrap().len(), 1);
+    }
+}
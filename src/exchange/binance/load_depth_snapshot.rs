@@ -0,0 +1,28 @@
+use tokio::task::JoinHandle;
+use crate::exchange::binance::binance_exchange::BinanceExchange;
+use crate::exchange::binance::rest_depth_snapshot::DepthSnapshot;
+
+/// Number of levels requested for the order book snapshot
+const SNAPSHOT_DEPTH_LIMIT: &str = "1000";
+
+impl BinanceExchange {
+    /// Load the order book snapshot from the depth REST endpoint on its own task, so the stream keeps being read
+    pub(crate) fn spawn_depth_snapshot(&self, symbol: &str) -> JoinHandle<Result<DepthSnapshot, String>> {
+        let url = format!("{}/api/v3/depth", self.rest_url.trim_end_matches('/'));
+        let symbol = symbol.to_string();
+        tokio::spawn(async move {
+            load_depth_snapshot(&url, &symbol).await.map_err(|e| e.to_string())
+        })
+    }
+}
+
+async fn load_depth_snapshot(url: &str, symbol: &str) -> Result<DepthSnapshot, reqwest::Error> {
+    reqwest::Client::new()
+        .get(url)
+        .query(&[("symbol", symbol), ("limit", SNAPSHOT_DEPTH_LIMIT)])
+        .send()
+        .await?
+        .error_for_status()?
+        .json::<DepthSnapshot>()
+        .await
+}
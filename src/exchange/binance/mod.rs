@@ -0,0 +1,7 @@
+pub mod binance_exchange;
+pub mod connect_and_listen;
+pub mod ws_depth_update;
+pub mod rest_depth_snapshot;
+pub mod depth_synchronizer;
+mod process_depth_update;
+mod load_depth_snapshot;
@@ -0,0 +1,73 @@
+use std::collections::BTreeMap;
+use ordered_float::OrderedFloat;
+use tokio::sync::mpsc::Sender;
+use crate::exchange::binance::binance_exchange::BinanceExchange;
+use crate::exchange::binance::rest_depth_snapshot::DepthSnapshot;
+use crate::exchange::binance::ws_depth_update::{DepthUpdate, PriceLevel};
+use crate::exchange::exchange_update::ExchangeUpdate;
+use crate::exchange::order_book::{OrderBook, TPrice, TVolume};
+use crate::exchange::send_orderbook_update::send_orderbook_update;
+
+impl BinanceExchange {
+    /// Replace the local order book with the snapshot and apply the buffered events on top of it
+    pub(crate) async fn process_snapshot(
+        &self,
+        update_sender: &Sender<ExchangeUpdate>,
+        snapshot: DepthSnapshot,
+        updates: Vec<DepthUpdate>)
+    {
+        let mut orderbook = self.orderbook.write().await;
+        *orderbook = OrderBook::new();
+        Self::apply_updates(&mut orderbook.bids, &snapshot.bids);
+        Self::apply_updates(&mut orderbook.asks, &snapshot.asks);
+        orderbook.sequence = snapshot.last_update_id;
+
+        for update in &updates {
+            Self::apply_depth_update(&mut orderbook, update);
+        }
+
+        send_orderbook_update(
+            update_sender,
+            &self.name,
+            &orderbook.get_best_bid(),
+            &orderbook.get_best_ask(),
+            orderbook.last_updated,
+        ).await;
+    }
+
+    /// Apply a diff depth event that passed the synchronization checks
+    pub(crate) async fn process_depth_update(
+        &self,
+        update_sender: &Sender<ExchangeUpdate>,
+        update: DepthUpdate)
+    {
+        let mut orderbook = self.orderbook.write().await;
+        Self::apply_depth_update(&mut orderbook, &update);
+
+        // *** Notify subscribers about the updated orderbook ***
+        send_orderbook_update(
+            update_sender,
+            &self.name,
+            &orderbook.get_best_bid(),
+            &orderbook.get_best_ask(),
+            orderbook.last_updated,
+        ).await;
+    }
+
+    fn apply_depth_update(orderbook: &mut OrderBook, update: &DepthUpdate) {
+        Self::apply_updates(&mut orderbook.bids, &update.b);
+        Self::apply_updates(&mut orderbook.asks, &update.a);
+        orderbook.sequence = update.final_update_id;
+        orderbook.last_updated = update.event_time;
+    }
+
+    fn apply_updates(side: &mut BTreeMap<TPrice, TVolume>, updates: &[PriceLevel]) {
+        for PriceLevel(price, quantity) in updates {
+            if *quantity == 0.0 {
+                side.remove(&OrderedFloat(*price));
+            } else {
+                side.insert(OrderedFloat(*price), *quantity);
+            }
+        }
+    }
+}
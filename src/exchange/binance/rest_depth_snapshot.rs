@@ -0,0 +1,38 @@
+use serde::Deserialize;
+use crate::exchange::binance::ws_depth_update::PriceLevel;
+
+/// Response of the `/api/v3/depth` endpoint
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepthSnapshot {
+    /// Update ID the snapshot corresponds to
+    pub last_update_id: u64,
+    pub bids: Vec<PriceLevel>,
+    pub asks: Vec<PriceLevel>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn test_depth_snapshot_deserialization() {
+        let json_data = r#"
+        {
+            "lastUpdateId":1027024,
+            "bids":[["143.52","431.00"]],
+            "asks":[["143.55","12.00"]]
+        }
+        "#;
+
+        let result = serde_json::from_str::<DepthSnapshot>(json_data);
+
+        assert!(result.is_ok(), "Failed to parse JSON: {:?}", result.err());
+
+        let snapshot = result.unwrap();
+        assert_eq!(snapshot.last_update_id, 1027024);
+        assert_eq!(snapshot.bids[0].0, 143.52);
+        assert_eq!(snapshot.asks[0].1, 12.0);
+    }
+}
@@ -0,0 +1,101 @@
+use std::str::FromStr;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer};
+
+/// Represents a diff depth stream event
+#[allow(dead_code)]
+#[derive(Clone, Debug, Deserialize)]
+pub struct DepthUpdate {
+    /// Event type, always `depthUpdate`
+    #[serde(rename = "e")]
+    pub event_type: String,
+    /// Event time
+    #[serde(rename = "E", with = "timestamp_ms")]
+    pub event_time: DateTime<Utc>,
+    /// Symbol
+    pub s: String,
+    /// First update ID in event
+    #[serde(rename = "U")]
+    pub first_update_id: u64,
+    /// Final update ID in event
+    #[serde(rename = "u")]
+    pub final_update_id: u64,
+    /// Bids to be updated
+    pub b: Vec<PriceLevel>,
+    /// Asks to be updated
+    pub a: Vec<PriceLevel>,
+}
+
+/// Represents a price level in the order book
+///
+/// The first element (index 0) is the price
+/// The second element (index 1) is the quantity, zero quantity means the level must be removed
+#[derive(Clone, Debug)]
+pub struct PriceLevel(pub f64, pub f64);
+
+impl<'de> Deserialize<'de> for PriceLevel {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (price_str, qty_str): (String, String) = Deserialize::deserialize(deserializer)?;
+
+        let parse_float = |s: &str, field: &str| {
+            f64::from_str(s).map_err(|e| {
+                serde::de::Error::custom(format!("Failed to parse {} '{}' as f64: {}", field, s, e))
+            })
+        };
+
+        Ok(PriceLevel(parse_float(&price_str, "price")?, parse_float(&qty_str, "quantity")?))
+    }
+}
+
+mod timestamp_ms {
+    use chrono::{DateTime, TimeZone, Utc};
+    use serde::{self, Deserialize, Deserializer};
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let ms = i64::deserialize(deserializer)?;
+        Utc.timestamp_millis_opt(ms)
+            .single()
+            .ok_or_else(|| serde::de::Error::custom("invalid timestamp"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn test_depth_update_deserialization() {
+        let json_data = r#"
+        {
+            "e":"depthUpdate",
+            "E":1724318672920,
+            "s":"SOLUSDC",
+            "U":157,
+            "u":160,
+            "b":[["143.52","10.5"]],
+            "a":[["143.55","0.00"],["143.56","3.2"]]
+        }
+        "#;
+
+        let result = serde_json::from_str::<DepthUpdate>(json_data);
+
+        assert!(result.is_ok(), "Failed to parse JSON: {:?}", result.err());
+
+        let update = result.unwrap();
+        assert_eq!(update.event_time, Utc.timestamp_millis_opt(1724318672920).unwrap());
+        assert_eq!(update.first_update_id, 157);
+        assert_eq!(update.final_update_id, 160);
+        assert_eq!(update.b[0].0, 143.52);
+        assert_eq!(update.b[0].1, 10.5);
+        assert_eq!(update.a.len(), 2);
+        assert_eq!(update.a[0].1, 0.0);
+    }
+}
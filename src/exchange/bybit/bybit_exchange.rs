@@ -1,17 +1,33 @@
 use std::sync::Arc;
-use std::time::Duration;
+use chrono::TimeDelta;
 use async_trait::async_trait;
 use tokio::sync::mpsc::Sender;
 use tokio::sync::RwLock;
-use tokio::time::sleep;
 use crate::exchange::exchange::Exchange;
 use crate::exchange::exchange_update::{ExchangeUpdate};
+use crate::exchange::instrument_rules::InstrumentRules;
 use crate::exchange::order_book::OrderBook;
+use crate::exchange::trade_tape::TradeTape;
 use crate::trading_pair::ETradingPair;
 
+/// How long public trades are kept in the trade tape
+const TRADE_TAPE_MAX_AGE: TimeDelta = TimeDelta::minutes(5);
+
 pub struct BybitExchange {
     pub(crate) name: String,
     pub(crate) orderbook: Arc<RwLock<OrderBook>>,
+    pub(crate) trade_tape: Arc<RwLock<TradeTape>>,
+    pub(crate) instrument_rules: RwLock<Option<InstrumentRules>>,
+}
+
+impl BybitExchange {
+    /// Returns the Bybit symbol of the trading pair
+    pub(crate) fn symbol(trading_pair: &ETradingPair) -> String {
+        match trading_pair {
+            ETradingPair::BtcUsdc => "BTCUSDC".to_string(),
+            ETradingPair::SolUsdc => "SOLUSDC".to_string(),
+        }
+    }
 }
 
 #[async_trait]
@@ -20,6 +36,8 @@ impl Exchange for BybitExchange {
         BybitExchange {
             name: "Bybit".to_string(),
             orderbook: Arc::new(RwLock::new(OrderBook::new())),
+            trade_tape: Arc::new(RwLock::new(TradeTape::new(TRADE_TAPE_MAX_AGE))),
+            instrument_rules: RwLock::new(None),
         }
     }
 
@@ -28,25 +46,32 @@ impl Exchange for BybitExchange {
     }
 
     async fn start(&self, trading_pair: ETradingPair, order_book_update_sender: Sender<ExchangeUpdate>) {
-        loop {
-            println!("[INFO][Bybit] Starting exchange websocket...");
-            match self.connect_and_listen(&trading_pair, &order_book_update_sender).await {
-                Ok(_) => {
-                    println!("[WARNING][Bybit] Bybit WebSocket connection has been closed");
-                }
-                Err(e) => {
-                    eprintln!("[ERROR][Bybit] Error in Bybit WebSocket connection: {:?}", e);
-                }
+        let symbol = Self::symbol(&trading_pair);
+        let instrument_rules = match self.load_instrument_rules(&symbol).await {
+            Ok(rules) => {
+                println!("[INFO][Bybit] Loaded instrument rules for {}: {:?}", symbol, rules);
+                Some(rules)
+            }
+            Err(e) => {
+                eprintln!("[ERROR][Bybit] Failed to load instrument rules for {}: {}", symbol, e);
+                None
             }
+        };
+        *self.instrument_rules.write().await = instrument_rules;
 
-            // If we're here, it means the connection was closed or an error occurred
-            // Wait for a short time before attempting to reconnect
-            sleep(Duration::from_secs(1)).await;
-            println!("[INFO][Bybit] Attempting to reconnect to WebSocket...");
-        }
+        println!("[INFO][Bybit] Starting exchange websocket...");
+        self.connect_and_listen(&trading_pair, &order_book_update_sender).await;
     }
 
     fn get_order_book(&self) -> Arc<RwLock<OrderBook>> {
         self.orderbook.clone()
     }
+
+    fn get_trade_tape(&self) -> Option<Arc<RwLock<TradeTape>>> {
+        Some(self.trade_tape.clone())
+    }
+
+    async fn get_instrument_rules(&self) -> Option<InstrumentRules> {
+        self.instrument_rules.read().await.clone()
+    }
 }
\ No newline at end of file
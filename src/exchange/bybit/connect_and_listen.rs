@@ -1,73 +1,76 @@
 use std::env;
-use std::time::{Duration, Instant};
-use tokio::time::interval;
-use futures_util::{SinkExt, StreamExt};
+use async_trait::async_trait;
 use tokio::sync::mpsc::Sender;
-use tokio_tungstenite::{connect_async, tungstenite::protocol::Message};
+use tokio_tungstenite::tungstenite::protocol::Message;
 use crate::exchange::bybit::bybit_exchange::BybitExchange;
 use crate::exchange::bybit::ws_ping::PingMessage;
 use crate::exchange::bybit::ws_pong::PongMessage;
+use crate::exchange::bybit::ws_public_trade::PublicTradeUpdate;
 use crate::exchange::bybit::ws_spot_orderbook::{OrderBookUpdate};
 use crate::exchange::bybit::ws_spot_subscribe::SubscribeRequest;
 use crate::exchange::bybit::ws_subscribe_response::SubscribeResponse;
 use crate::exchange::exchange_update::{ExchangeUpdate};
+use crate::exchange::reconnect_policy::ReconnectPolicy;
+use crate::exchange::ws_connection::ws_connection_manager::run_ws_connection;
+use crate::exchange::ws_connection::ws_protocol::{EHeartbeat, EWsMessage, WsProtocol};
 use crate::trading_pair::ETradingPair;
 
-const PING_INTERVAL: Duration = Duration::from_secs(1);
-const PONG_TIMEOUT: Duration = Duration::from_secs(5);
+/// Bybit spot public stream protocol: JSON subscribe and JSON ping/pong
+struct BybitWsProtocol<'a> {
+    exchange: &'a BybitExchange,
+    instrument: String,
+    update_sender: &'a Sender<ExchangeUpdate>,
+}
 
-impl BybitExchange {
-    pub async fn connect_and_listen(
-        &self,
-        trading_pair: &ETradingPair,
-        order_book_update_sender: &Sender<ExchangeUpdate>,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        let url = env::var("BYBIT_WS_URL").expect("BYBIT_WS_URL must be set");
-        let instrument = match trading_pair {
-            ETradingPair::BtcUsdc => "BTCUSDC".to_string(),
-            ETradingPair::SolUsdc => "SOLUSDC".to_string(),
-        };
-
-        let (ws_stream, _) = connect_async(url).await?;
-        let (mut write, mut read) = ws_stream.split();
+#[async_trait]
+impl WsProtocol for BybitWsProtocol<'_> {
+    fn name(&self) -> String {
+        self.exchange.name.clone()
+    }
 
-        // Subscribe to the orderbook
-        let subscribe_request = SubscribeRequest::new(&[instrument]);
-        write.send(Message::Text(serde_json::to_string(&subscribe_request)?)).await?;
+    fn url(&self) -> String {
+        env::var("BYBIT_WS_URL").expect("BYBIT_WS_URL must be set")
+    }
 
-        let mut ping_interval = interval(PING_INTERVAL);
-        let mut last_pong = Instant::now();
+    fn subscribe_messages(&self) -> Result<Vec<Message>, Box<dyn std::error::Error>> {
+        // Subscribe to the orderbook and public trades
+        let subscribe_request = SubscribeRequest::new(std::slice::from_ref(&self.instrument));
+        Ok(vec![Message::Text(serde_json::to_string(&subscribe_request)?)])
+    }
 
-        loop {
-            tokio::select! {
-                _ = ping_interval.tick() => {
-                    let ping = PingMessage::new(None);
-                    write.send(Message::Text(serde_json::to_string(&ping)?)).await?;
-                }
-                Some(message) = read.next() => {
-                    match message? {
-                        Message::Text(text) => {
-                            if let Ok(orderbook_update) = serde_json::from_str::<OrderBookUpdate>(&text) {
-                                self.process_orderbook_update(order_book_update_sender, orderbook_update).await;
-                            } else if let Ok(_pong) = serde_json::from_str::<PongMessage>(&text) {
-                                last_pong = Instant::now();
-                            } else if let Ok(subscribe_response) = serde_json::from_str::<SubscribeResponse>(&text) {
-                                println!("Subscribed to: {:?}", subscribe_response);
-                            } else {
-                                println!("Received unknown message: {}", text);
-                            }
-                        }
-                        _ => {}
-                    }
-                }
-            }
+    fn heartbeat(&self) -> EHeartbeat {
+        let ping = PingMessage::new(None);
+        EHeartbeat::Text(serde_json::to_string(&ping).expect("Ping message is serializable"))
+    }
 
-            if last_pong.elapsed() > PONG_TIMEOUT {
-                println!("[ERROR][Bybit] No pong received within timeout, reconnecting...");
-                break;
-            }
+    async fn handle_text(&self, text: &str) -> EWsMessage {
+        if let Ok(orderbook_update) = serde_json::from_str::<OrderBookUpdate>(text) {
+            self.exchange.process_orderbook_update(self.update_sender, orderbook_update).await;
+        } else if let Ok(trade_update) = serde_json::from_str::<PublicTradeUpdate>(text) {
+            self.exchange.process_trade_update(self.update_sender, trade_update).await;
+        } else if let Ok(_pong) = serde_json::from_str::<PongMessage>(text) {
+            return EWsMessage::Pong;
+        } else if let Ok(subscribe_response) = serde_json::from_str::<SubscribeResponse>(text) {
+            println!("Subscribed to: {:?}", subscribe_response);
+        } else {
+            return EWsMessage::Unknown;
         }
+        EWsMessage::Handled
+    }
+}
 
-        Ok(())
+impl BybitExchange {
+    /// Maintain the WebSocket connection until the reconnect budget is exhausted
+    pub async fn connect_and_listen(
+        &self,
+        trading_pair: &ETradingPair,
+        order_book_update_sender: &Sender<ExchangeUpdate>,
+    ) {
+        let protocol = BybitWsProtocol {
+            exchange: self,
+            instrument: Self::symbol(trading_pair),
+            update_sender: order_book_update_sender,
+        };
+        run_ws_connection(&protocol, ReconnectPolicy::default(), order_book_update_sender).await;
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,33 @@
+use std::env;
+use crate::exchange::bybit::bybit_exchange::BybitExchange;
+use crate::exchange::bybit::rest_instruments_info::InstrumentsInfoResponse;
+use crate::exchange::instrument_rules::InstrumentRules;
+
+impl BybitExchange {
+    /// Load the trading rules of the spot instrument from the instruments-info REST endpoint
+    pub(crate) async fn load_instrument_rules(&self, symbol: &str) -> Result<InstrumentRules, Box<dyn std::error::Error>> {
+        let url = env::var("BYBIT_REST_URL").expect("BYBIT_REST_URL must be set");
+        let response = reqwest::Client::new()
+            .get(format!("{}/v5/market/instruments-info", url.trim_end_matches('/')))
+            .query(&[("category", "spot"), ("symbol", symbol)])
+            .send()
+            .await?
+            .json::<InstrumentsInfoResponse>()
+            .await?;
+
+        if response.ret_code != 0 {
+            return Err(format!("Bybit instruments-info error {}: {}", response.ret_code, response.ret_msg).into());
+        }
+        let info = response.result.list
+            .into_iter()
+            .find(|info| info.symbol == symbol)
+            .ok_or_else(|| format!("Instrument {} not found on Bybit", symbol))?;
+
+        Ok(InstrumentRules {
+            tick_size: info.price_filter.tick_size,
+            lot_size: info.lot_size_filter.base_precision,
+            min_qty: info.lot_size_filter.min_order_qty,
+            min_notional: info.lot_size_filter.min_order_amt,
+        })
+    }
+}
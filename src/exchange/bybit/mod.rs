@@ -1,8 +1,12 @@
 pub mod bybit_exchange;
 pub mod connect_and_listen;
 pub mod ws_spot_orderbook;
+pub mod ws_public_trade;
 pub mod ws_ping;
 pub mod ws_pong;
 pub mod ws_spot_subscribe;
 pub mod ws_subscribe_response;
-mod process_orderbook_update;
\ No newline at end of file
+pub mod rest_instruments_info;
+mod process_orderbook_update;
+mod process_trade_update;
+mod load_instrument_rules;
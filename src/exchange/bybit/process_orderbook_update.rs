@@ -49,10 +49,11 @@ impl BybitExchange {
 
         // *** Notify subscribers about the updated orderbook ***
         send_orderbook_update(
-            &update_sender,
+            update_sender,
             &self.name,
             &orderbook.get_best_bid(),
             &orderbook.get_best_ask(),
+            orderbook.last_updated,
         ).await;
     }
     fn apply_updates(&self, side: &mut BTreeMap<TPrice, TVolume>, updates: &[PriceLevel]) {
@@ -0,0 +1,32 @@
+use ordered_float::OrderedFloat;
+use tokio::sync::mpsc::Sender;
+use crate::exchange::bybit::bybit_exchange::BybitExchange;
+use crate::exchange::bybit::ws_public_trade::{PublicTradeUpdate, TakerSide};
+use crate::exchange::exchange_update::ExchangeUpdate;
+use crate::exchange::send_trade_update::send_trade_update;
+use crate::exchange::trade_tape::{EAggressorSide, Trade};
+
+impl BybitExchange {
+    pub(crate) async fn process_trade_update(
+        &self,
+        update_sender: &Sender<ExchangeUpdate>,
+        update: PublicTradeUpdate)
+    {
+        for public_trade in update.data {
+            let trade = Trade {
+                price: OrderedFloat(public_trade.price),
+                size: public_trade.size,
+                side: match public_trade.side {
+                    TakerSide::Buy => EAggressorSide::Buy,
+                    TakerSide::Sell => EAggressorSide::Sell,
+                },
+                timestamp: public_trade.trade_time,
+                slot: None,
+            };
+            self.trade_tape.write().await.push(trade.clone());
+
+            // *** Notify subscribers about the new trade ***
+            send_trade_update(update_sender, &self.name, trade).await;
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use serde::Deserialize;
+use crate::exchange::bybit::ws_public_trade::deserialize_f64_from_str;
+
+/// Response of the `/v5/market/instruments-info` endpoint
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstrumentsInfoResponse {
+    /// Success/Error code, 0 means success
+    pub ret_code: i64,
+    /// Success/Error message
+    pub ret_msg: String,
+    pub result: InstrumentsInfoResult,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+pub struct InstrumentsInfoResult {
+    /// Product type
+    pub category: String,
+    pub list: Vec<InstrumentInfo>,
+}
+
+/// Represents the trading rules of a single spot instrument
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstrumentInfo {
+    /// Symbol name
+    pub symbol: String,
+    /// Instrument status
+    pub status: String,
+    pub lot_size_filter: LotSizeFilter,
+    pub price_filter: PriceFilter,
+}
+
+/// Size attributes
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LotSizeFilter {
+    /// The precision of base coin
+    #[serde(deserialize_with = "deserialize_f64_from_str")]
+    pub base_precision: f64,
+    /// The precision of quote coin
+    #[serde(deserialize_with = "deserialize_f64_from_str")]
+    pub quote_precision: f64,
+    /// Minimum order quantity
+    #[serde(deserialize_with = "deserialize_f64_from_str")]
+    pub min_order_qty: f64,
+    /// Maximum order quantity
+    #[serde(deserialize_with = "deserialize_f64_from_str")]
+    pub max_order_qty: f64,
+    /// Minimum order amount
+    #[serde(deserialize_with = "deserialize_f64_from_str")]
+    pub min_order_amt: f64,
+    /// Maximum order amount
+    #[serde(deserialize_with = "deserialize_f64_from_str")]
+    pub max_order_amt: f64,
+}
+
+/// Price attributes
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PriceFilter {
+    /// The step to increase/reduce order price
+    #[serde(deserialize_with = "deserialize_f64_from_str")]
+    pub tick_size: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn test_instruments_info_deserialization() {
+        let json_data = r#"
+        {
+            "retCode":0,
+            "retMsg":"OK",
+            "result":{
+                "category":"spot",
+                "list":[
+                    {
+                        "symbol":"SOLUSDC",
+                        "baseCoin":"SOL",
+                        "quoteCoin":"USDC",
+                        "innovation":"0",
+                        "status":"Trading",
+                        "marginTrading":"both",
+                        "lotSizeFilter":{
+                            "basePrecision":"0.001",
+                            "quotePrecision":"0.0000001",
+                            "minOrderQty":"0.007",
+                            "maxOrderQty":"7213.02397",
+                            "minOrderAmt":"1",
+                            "maxOrderAmt":"1000000"
+                        },
+                        "priceFilter":{
+                            "tickSize":"0.01"
+                        }
+                    }
+                ]
+            },
+            "retExtInfo":{},
+            "time":1724318672920
+        }
+        "#;
+
+        let result = serde_json::from_str::<InstrumentsInfoResponse>(json_data);
+
+        assert!(result.is_ok(), "Failed to parse JSON: {:?}", result.err());
+
+        let response = result.unwrap();
+        assert_eq!(response.ret_code, 0);
+        assert_eq!(response.result.list.len(), 1);
+
+        let info = &response.result.list[0];
+        assert_eq!(info.symbol, "SOLUSDC");
+        assert_eq!(info.lot_size_filter.base_precision, 0.001);
+        assert_eq!(info.lot_size_filter.min_order_qty, 0.007);
+        assert_eq!(info.lot_size_filter.min_order_amt, 1.0);
+        assert_eq!(info.price_filter.tick_size, 0.01);
+    }
+}
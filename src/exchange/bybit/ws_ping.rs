@@ -14,7 +14,7 @@ impl PingMessage {
     pub fn new(req_id: Option<String>) -> Self {
         PingMessage {
             op: "ping".to_string(),
-            req_id: req_id.unwrap_or_else(|| String::new()),
+            req_id: req_id.unwrap_or_default(),
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,127 @@
+use std::str::FromStr;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer};
+use crate::exchange::bybit::ws_spot_orderbook::timestamp_ms;
+
+/// Represents a public trade update
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+pub struct PublicTradeUpdate {
+    /// Topic name
+    pub topic: String,
+    /// The timestamp when the system generates this update message
+    #[serde(with = "timestamp_ms")]
+    pub ts: DateTime<Utc>,
+    /// Data type. Always `snapshot`
+    #[serde(rename = "type")]
+    pub update_type: String,
+    /// Trades sorted by time in ascending order
+    pub data: Vec<PublicTrade>,
+}
+
+/// Side of the taker
+#[derive(Debug, Deserialize, PartialEq)]
+pub enum TakerSide {
+    Buy,
+    Sell,
+}
+
+/// Represents a single public trade
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+pub struct PublicTrade {
+    /// The timestamp when the order is filled
+    #[serde(rename = "T", with = "timestamp_ms")]
+    pub trade_time: DateTime<Utc>,
+    /// Symbol name
+    pub s: String,
+    /// Side of taker
+    #[serde(rename = "S")]
+    pub side: TakerSide,
+    /// Trade size
+    #[serde(rename = "v", deserialize_with = "deserialize_f64_from_str")]
+    pub size: f64,
+    /// Trade price
+    #[serde(rename = "p", deserialize_with = "deserialize_f64_from_str")]
+    pub price: f64,
+    /// Trade ID
+    pub i: String,
+    /// Whether it is a block trade order or not
+    #[serde(rename = "BT")]
+    pub block_trade: bool,
+}
+
+pub(super) fn deserialize_f64_from_str<'de, D>(deserializer: D) -> Result<f64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    f64::from_str(&s).map_err(|e| {
+        serde::de::Error::custom(format!("Failed to parse '{}' as f64: {}", s, e))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn test_public_trade_update_deserialization() {
+        let json_data = r#"
+        {
+            "topic":"publicTrade.SOLUSDC",
+            "type":"snapshot",
+            "ts":1724318672920,
+            "data":[
+                {
+                    "i":"2290000000067123781",
+                    "T":1724318672918,
+                    "p":"143.52",
+                    "v":"1.386",
+                    "S":"Sell",
+                    "s":"SOLUSDC",
+                    "BT":false
+                }
+            ]
+        }
+        "#;
+
+        let result = serde_json::from_str::<PublicTradeUpdate>(json_data);
+
+        assert!(result.is_ok(), "Failed to parse JSON: {:?}", result.err());
+
+        let update = result.unwrap();
+        assert_eq!(update.topic, "publicTrade.SOLUSDC");
+        assert_eq!(update.data.len(), 1);
+
+        let trade = &update.data[0];
+        assert_eq!(trade.trade_time, Utc.timestamp_millis_opt(1724318672918).unwrap());
+        assert_eq!(trade.side, TakerSide::Sell);
+        assert_eq!(trade.price, 143.52);
+        assert_eq!(trade.size, 1.386);
+        assert!(!trade.block_trade);
+    }
+
+    #[test]
+    fn test_orderbook_update_is_not_public_trade() {
+        let json_data = r#"
+        {
+            "topic":"orderbook.50.SOLUSDC",
+            "ts":1724318672920,
+            "type":"delta",
+            "data":{
+                "s":"SOLUSDC",
+                "b":[["143.5","0.016796"]],
+                "a":[],
+                "u":45468681,
+                "seq":46063562942
+            },
+            "cts":1724318672915
+        }
+        "#;
+
+        assert!(serde_json::from_str::<PublicTradeUpdate>(json_data).is_err());
+    }
+}
@@ -74,7 +74,7 @@ impl<'de> Deserialize<'de> for PriceLevel {
     }
 }
 
-mod timestamp_ms {
+pub(super) mod timestamp_ms {
     use chrono::{DateTime, TimeZone, Utc};
     use serde::{self, Deserialize, Deserializer};
 
@@ -11,12 +11,15 @@ pub struct SubscribeRequest {
 }
 
 impl SubscribeRequest {
-    /// Creates a new subscription request for the given symbols
+    /// Creates a new subscription request to the order book and public trades for the given symbols
     pub(crate) fn new(symbols: &[String]) -> Self {
         let depth = 50;
         let args = symbols
             .iter()
-            .map(|symbol| format!("orderbook.{}.{}", depth, symbol))
+            .flat_map(|symbol| [
+                format!("orderbook.{}.{}", depth, symbol),
+                format!("publicTrade.{}", symbol),
+            ])
             .collect();
 
         SubscribeRequest {
@@ -0,0 +1,43 @@
+use chrono::{DateTime, Utc};
+
+/// State of an exchange connection
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EConnectionState {
+    Connecting,
+    Connected,
+    Reconnecting,
+    /// The circuit breaker is open, the exchange gave up reconnecting
+    Failed,
+}
+
+/// Connection attempt statistics of an exchange
+#[derive(Clone, Debug)]
+pub struct ConnectionStats {
+    pub state: EConnectionState,
+    /// Total number of connection attempts
+    pub attempts: u64,
+    /// Number of attempts that resulted in an established connection
+    pub successful_connections: u64,
+    /// Number of connections that ended with an error
+    pub failures: u64,
+    /// Number of attempts since the last stable connection
+    pub consecutive_failures: u32,
+    pub last_error: Option<String>,
+    pub last_connected: Option<DateTime<Utc>>,
+    pub last_disconnected: Option<DateTime<Utc>>,
+}
+
+impl ConnectionStats {
+    pub(crate) fn new() -> Self {
+        ConnectionStats {
+            state: EConnectionState::Connecting,
+            attempts: 0,
+            successful_connections: 0,
+            failures: 0,
+            consecutive_failures: 0,
+            last_error: None,
+            last_connected: None,
+            last_disconnected: None,
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use chrono::DateTime;
+use crate::exchange::dexnow::data_structures::candle::{Candle, ECandleInterval};
+use crate::exchange::dexnow::dexnow_engine::DEXnowEngine;
+
+const CSV_HEADER: &str = "time,open,high,low,close,volume,turnover,trades";
+
+impl DEXnowEngine {
+    /// Load the bars of the instrument and write them to `<dir>/dexnow_<instr_id>_<interval>.csv`
+    pub async fn export_candles_csv(
+        &self,
+        instr_id: u64,
+        interval: ECandleInterval,
+        dir: &Path,
+    ) -> Result<usize, Box<dyn std::error::Error>> {
+        let candles = self.candles(instr_id, interval).await?;
+        let path = dir.join(format!("dexnow_{}_{}.csv", instr_id, interval));
+        write_candles_csv(&candles, BufWriter::new(File::create(&path)?))?;
+        println!("[INFO][DEXnow] Exported {} {} candles to {}", candles.len(), interval, path.display());
+        Ok(candles.len())
+    }
+}
+
+/// Write the bars as CSV with RFC 3339 UTC times
+pub fn write_candles_csv(candles: &[Candle], mut writer: impl Write) -> std::io::Result<()> {
+    writeln!(writer, "{}", CSV_HEADER)?;
+    for candle in candles {
+        let time = DateTime::from_timestamp(candle.time as i64, 0).unwrap_or_default();
+        writeln!(
+            writer,
+            "{},{},{},{},{},{},{},{}",
+            time.to_rfc3339(),
+            candle.open,
+            candle.high,
+            candle.low,
+            candle.close,
+            candle.volume,
+            candle.turnover,
+            candle.trades,
+        )?;
+    }
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_candles_csv() {
+        let candles = vec![Candle {
+            time: 1_700_000_040,
+            open: 150.0,
+            high: 151.0,
+            low: 149.5,
+            close: 150.5,
+            volume: 2.0,
+            turnover: 301.0,
+            trades: 3,
+        }];
+        let mut csv = Vec::new();
+
+        write_candles_csv(&candles, &mut csv).unwrap();
+
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "time,open,high,low,close,volume,turnover,trades\n2023-11-14T22:14:00+00:00,150,151,149.5,150.5,2,301,3\n"
+        );
+    }
+}
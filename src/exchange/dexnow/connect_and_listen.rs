@@ -1,86 +1,585 @@
-use std::env;
-use std::time::{Duration, Instant};
-use tokio::time::interval;
-use futures_util::{SinkExt, StreamExt};
-use tokio_tungstenite::{connect_async, tungstenite::protocol::Message};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::Instant;
+use async_trait::async_trait;
+use chrono::Utc;
+use futures::future::join_all;
+use tokio_tungstenite::tungstenite::protocol::Message;
 use solana_sdk::pubkey::Pubkey;
-use solana_sdk::commitment_config::CommitmentLevel;
+use solana_sdk::commitment_config::{CommitmentConfig, CommitmentLevel};
+use crate::exchange::dexnow::data_structures::constants::*;
+use crate::exchange::dexnow::data_structures::instrument::Instrument;
+use crate::exchange::dexnow::data_structures::full_book::{EBookAccount, EBookDepth, FullBook};
+use crate::exchange::dexnow::data_structures::market_accounts::MarketAccounts;
+use crate::exchange::dexnow::data_structures::pending_slots::PendingSlots;
+use crate::exchange::dexnow::decode_client::client_open_orders;
+use crate::exchange::dexnow::decode_futures::apply_futures_book;
+use crate::exchange::dexnow::decode_trace::new_trace_records;
 use crate::exchange::dexnow::dexnow_engine::DEXnowEngine;
-use crate::exchange::dexnow::solana::account_notification::AccountNotification;
-use crate::exchange::dexnow::solana::account_subscribe::SubscribeMessage;
-use crate::exchange::dexnow::solana::subscription_response::SubscriptionResponse;
+use crate::exchange::dexnow::process_orderbook_update::ProcessOrderbookError;
+use crate::exchange::solana::pubsub_client::{EPubSubMessage, ESubscriptionTarget, PubSubClient};
+use crate::exchange::solana::slot_dedup::{EDelivery, SlotDedup};
+use crate::exchange::reconnect_policy::ReconnectPolicy;
+use crate::exchange::send_trade_update::send_trade_update;
+use crate::exchange::ws_connection::ws_connection_manager::run_ws_connection;
+use crate::exchange::ws_connection::ws_protocol::{EHeartbeat, EWsMessage, WsProtocol};
 
-const PING_INTERVAL: Duration = Duration::from_secs(1);
-const PONG_TIMEOUT: Duration = Duration::from_secs(5);
+/// Notifications delivered more slots than this behind the cluster are reported, finalized ones always lag
+const MAX_SLOT_LAG: u64 = 8;
+/// Slots finalized notifications lag behind the processed slots reported by the slot subscription
+const FINALIZED_SLOT_LAG: u64 = 40;
 
-impl DEXnowEngine {
-    pub async fn connect_and_listen(
-        &self,
-        account_pubkey: &Pubkey
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        let ws_url = env::var("SOLANA_WS_URL").expect("SOLANA_WS_URL must be set");
+/// Markets of the instrument with a full book
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum EBookMarket {
+    Spot,
+    /// Futures market of the derivative with the given index
+    Futures(usize),
+}
+
+/// Accounts of the instrument the driver subscribes to
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum EDEXnowAccount {
+    InstrDynamic,
+    Book(EBookMarket, EBookAccount),
+    /// Maps account with the options data of the derivative with the given index
+    OptionsMaps(usize),
+    /// Trace account with the recent spot trades
+    Trace,
+    /// Primary account of the client with its token balances
+    ClientPrimary,
+    /// Slots processed by the node, not an account: they date the notifications of the accounts
+    Slot,
+}
+
+/// Accounts of the DEXnow instrument and their latest data, shared by the PubSub connections
+/// At `EBookDepth::Top` the spot book comes from the dynamic account, otherwise it is rebuilt from the
+/// tree, lines and (at `EBookDepth::Orders`) orders accounts. Futures books are always rebuilt from their
+/// accounts, the ones of `DEXnowEngine::futures_books` are published under their own name. Market statistics
+/// and options pool data come from the dynamic account and options strikes from the maps account of every
+/// derivative. New trades of the trace account are pushed to the trade tape.
+/// A book is only published once every account notification of its slot has arrived, when an account
+/// of a later slot is notified or the slot subscription has moved past it. With processed commitment the
+/// accounts of the recent published slots are kept: a notification of a lower slot than the latest change
+/// means the fork was abandoned, the accounts are restored to the slot before it and the book is rebuilt.
+/// With a client loaded, its balances come from the primary account and its open orders from the spot
+/// orders accounts, which are then tracked at any depth
+struct DEXnowListener<'a> {
+    engine: &'a DEXnowEngine,
+    instrument: &'a Instrument,
+    /// Accounts loaded over HTTP before every connection
+    accounts: Vec<(Pubkey, EDEXnowAccount)>,
+    /// Latest data of the spot book accounts
+    spot_accounts: std::sync::Mutex<MarketAccounts>,
+    /// Latest data of the futures book accounts, one per derivative
+    futures_accounts: std::sync::Mutex<Vec<MarketAccounts>>,
+    /// ID of the newest trade read from the trace account, kept across reconnects to catch up on missed trades
+    last_trace_id: std::sync::Mutex<Option<u64>>,
+    /// Applied slots of the accounts, in race mode the connections deliver the same notifications
+    deliveries: SlotDedup<EDEXnowAccount>,
+    /// Markets whose book accounts changed in a slot not complete yet
+    pending_books: std::sync::Mutex<PendingSlots<EBookMarket>>,
+    /// Racing connections subscribed and delivering notifications
+    live_connections: AtomicUsize,
+    /// True once no racing connection is live, the next racing connection reloads the accounts
+    reload_needed: AtomicBool,
+}
+
+/// Solana PubSub protocol for the DEXnow instrument accounts: JSON-RPC subscribe and WebSocket Ping frames
+/// A single connection follows the active endpoint and fails over to the next one when it is lost,
+/// in race mode every endpoint has its own connection
+struct DEXnowWsProtocol<'a> {
+    listener: &'a DEXnowListener<'a>,
+    /// Endpoint raced by the connection, `None` to follow the active endpoint
+    endpoint: Option<usize>,
+    /// Subscriptions to the accounts, multiplexed over one connection
+    pubsub: PubSubClient<EDEXnowAccount>,
+    /// When the subscription requests were sent, until the first one is confirmed
+    subscribing_since: std::sync::Mutex<Option<Instant>>,
+    /// True while the connection is subscribed
+    live: AtomicBool,
+}
+
+impl DEXnowListener<'_> {
+    /// Run `f` on the book accounts of the market, None for an unknown derivative
+    fn with_market_accounts<R>(&self, market: EBookMarket, f: impl FnOnce(&mut MarketAccounts) -> R) -> Option<R> {
+        match market {
+            EBookMarket::Spot => Some(f(&mut self.spot_accounts.lock().unwrap())),
+            EBookMarket::Futures(i) => self.futures_accounts.lock().unwrap().get_mut(i).map(f),
+        }
+    }
 
-        loop {
-            let dyn_acc = self.connection.get_account(&account_pubkey).await;
-            if let Ok(dyn_acc) = dyn_acc {
-                let dyn_data = self.decode_instr_dynamic_account(&dyn_acc.data);
-                let _ = self.process_orderbook_update(&dyn_data, 0).await;
+    /// Keep the latest data of a book account, accounts with an unexpected header are skipped
+    /// With processed commitment a slot lower than the latest change of the market rolls its accounts back
+    fn store_book_account(&self, market: EBookMarket, account: EBookAccount, data: Vec<u8>, slot: u64) {
+        if let Err(e) = self.engine.check_account_header(&data, book_account_tag(market, account)) {
+            eprintln!("[ERROR][DEXnow] Skipped {:?} {:?} account update: {}", market, account, e);
+            return;
+        }
+        let processed = self.engine.commitment == CommitmentLevel::Processed;
+        self.with_market_accounts(market, |accounts| {
+            if processed && slot < accounts.slot {
+                let abandoned_slot = accounts.slot;
+                let restored_slot = accounts.roll_back_before(slot);
+                println!("[WARNING][DEXnow] Fork abandoned: {:?} {:?} account of slot {} after slot {}, restored the {:?} accounts of slot {}",
+                         market, account, slot, abandoned_slot, market, restored_slot.map_or("none".to_string(), |slot| slot.to_string()));
             }
-            println!("[INFO][DEXnow] Connecting to Solana WebSocket to listen account {}...", account_pubkey);
-            let (ws_stream, _) = connect_async(&ws_url).await?;
-            let (mut write, mut read) = ws_stream.split();
+            accounts.set(account, data, slot);
+        });
+    }
 
-            let subscribe_message = SubscribeMessage::new(account_pubkey, CommitmentLevel::Confirmed);
-            let json_message = serde_json::to_string(&subscribe_message).unwrap();
+    /// Top spot lines at `EBookDepth::Top`, the spot and futures statistics and the options pool data
+    async fn process_dynamic_account(&self, data: &[u8], slot: u64) {
+        if let Err(e) = self.engine.check_account_header(data, INSTR_DYNAMIC_TAG) {
+            eprintln!("[ERROR][DEXnow] Skipped dynamic account update: {}", e);
+            return;
+        }
+        if self.engine.book_depth == EBookDepth::Top {
+            match self.engine.decode_instr_dynamic_account(data) {
+                Ok(decoded_account) => {
+                    let _ = self.engine.process_orderbook_update(&decoded_account.spot_bids, &decoded_account.spot_asks, slot).await;
+                }
+                Err(e) => eprintln!("[ERROR][DEXnow] Failed to decode top of the book: {}", e),
+            }
+        }
+        let mut live_instrument = self.engine.live_instrument.write().await;
+        let Some(instrument) = live_instrument.as_mut() else {
+            return;
+        };
+        if let Err(e) = self.engine.decode_spot_stats(data, instrument) {
+            eprintln!("[ERROR][DEXnow] Failed to decode spot statistics: {}", e);
+        }
+        if !instrument.derivatives.is_empty() {
+            if let Err(e) = self.engine.decode_futures_stats(data, instrument) {
+                eprintln!("[ERROR][DEXnow] Failed to decode futures statistics: {}", e);
+            }
+            if let Err(e) = self.engine.decode_options_pool(data, instrument) {
+                eprintln!("[ERROR][DEXnow] Failed to decode options pool: {}", e);
+            }
+        }
+    }
 
-            write.send(Message::Text(json_message)).await?;
-            println!("[INFO][DEXnow] Subscribed to account: {}", account_pubkey);
+    async fn process_options_update(&self, derivative_index: usize, data: &[u8]) {
+        let mut live_instrument = self.engine.live_instrument.write().await;
+        let Some(derivative) = live_instrument.as_mut().and_then(|instrument| instrument.derivatives.get_mut(derivative_index)) else {
+            return;
+        };
+        if let Err(e) = self.engine.decode_derivative_options(self.instrument, data, derivative) {
+            eprintln!("[ERROR][DEXnow] Failed to decode options of derivative {}: {}", derivative_index, e);
+        }
+    }
 
-            let mut ping_interval = interval(PING_INTERVAL);
-            let mut last_pong = Instant::now();
-            let mut ping_msg: u8 = 0;
+    /// Push the trades newer than the last seen one to the trade tape and send them to the manager
+    /// The trades found on the first load are history and only fill the tape
+    async fn process_trace_update(&self, data: &[u8]) {
+        let records = match self.engine.decode_trace(self.instrument, data) {
+            Ok(records) => records,
+            Err(e) => {
+                eprintln!("[ERROR][DEXnow] Failed to decode trace account: {}", e);
+                return;
+            }
+        };
+        let last_id = *self.last_trace_id.lock().unwrap();
+        for record in new_trace_records(records, last_id) {
+            *self.last_trace_id.lock().unwrap() = Some(record.id);
+            self.engine.trade_tape.write().await.push(record.trade.clone());
+            if let (Some(update_sender), Some(_)) = (self.engine.update_sender.as_ref(), last_id) {
+                send_trade_update(update_sender, &self.engine.name, record.trade).await;
+            }
+        }
+    }
 
-            loop {
-                tokio::select! {
-                    _ = ping_interval.tick() => {
-                        ping_msg = ping_msg.wrapping_add(1);
-                        write.send(Message::Ping(vec![ping_msg])).await?;
-                    }
-                    Some(message) = read.next() => {
-                        match message? {
-                            Message::Text(text) => {
-                                if let Ok(_subscription) = serde_json::from_str::<SubscriptionResponse>(&text) {
-                                    // println!("[INFO][DEXnow] Subscription confirmed: {:?}", subscription);
-                                } else if let Ok(notification) = serde_json::from_str::<AccountNotification>(&text) {
-                                    // println!("Account notification: {:?}", notification.params.subscription);
-                                    let account_data = notification.params.result.value.data;
-                                    let decoded_account = self.decode_instr_dynamic_account(&account_data);
-                                    let _ = self.process_orderbook_update(&decoded_account, notification.params.result.context.slot).await;
-                                } else {
-                                    println!("[WARNING][DEXnow] Received unknown message format");
-                                }
-                            }
-                            Message::Pong(response) => {
-                                if response.len() > 0 && response[0] == ping_msg {
-                                    last_pong = Instant::now();
-                                    continue;
-                                }
-                            }
-                            _ => {}
-                        }
-                    }
+    /// Replace the token balances of the client
+    async fn process_client_update(&self, data: &[u8]) {
+        let balances = match self.engine.decode_client_primary_account(data) {
+            Ok(decoded) => decoded.balances,
+            Err(e) => {
+                eprintln!("[ERROR][DEXnow] Failed to decode client primary account: {}", e);
+                return;
+            }
+        };
+        if let Some(client) = self.engine.client.write().await.as_mut() {
+            client.balances = balances;
+        }
+    }
+
+    /// Rebuild the full book of the market from the latest accounts data, None until its accounts are known
+    /// With processed commitment the accounts are kept as the version of their slot, to roll back to
+    fn rebuild_book(&self, market: EBookMarket) -> Option<FullBook> {
+        let processed = self.engine.commitment == CommitmentLevel::Processed;
+        let book = self.with_market_accounts(market, |accounts| {
+            if processed {
+                accounts.keep_version();
+            }
+            self.engine.decode_full_book(self.instrument, &accounts.accounts)
+        })?;
+        book.unwrap_or_else(|e| {
+            eprintln!("[ERROR][DEXnow] Failed to decode {:?} book: {}", market, e);
+            None
+        })
+    }
+
+    /// Rebuild the books whose accounts last changed in `slot` or before and publish them
+    /// Every book is rebuilt before the first one is published, so no change of a later slot slips in meanwhile
+    async fn publish_settled_books(&self, slot: u64) {
+        let settled = self.pending_books.lock().unwrap().settle(slot);
+        let books: Vec<(EBookMarket, u64, FullBook)> = settled.into_iter()
+            .filter_map(|(market, slot)| self.rebuild_book(market).map(|book| (market, slot, book)))
+            .collect();
+        for (market, slot, book) in books {
+            self.publish_book(market, book, slot).await;
+        }
+    }
+
+    /// Publish the book of the market as of the slot
+    async fn publish_book(&self, market: EBookMarket, book: FullBook, slot: u64) {
+        match market {
+            EBookMarket::Spot => {
+                // At `EBookDepth::Top` the spot book is only tracked for the client orders
+                if self.engine.book_depth != EBookDepth::Top {
+                    let bids = book.bids.iter().map(|book_line| &book_line.line);
+                    let asks = book.asks.iter().map(|book_line| &book_line.line);
+                    let _ = self.engine.process_orderbook_update(bids, asks, slot).await;
                 }
+                if let Some(client) = self.engine.client.write().await.as_mut() {
+                    client.open_orders = client_open_orders(client.id, self.instrument.id, &book);
+                }
+                *self.engine.spot_book.write().await = book;
+            }
+            EBookMarket::Futures(i) => {
+                let bids = book.bids.iter().map(|book_line| &book_line.line);
+                let asks = book.asks.iter().map(|book_line| &book_line.line);
+                let _ = self.engine.process_futures_orderbook_update(i, bids, asks, slot).await;
+                let mut live_instrument = self.engine.live_instrument.write().await;
+                if let Some(derivative) = live_instrument.as_mut().and_then(|instrument| instrument.derivatives.get_mut(i)) {
+                    apply_futures_book(&mut derivative.futures, book);
+                }
+            }
+        }
+    }
+
+    /// Dispatch the notification of an account to its decoder
+    /// The notifications of the previous slots are complete, their books are published first
+    async fn process_account_update(&self, account: EDEXnowAccount, slot: u64, data: Vec<u8>) {
+        self.publish_settled_books(slot.saturating_sub(1)).await;
+        match account {
+            EDEXnowAccount::InstrDynamic => self.process_dynamic_account(&data, slot).await,
+            EDEXnowAccount::Book(market, book_account) => {
+                self.store_book_account(market, book_account, data, slot);
+                self.pending_books.lock().unwrap().mark(market, slot);
+            }
+            EDEXnowAccount::OptionsMaps(i) => self.process_options_update(i, &data).await,
+            EDEXnowAccount::Trace => self.process_trace_update(&data).await,
+            EDEXnowAccount::ClientPrimary => self.process_client_update(&data).await,
+            EDEXnowAccount::Slot => {}
+        }
+    }
+
+    /// Report account notifications delivered long after their slot was produced
+    fn check_slot_lag(&self, account: EDEXnowAccount, slot: u64) {
+        if self.engine.commitment == CommitmentLevel::Finalized {
+            return;
+        }
+        let slot_clock = self.engine.slot_clock.lock().unwrap();
+        let lag = slot_clock.current_slot().map_or(0, |current_slot| current_slot.saturating_sub(slot));
+        if lag > MAX_SLOT_LAG {
+            println!("[WARNING][DEXnow] {:?} update of slot {} delivered {} slots (~{} ms) behind the cluster",
+                     account, slot, lag, slot_clock.slot_duration().num_milliseconds() * lag as i64);
+        }
+    }
+
+    /// Load the accounts over HTTP, notifications only arrive on changes
+    async fn load_accounts(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.pending_books.lock().unwrap().clear();
+        *self.spot_accounts.lock().unwrap() = MarketAccounts::default();
+        *self.futures_accounts.lock().unwrap() = self.instrument.derivatives.iter().map(|_| MarketAccounts::default()).collect();
+        *self.engine.live_instrument.write().await = Some(self.instrument.clone());
 
-                if last_pong.elapsed() > PONG_TIMEOUT {
-                    println!("[ERROR][DEXnow] No pong received within timeout, reconnecting...");
-                    break;
+        let mut dynamic_account = None;
+        let mut options_accounts = Vec::new();
+        let mut trace_account = None;
+        let mut client_account = None;
+        for requests in self.accounts.chunks(MAX_MULTIPLE_ACCOUNTS) {
+            let pubkeys: Vec<Pubkey> = requests.iter().map(|(pubkey, _)| *pubkey).collect();
+            let commitment = CommitmentConfig { commitment: self.engine.commitment };
+            let accounts = self.engine.connection().get_multiple_accounts_with_commitment(&pubkeys, commitment).await?.value;
+            for ((_, account), data) in requests.iter().zip(accounts) {
+                let Some(data) = data else {
+                    continue;
+                };
+                match *account {
+                    EDEXnowAccount::InstrDynamic => dynamic_account = Some(data.data),
+                    EDEXnowAccount::Book(market, account) => self.store_book_account(market, account, data.data, 0),
+                    EDEXnowAccount::OptionsMaps(i) => options_accounts.push((i, data.data)),
+                    EDEXnowAccount::Trace => trace_account = Some(data.data),
+                    EDEXnowAccount::ClientPrimary => client_account = Some(data.data),
+                    EDEXnowAccount::Slot => {}
                 }
             }
+        }
+        if let Some(data) = dynamic_account {
+            self.process_dynamic_account(&data, 0).await;
+        }
+        for (i, data) in options_accounts {
+            self.process_options_update(i, &data).await;
+        }
+        if let Some(data) = client_account {
+            self.process_client_update(&data).await;
+        }
+        for market in self.book_markets() {
+            if let Some(book) = self.rebuild_book(market) {
+                self.publish_book(market, book, 0).await;
+            }
+        }
+        if let Some(data) = trace_account {
+            self.process_trace_update(&data).await;
+        }
+        Ok(())
+    }
+
+    /// Connection subscribing to every account, on the given endpoint or on the active one
+    fn protocol(&self, endpoint: Option<usize>) -> DEXnowWsProtocol<'_> {
+        let pubsub = PubSubClient::new(self.engine.commitment);
+        pubsub.add(ESubscriptionTarget::Slot, EDEXnowAccount::Slot);
+        for (pubkey, account) in &self.accounts {
+            pubsub.add(ESubscriptionTarget::Account(*pubkey), *account);
+        }
+        DEXnowWsProtocol { listener: self, endpoint, pubsub, subscribing_since: std::sync::Mutex::new(None), live: AtomicBool::new(false) }
+    }
+
+    /// Slot whose account notifications are complete once the slot subscription reports `slot`
+    /// The subscription reports processed slots, confirmed and finalized notifications arrive later
+    fn settled_slot(&self, slot: u64) -> u64 {
+        let lag = match self.engine.commitment {
+            CommitmentLevel::Processed => 1,
+            CommitmentLevel::Confirmed => MAX_SLOT_LAG,
+            CommitmentLevel::Finalized => FINALIZED_SLOT_LAG,
+        };
+        slot.saturating_sub(lag)
+    }
+
+    /// Markets whose full book is rebuilt
+    fn book_markets(&self) -> Vec<EBookMarket> {
+        let spot = self.engine.tracks_spot_book().then_some(EBookMarket::Spot);
+        spot.into_iter().chain((0..self.instrument.derivatives.len()).map(EBookMarket::Futures)).collect()
+    }
+}
+
+impl DEXnowWsProtocol<'_> {
+    fn endpoint(&self) -> usize {
+        self.endpoint.unwrap_or_else(|| self.listener.engine.ws_endpoints.active())
+    }
+
+    /// Load the accounts over HTTP before connecting. Racing connections share the accounts loaded before
+    /// the race and only reload them once no racing connection is live, otherwise they would wipe the
+    /// accounts and books kept up to date by the live ones
+    async fn load_accounts(&self) -> Result<(), Box<dyn std::error::Error>> {
+        if self.endpoint.is_none() {
+            return self.listener.load_accounts().await;
+        }
+        if !self.listener.reload_needed.swap(false, Ordering::SeqCst) {
+            return Ok(());
+        }
+        self.listener.load_accounts().await.inspect_err(|_| self.listener.reload_needed.store(true, Ordering::SeqCst))
+    }
+}
+
+#[async_trait]
+impl WsProtocol for DEXnowWsProtocol<'_> {
+    fn name(&self) -> String {
+        let engine = self.listener.engine;
+        match self.endpoint {
+            Some(endpoint) => format!("{}@{}", engine.name, engine.ws_endpoints.label(endpoint)),
+            None => engine.name.clone(),
+        }
+    }
+
+    fn url(&self) -> String {
+        self.listener.engine.ws_endpoints.url(self.endpoint()).to_string()
+    }
+
+    async fn on_connecting(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.listener.engine.rpc.health_check().await?;
+        self.listener.engine.log_endpoint_stats();
+        self.load_accounts().await?;
+        println!("[INFO][DEXnow] Connecting to Solana WebSocket to listen {} accounts of instrument {}...", self.pubsub.len(), self.listener.instrument.id);
+        Ok(())
+    }
+
+    fn on_disconnected(&self, error: Option<&str>) {
+        let ws_endpoints = &self.listener.engine.ws_endpoints;
+        let endpoint = self.endpoint();
+        ws_endpoints.record_failure(endpoint, error.unwrap_or("connection closed").to_string());
+        if self.live.swap(false, Ordering::SeqCst) {
+            self.listener.live_connections.fetch_sub(1, Ordering::SeqCst);
+        }
+        if self.endpoint.is_some() && self.listener.live_connections.load(Ordering::SeqCst) == 0 {
+            self.listener.reload_needed.store(true, Ordering::SeqCst);
+        }
+        if self.endpoint.is_none() {
+            ws_endpoints.fail_over(endpoint);
+        }
+    }
+
+    fn subscribe_messages(&self) -> Result<Vec<Message>, Box<dyn std::error::Error>> {
+        *self.subscribing_since.lock().unwrap() = Some(Instant::now());
+        Ok(self.pubsub.subscribe_messages()?)
+    }
+
+    fn heartbeat(&self) -> EHeartbeat {
+        EHeartbeat::PingFrame
+    }
 
-            // If we're here, it means the connection was closed or an error occurred
-            // Wait for a short time before attempting to reconnect
-            tokio::time::sleep(Duration::from_secs(1)).await;
-            println!("[INFO][DEXnow] Attempting to reconnect to DEXnow WebSocket...");
+    async fn handle_text(&self, text: &str) -> EWsMessage {
+        let engine = self.listener.engine;
+        match self.pubsub.handle_text(text) {
+            Some(EPubSubMessage::Subscribed { key, target }) => {
+                if let Some(since) = self.subscribing_since.lock().unwrap().take() {
+                    engine.ws_endpoints.record_subscribed(self.endpoint(), since.elapsed());
+                }
+                if !self.live.swap(true, Ordering::SeqCst) {
+                    self.listener.live_connections.fetch_add(1, Ordering::SeqCst);
+                }
+                println!("[INFO][DEXnow] Subscribed to {:?} {}", key, target);
+            }
+            Some(EPubSubMessage::Account { key, slot, data }) => {
+                if engine.race_mode {
+                    let delivery = self.listener.deliveries.claim(key, slot, Instant::now());
+                    engine.ws_endpoints.record_delivery(self.endpoint(), delivery);
+                    if delivery != EDelivery::First {
+                        return EWsMessage::Handled;
+                    }
+                }
+                self.listener.check_slot_lag(key, slot);
+                self.listener.process_account_update(key, slot, data).await;
+            }
+            Some(EPubSubMessage::Slot { slot, root, .. }) => {
+                engine.slot_clock.lock().unwrap().on_slot(slot, Utc::now());
+                engine.confirm_rooted_books(root).await;
+                self.listener.publish_settled_books(self.listener.settled_slot(slot)).await;
+            }
+            Some(EPubSubMessage::Unsubscribed | EPubSubMessage::Stale) => {}
+            Some(EPubSubMessage::Program { .. }) | None => return EWsMessage::Unknown,
+        }
+        EWsMessage::Handled
+    }
+}
+
+impl DEXnowEngine {
+    /// True if the full spot book is rebuilt, for the book itself or for the open orders of the client
+    fn tracks_spot_book(&self) -> bool {
+        self.book_depth != EBookDepth::Top || self.client_primary_account.is_some()
+    }
+
+    /// Print the health and latency of every endpoint, when there are several
+    fn log_endpoint_stats(&self) {
+        if self.rpc.len() == 1 && self.ws_endpoints.len() == 1 {
+            return;
+        }
+        let (rpc_stats, ws_stats) = self.endpoint_stats();
+        for stats in rpc_stats {
+            println!("[INFO][DEXnow] RPC endpoint {}", stats);
+        }
+        for stats in ws_stats {
+            println!("[INFO][DEXnow] PubSub endpoint {}", stats);
         }
     }
-}
\ No newline at end of file
+
+    /// Listen to the instrument accounts until the reconnect budget is exhausted, of every endpoint in race mode
+    pub async fn connect_and_listen(
+        &self,
+        instrument: &Instrument,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let update_sender = self.update_sender.as_ref()
+            .ok_or(ProcessOrderbookError::UpdateSenderNotInitialized)?;
+        if self.ws_endpoints.is_empty() {
+            return Err("SOLANA_WS_URLS or SOLANA_WS_URL must be set".into());
+        }
+        let with_orders = self.book_depth == EBookDepth::Orders;
+        let with_client_orders = self.client_primary_account.is_some();
+        let mut requests = Vec::new();
+        requests.push((instrument.dynamic_account, EDEXnowAccount::InstrDynamic));
+        if let Some(trace_account) = instrument.trace_account {
+            requests.push((trace_account, EDEXnowAccount::Trace));
+        }
+        if let Some(client_primary_account) = self.client_primary_account {
+            requests.push((client_primary_account, EDEXnowAccount::ClientPrimary));
+        }
+        if self.tracks_spot_book() {
+            let spot = &instrument.spot;
+            requests.extend(book_requests(
+                EBookMarket::Spot,
+                [spot.bids_tree_account, spot.asks_tree_account, spot.lines_account, spot.bid_orders_account, spot.ask_orders_account],
+                with_orders || with_client_orders,
+            ));
+        }
+        for (i, derivative) in instrument.derivatives.iter().enumerate() {
+            let futures = &derivative.futures;
+            requests.extend(book_requests(
+                EBookMarket::Futures(i),
+                [futures.bids_tree_account, futures.asks_tree_account, futures.lines_account, futures.bid_orders_account, futures.ask_orders_account],
+                with_orders,
+            ));
+            requests.push((futures.maps_account, EDEXnowAccount::OptionsMaps(i)));
+        }
+        let listener = DEXnowListener {
+            engine: self,
+            instrument,
+            accounts: requests,
+            spot_accounts: std::sync::Mutex::new(MarketAccounts::default()),
+            futures_accounts: std::sync::Mutex::new(Vec::new()),
+            last_trace_id: std::sync::Mutex::new(None),
+            deliveries: SlotDedup::default(),
+            pending_books: std::sync::Mutex::new(PendingSlots::default()),
+            live_connections: AtomicUsize::new(0),
+            reload_needed: AtomicBool::new(false),
+        };
+        if self.race_mode && self.ws_endpoints.len() > 1 {
+            // The racing connections report their state under their own name, the books stay fresh while any of them delivers
+            println!("[INFO][DEXnow] Racing {} Solana WebSocket endpoints", self.ws_endpoints.len());
+            // The accounts are loaded once for every racing connection, a failed load is retried by the first one connecting
+            let loaded = match self.rpc.health_check().await {
+                Ok(()) => listener.load_accounts().await.map_err(|e| e.to_string()),
+                Err(e) => Err(e.to_string()),
+            };
+            if let Err(e) = loaded {
+                eprintln!("[ERROR][DEXnow] Failed to load the accounts before racing: {}", e);
+                listener.reload_needed.store(true, Ordering::SeqCst);
+            }
+            let protocols: Vec<DEXnowWsProtocol> = (0..self.ws_endpoints.len())
+                .map(|endpoint| listener.protocol(Some(endpoint)))
+                .collect();
+            join_all(protocols.iter().map(|protocol| run_ws_connection(protocol, ReconnectPolicy::default(), update_sender))).await;
+        } else {
+            run_ws_connection(&listener.protocol(None), ReconnectPolicy::default(), update_sender).await;
+        }
+        Err("Reconnect budget exhausted, DEXnow exchange marked as failed".into())
+    }
+}
+
+/// Subscribe requests for the book accounts of a market: bids tree, asks tree, lines and,
+/// if the orders are needed, bid orders and ask orders
+fn book_requests(market: EBookMarket, accounts: [Pubkey; 5], with_orders: bool) -> Vec<(Pubkey, EDEXnowAccount)> {
+    let kinds = [EBookAccount::BidsTree, EBookAccount::AsksTree, EBookAccount::Lines, EBookAccount::BidOrders, EBookAccount::AskOrders];
+    let count = if with_orders { 5 } else { 3 };
+    accounts.into_iter()
+        .zip(kinds)
+        .take(count)
+        .map(|(pubkey, kind)| (pubkey, EDEXnowAccount::Book(market, kind)))
+        .collect()
+}
+
+/// Tag of a book account of the market
+fn book_account_tag(market: EBookMarket, account: EBookAccount) -> u8 {
+    match (market, account) {
+        (EBookMarket::Spot, EBookAccount::BidsTree) => SPOT_BIDS_TREE_TAG,
+        (EBookMarket::Spot, EBookAccount::AsksTree) => SPOT_ASKS_TREE_TAG,
+        (EBookMarket::Spot, EBookAccount::BidOrders) => SPOT_BID_ORDERS_TAG,
+        (EBookMarket::Spot, EBookAccount::AskOrders) => SPOT_ASK_ORDERS_TAG,
+        (EBookMarket::Spot, EBookAccount::Lines) => SPOT_LINES_TAG,
+        (EBookMarket::Futures(_), EBookAccount::BidsTree) => FUTURES_BIDS_TREE_TAG,
+        (EBookMarket::Futures(_), EBookAccount::AsksTree) => FUTURES_ASKS_TREE_TAG,
+        (EBookMarket::Futures(_), EBookAccount::BidOrders) => FUTURES_BID_ORDERS_TAG,
+        (EBookMarket::Futures(_), EBookAccount::AskOrders) => FUTURES_ASK_ORDERS_TAG,
+        (EBookMarket::Futures(_), EBookAccount::Lines) => FUTURES_LINES_TAG,
+    }
+}
@@ -0,0 +1,86 @@
+#![allow(dead_code)]
+use std::collections::VecDeque;
+use crate::exchange::dexnow::data_structures::full_book::BookAccounts;
+
+/// Number of recent slots whose book accounts are kept, processed slots are rolled back well before that
+pub const BOOK_HISTORY_SLOTS: usize = 32;
+
+/// Book accounts of a market as of a slot
+#[derive(Debug, Clone, PartialEq)]
+pub struct BookVersion {
+    pub slot: u64,
+    pub accounts: BookAccounts,
+}
+
+/// Book accounts of the recent slots, used to roll back when a fork with processed slots is abandoned
+#[derive(Debug, Default)]
+pub struct BookHistory {
+    versions: VecDeque<BookVersion>,
+}
+
+impl BookHistory {
+    pub fn clear(&mut self) {
+        self.versions.clear();
+    }
+
+    /// Keep the accounts of the slot, replacing the previous version of the same slot
+    pub fn push(&mut self, version: BookVersion) {
+        if self.versions.back().is_some_and(|last| last.slot == version.slot) {
+            self.versions.pop_back();
+        }
+        self.versions.push_back(version);
+        if self.versions.len() > BOOK_HISTORY_SLOTS {
+            self.versions.pop_front();
+        }
+    }
+
+    /// Drop the versions of the slots after `slot` and return the version the state rolls back to, if still kept
+    pub fn rollback_to(&mut self, slot: u64) -> Option<&BookVersion> {
+        while self.versions.back().is_some_and(|last| last.slot > slot) {
+            self.versions.pop_back();
+        }
+        self.versions.back()
+    }
+
+    pub fn len(&self) -> usize {
+        self.versions.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(slot: u64, lines: u8) -> BookVersion {
+        BookVersion { slot, accounts: BookAccounts { lines: Some(vec![lines]), ..BookAccounts::default() } }
+    }
+
+    #[test]
+    fn test_rollback_drops_abandoned_slots() {
+        let mut history = BookHistory::default();
+        history.push(version(100, 10));
+        history.push(version(101, 11));
+        history.push(version(102, 12));
+
+        let restored = history.rollback_to(100).cloned();
+
+        assert_eq!(restored, Some(version(100, 10)));
+        assert_eq!(history.len(), 1);
+    }
+
+    #[test]
+    fn test_push_replaces_same_slot_and_keeps_recent_slots() {
+        let mut history = BookHistory::default();
+        history.push(version(1, 1));
+        history.push(version(1, 2));
+        assert_eq!(history.len(), 1);
+
+        for slot in 2..=(BOOK_HISTORY_SLOTS as u64 + 1) {
+            history.push(version(slot, 1));
+        }
+
+        assert_eq!(history.len(), BOOK_HISTORY_SLOTS);
+        // The oldest slot is gone, nothing is left to roll back to
+        assert_eq!(history.rollback_to(1), None);
+    }
+}
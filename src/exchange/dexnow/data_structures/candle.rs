@@ -0,0 +1,47 @@
+use std::fmt;
+use crate::exchange::dexnow::data_structures::constants::{DAY_CANDLES_CAPACITY, M15_CANDLES_CAPACITY, M1_CANDLES_CAPACITY};
+
+/// Contains data about an OHLCV bar of the spot market
+#[derive(Debug, Clone, PartialEq)]
+pub struct Candle {
+    /// Start time of the bar in seconds since epoch
+    pub time: u64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    /// Asset tokens volume
+    pub volume: f64,
+    /// Base currency tokens volume
+    pub turnover: f64,
+    pub trades: u32,
+}
+
+/// Intervals of the candle accounts
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ECandleInterval {
+    M1,
+    M15,
+    Day,
+}
+
+impl ECandleInterval {
+    /// Number of bars the ring buffer of the account holds
+    pub fn capacity(&self) -> usize {
+        match self {
+            ECandleInterval::M1 => M1_CANDLES_CAPACITY as usize,
+            ECandleInterval::M15 => M15_CANDLES_CAPACITY as usize,
+            ECandleInterval::Day => DAY_CANDLES_CAPACITY as usize,
+        }
+    }
+}
+
+impl fmt::Display for ECandleInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ECandleInterval::M1 => write!(f, "1m"),
+            ECandleInterval::M15 => write!(f, "15m"),
+            ECandleInterval::Day => write!(f, "1d"),
+        }
+    }
+}
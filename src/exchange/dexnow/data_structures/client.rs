@@ -0,0 +1,47 @@
+#![allow(dead_code)]
+use solana_sdk::pubkey::Pubkey;
+
+/// Side of an order resting in the book
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EOrderSide {
+    Bid,
+    Ask,
+}
+
+/// Amount of a token the client holds inside DEXnow
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClientBalance {
+    pub token_id: u64,
+    pub amount: f64,
+}
+
+/// Spot order of the client resting in the book of an instrument
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClientOrder {
+    pub instr_id: u64,
+    pub side: EOrderSide,
+    /// Order ID
+    pub id: u64,
+    pub px: f64,
+    /// Remaining quantity
+    pub qty: f64,
+    /// Time of placement in seconds since epoch
+    pub time: u32,
+}
+
+/// Contains data about the client of the configured wallet
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Client {
+    /// Owner wallet
+    pub wallet: Pubkey,
+    /// Client ID the orders of the client are marked with
+    pub id: u32,
+    pub primary_account: Pubkey,
+    pub drv_account: Pubkey,
+    pub dexnow_account: Pubkey,
+    pub lut_account: Option<Pubkey>,
+    /// Token balances inside DEXnow
+    pub balances: Vec<ClientBalance>,
+    /// Open spot orders of the listened instrument
+    pub open_orders: Vec<ClientOrder>,
+}
@@ -1,4 +1,11 @@
 #![allow(dead_code)]
+//! Tags, sizes and byte offsets of the DEXnow accounts.
+//!
+//! The account tags and the root, token, instrument static, task static and base currency layouts come from the
+//! DEXnow TypeScript library ported to this crate. The other layouts (book header, trees, lines, orders,
+//! instrument dynamic, tasks, maps, strikes, candles, trace and client accounts) have no published source.
+//! `test_layouts_match_live_instrument_accounts` in the layouts module checks the spot accounts of a live
+//! instrument against them. The options, candles, trace and client records are not covered by it yet.
 use std::str::FromStr;
 use lazy_static::lazy_static;
 use solana_sdk::pubkey::Pubkey;
@@ -45,6 +52,8 @@ pub const MAPS_SIZE: u32 = 42160;
 pub const MARKET_DEPTH: usize = 20;
 pub const MAX_DURATION: u8 = 28;
 pub const MAX_LINES: u16 = 2048;
+/// Maximum number of accounts in one getMultipleAccounts request
+pub const MAX_MULTIPLE_ACCOUNTS: usize = 100;
 pub const MAX_ORDERS: u16 = 14336;
 pub const PK_SIZE: u8 = 32;
 pub const ROOT_TAG: u8 = 2;
@@ -63,6 +72,7 @@ pub const SPOT_LINES_TAG: u8 = 18;
 pub const SPOT_MAPS_TAG: u8 = 10;
 pub const STRIKES_COUNT: u8 = 100;
 pub const TOKEN_TAG: u32 = 4;
+pub const TRACE_CAPACITY: u16 = 256;
 pub const U32_SIZE: u8 = 4;
 pub const U8_SIZE: u8 = 1;
 
@@ -72,6 +82,10 @@ pub const BASE_CRNCY_FUNDS_OFFSET: usize = 8;
 pub const BASE_CRNCY_RATE_OFFSET: usize = 16;
 pub const BASE_CRNCY_SIZE: usize = 24;
 
+/// Every account starts with its tag and the version of the root account
+pub const ACCOUNT_TAG_OFFSET: usize = 0;
+pub const ACCOUNT_VERSION_OFFSET: usize = 4;
+
 pub const ROOT_ACCOUNT_TAG_OFFSET: usize = 0;
 pub const ROOT_ACCOUNT_VERSION_OFFSET: usize = 4;
 pub const ROOT_ACCOUNT_OPERATOR_ADDRESS_OFFSET: usize = 8;
@@ -143,5 +157,143 @@ pub const TASK_STATIC_SIZE: usize = 296;
 
 
 pub const INSTR_DYNAMIC_ACCOUNT_ID_OFFSET: usize = 40;
+pub const INSTR_DYNAMIC_ACCOUNT_POOL_TOKENS_SUPPLY_OFFSET: usize = 48;
+pub const INSTR_DYNAMIC_ACCOUNT_ASSET_TOKENS_IN_POOL_OFFSET: usize = 56;
+pub const INSTR_DYNAMIC_ACCOUNT_BASE_CRNCY_TOKENS_IN_POOL_OFFSET: usize = 64;
+pub const INSTR_DYNAMIC_ACCOUNT_ASSET_TOKEN_FEES_IN_POOL_OFFSET: usize = 72;
+pub const INSTR_DYNAMIC_ACCOUNT_BASE_CRNCY_TOKEN_FEES_IN_POOL_OFFSET: usize = 80;
+pub const INSTR_DYNAMIC_ACCOUNT_DAY_SIGMA_OFFSET: usize = 88;
+pub const INSTR_DYNAMIC_ACCOUNT_DAY_SIGMA2_OFFSET: usize = 96;
+pub const INSTR_DYNAMIC_ACCOUNT_HOUR_SIGMA_OFFSET: usize = 104;
+pub const INSTR_DYNAMIC_ACCOUNT_LAST_HOUR_PX_OFFSET: usize = 112;
+pub const INSTR_DYNAMIC_ACCOUNT_LAST_PX_OFFSET: usize = 120;
+pub const INSTR_DYNAMIC_ACCOUNT_LAST_CLOSE_PX_OFFSET: usize = 128;
+pub const INSTR_DYNAMIC_ACCOUNT_FIXING_PX_OFFSET: usize = 136;
+pub const INSTR_DYNAMIC_ACCOUNT_DAY_ASSET_TOKENS_OFFSET: usize = 144;
+pub const INSTR_DYNAMIC_ACCOUNT_DAY_BASE_CRNCY_TOKENS_OFFSET: usize = 152;
+pub const INSTR_DYNAMIC_ACCOUNT_DAY_TRADES_OFFSET: usize = 160;
+pub const INSTR_DYNAMIC_ACCOUNT_ALLTIME_ASSET_TOKENS_OFFSET: usize = 168;
+pub const INSTR_DYNAMIC_ACCOUNT_ALLTIME_BASE_CRNCY_TOKENS_OFFSET: usize = 176;
+pub const INSTR_DYNAMIC_ACCOUNT_ALLTIME_TRADES_OFFSET: usize = 184;
+pub const INSTR_DYNAMIC_ACCOUNT_LAST_ASSET_TOKENS_OFFSET: usize = 192;
+pub const INSTR_DYNAMIC_ACCOUNT_LAST_BASE_CRNCY_TOKENS_OFFSET: usize = 200;
+pub const INSTR_DYNAMIC_ACCOUNT_LAST_TRADE_TIME_OFFSET: usize = 208;
+pub const INSTR_DYNAMIC_ACCOUNT_OPTIONS_POOL_EST_PX_OFFSET: usize = 368;
+pub const INSTR_DYNAMIC_ACCOUNT_OPTIONS_POOL_SUPPLY_OFFSET: usize = 376;
 pub const INSTR_DYNAMIC_ACCOUNT_BIDS_OFFSET: usize = 384;
-pub const INSTR_DYNAMIC_ACCOUNT_ASKS_OFFSET: usize = 704;
\ No newline at end of file
+pub const INSTR_DYNAMIC_ACCOUNT_ASKS_OFFSET: usize = 704;
+pub const INSTR_DYNAMIC_ACCOUNT_TASKS_OFFSET: usize = 1024;
+
+pub const TASK_DYNAMIC_INSTANCE_ID_OFFSET: usize = 0;
+pub const TASK_DYNAMIC_EXPIRATION_OFFSET: usize = 4;
+pub const TASK_DYNAMIC_FUTURES_OPEN_INT_OFFSET: usize = 8;
+pub const TASK_DYNAMIC_FUTURES_LAST_PX_OFFSET: usize = 16;
+pub const TASK_DYNAMIC_FUTURES_LAST_CLOSE_PX_OFFSET: usize = 24;
+pub const TASK_DYNAMIC_FUTURES_LAST_FUTURES_OFFSET: usize = 32;
+pub const TASK_DYNAMIC_FUTURES_APR_OFFSET: usize = 40;
+pub const TASK_DYNAMIC_FUTURES_LAST_TRADE_TIME_OFFSET: usize = 48;
+pub const TASK_DYNAMIC_FUTURES_DAY_TRADES_OFFSET: usize = 52;
+pub const TASK_DYNAMIC_SIZE: usize = 56;
+
+pub const NULL_LINE: u32 = 0xFFFF;
+
+pub const BOOK_ACCOUNT_TAG_OFFSET: usize = 0;
+pub const BOOK_ACCOUNT_VERSION_OFFSET: usize = 4;
+pub const BOOK_ACCOUNT_INSTR_ID_OFFSET: usize = 8;
+pub const BOOK_ACCOUNT_HEADER_SIZE: usize = 16;
+
+pub const TREE_ACCOUNT_ROOT_OFFSET: usize = 12;
+pub const TREE_NODE_LEFT_OFFSET: usize = 0;
+pub const TREE_NODE_RIGHT_OFFSET: usize = 4;
+pub const TREE_NODE_SIZE: usize = 8;
+
+pub const LINE_PX_OFFSET: usize = 0;
+pub const LINE_QTY_OFFSET: usize = 8;
+pub const LINE_NEXT_OFFSET: usize = 16;
+pub const LINE_PREV_OFFSET: usize = 20;
+pub const LINE_SREF_OFFSET: usize = 24;
+pub const LINE_EREF_OFFSET: usize = 28;
+pub const LINE_SIZE: usize = 32;
+
+pub const ORDER_QTY_OFFSET: usize = 0;
+pub const ORDER_CLIENT_ID_OFFSET: usize = 8;
+pub const ORDER_LINE_OFFSET: usize = 12;
+pub const ORDER_NEXT_OFFSET: usize = 16;
+pub const ORDER_PREV_OFFSET: usize = 20;
+pub const ORDER_ID_OFFSET: usize = 24;
+pub const ORDER_TIME_OFFSET: usize = 32;
+pub const ORDER_SIZE: usize = 40;
+
+pub const MAPS_ACCOUNT_INSTANCE_ID_OFFSET: usize = 16;
+pub const MAPS_ACCOUNT_STRIKES_COUNT_OFFSET: usize = 20;
+pub const MAPS_ACCOUNT_EXPIRATION_OFFSET: usize = 24;
+pub const MAPS_ACCOUNT_PX_GRANULAR_OFFSET: usize = 32;
+pub const MAPS_ACCOUNT_CONTRACT_SIZE_OFFSET: usize = 40;
+pub const MAPS_ACCOUNT_OPTIONS_CASHFLOW_OFFSET: usize = 48;
+pub const MAPS_ACCOUNT_OPTIONS_HEDGE_COLLATERAL_OFFSET: usize = 56;
+pub const MAPS_ACCOUNT_OPTIONS_HEDGE_POS_OFFSET: usize = 64;
+pub const MAPS_ACCOUNT_OPTIONS_HEDGE_RESULT_OFFSET: usize = 72;
+pub const MAPS_ACCOUNT_OPTIONS_HEDGE_EDGE_OFFSET: usize = 80;
+pub const MAPS_ACCOUNT_MIN_PX_OFFSET: usize = 88;
+pub const MAPS_ACCOUNT_MAX_PX_OFFSET: usize = 96;
+pub const MAPS_ACCOUNT_MIN_PX_HEIGHT_OFFSET: usize = 104;
+pub const MAPS_ACCOUNT_MAX_PX_HEIGHT_OFFSET: usize = 112;
+pub const MAPS_ACCOUNT_OPTIONS_DAY_NOTIONAL_VOLUME_OFFSET: usize = 120;
+pub const MAPS_ACCOUNT_OPTIONS_DAY_MARKET_VOLUME_OFFSET: usize = 128;
+pub const MAPS_ACCOUNT_OPTIONS_DAY_PREMIUM_VOLUME_OFFSET: usize = 136;
+pub const MAPS_ACCOUNT_OPTIONS_DAY_TRADES_OFFSET: usize = 144;
+pub const MAPS_ACCOUNT_OPTIONS_ALLTIME_NOTIONAL_VOLUME_OFFSET: usize = 152;
+pub const MAPS_ACCOUNT_OPTIONS_ALLTIME_MARKET_VOLUME_OFFSET: usize = 160;
+pub const MAPS_ACCOUNT_OPTIONS_ALLTIME_PREMIUM_VOLUME_OFFSET: usize = 168;
+pub const MAPS_ACCOUNT_OPTIONS_ALLTIME_TRADES_OFFSET: usize = 176;
+pub const MAPS_ACCOUNT_STRIKES_OFFSET: usize = 184;
+
+pub const STRIKE_ID_OFFSET: usize = 0;
+pub const STRIKE_PX_OFFSET: usize = 8;
+pub const STRIKE_CALLS_OFFSET: usize = 16;
+pub const STRIKE_PUTS_OFFSET: usize = 24;
+pub const STRIKE_HEIGHT_OFFSET: usize = 32;
+pub const STRIKE_CALL_PX_OFFSET: usize = 40;
+pub const STRIKE_PUT_PX_OFFSET: usize = 48;
+pub const STRIKE_SIZE: usize = 56;
+
+pub const CANDLES_ACCOUNT_HEAD_OFFSET: usize = 12;
+pub const CANDLES_ACCOUNT_COUNT_OFFSET: usize = 16;
+pub const CANDLES_ACCOUNT_HEADER_SIZE: usize = 24;
+
+pub const CANDLE_TIME_OFFSET: usize = 0;
+pub const CANDLE_TRADES_OFFSET: usize = 4;
+pub const CANDLE_OPEN_OFFSET: usize = 8;
+pub const CANDLE_HIGH_OFFSET: usize = 16;
+pub const CANDLE_LOW_OFFSET: usize = 24;
+pub const CANDLE_CLOSE_OFFSET: usize = 32;
+pub const CANDLE_VOLUME_OFFSET: usize = 40;
+pub const CANDLE_TURNOVER_OFFSET: usize = 48;
+pub const CANDLE_SIZE: usize = 56;
+
+pub const TRACE_ACCOUNT_HEAD_OFFSET: usize = 12;
+pub const TRACE_ACCOUNT_COUNT_OFFSET: usize = 16;
+pub const TRACE_ACCOUNT_HEADER_SIZE: usize = 24;
+
+pub const TRACE_RECORD_ID_OFFSET: usize = 0;
+pub const TRACE_RECORD_TIME_OFFSET: usize = 8;
+pub const TRACE_RECORD_SIDE_OFFSET: usize = 12;
+pub const TRACE_RECORD_PX_OFFSET: usize = 16;
+pub const TRACE_RECORD_QTY_OFFSET: usize = 24;
+pub const TRACE_RECORD_SLOT_OFFSET: usize = 32;
+pub const TRACE_RECORD_SIZE: usize = 40;
+
+pub const CLIENT_PRIMARY_ACCOUNT_TAG_OFFSET: usize = 0;
+pub const CLIENT_PRIMARY_ACCOUNT_VERSION_OFFSET: usize = 4;
+pub const CLIENT_PRIMARY_ACCOUNT_WALLET_ADDRESS_OFFSET: usize = 8;
+pub const CLIENT_PRIMARY_ACCOUNT_ID_OFFSET: usize = 40;
+pub const CLIENT_PRIMARY_ACCOUNT_MASK_OFFSET: usize = 44;
+pub const CLIENT_PRIMARY_ACCOUNT_DRV_ADDRESS_OFFSET: usize = 48;
+pub const CLIENT_PRIMARY_ACCOUNT_DEXNOW_ADDRESS_OFFSET: usize = 80;
+pub const CLIENT_PRIMARY_ACCOUNT_LUT_ADDRESS_OFFSET: usize = 112;
+pub const CLIENT_PRIMARY_ACCOUNT_ASSETS_COUNT_OFFSET: usize = 144;
+pub const CLIENT_PRIMARY_ACCOUNT_HEADER_SIZE: usize = 152;
+
+pub const CLIENT_ASSET_TOKEN_ID_OFFSET: usize = 0;
+pub const CLIENT_ASSET_AMOUNT_OFFSET: usize = 8;
+pub const CLIENT_ASSET_SIZE: usize = 16;
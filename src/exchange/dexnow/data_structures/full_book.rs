@@ -0,0 +1,91 @@
+#![allow(dead_code)]
+use crate::exchange::dexnow::data_structures::line_px::LinePx;
+
+/// Contains data about an order resting on a line of the order book
+#[derive(Debug, Clone, PartialEq)]
+pub struct BookOrder {
+    /// Order ID
+    pub id: u64,
+    /// Owner client ID
+    pub client_id: u32,
+    /// Remaining quantity
+    pub qty: f64,
+    /// Time of placement in seconds since epoch
+    pub time: u32,
+}
+
+/// Contains data about a price line of the order book and its orders
+#[derive(Debug, Clone, PartialEq)]
+pub struct BookLine {
+    pub line: LinePx,
+    /// Orders of the line in queue priority, empty if orders were not decoded
+    pub orders: Vec<BookOrder>,
+}
+
+/// Complete order book of a spot or futures market rebuilt from its tree, lines and orders accounts
+#[derive(Debug, Default)]
+pub struct FullBook {
+    /// Bid lines from the best (highest) price
+    pub bids: Vec<BookLine>,
+    /// Ask lines from the best (lowest) price
+    pub asks: Vec<BookLine>,
+}
+
+/// Accounts of a market the full book is rebuilt from
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EBookAccount {
+    BidsTree,
+    AsksTree,
+    BidOrders,
+    AskOrders,
+    Lines,
+}
+
+/// Latest data of the accounts the full book of a market is rebuilt from
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct BookAccounts {
+    pub bids_tree: Option<Vec<u8>>,
+    pub asks_tree: Option<Vec<u8>>,
+    pub bid_orders: Option<Vec<u8>>,
+    pub ask_orders: Option<Vec<u8>>,
+    pub lines: Option<Vec<u8>>,
+}
+
+impl BookAccounts {
+    pub fn set(&mut self, account: EBookAccount, data: Vec<u8>) {
+        let slot = match account {
+            EBookAccount::BidsTree => &mut self.bids_tree,
+            EBookAccount::AsksTree => &mut self.asks_tree,
+            EBookAccount::BidOrders => &mut self.bid_orders,
+            EBookAccount::AskOrders => &mut self.ask_orders,
+            EBookAccount::Lines => &mut self.lines,
+        };
+        *slot = Some(data);
+    }
+}
+
+/// How deep the driver rebuilds the spot book
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EBookDepth {
+    /// Aggregated top lines of the instrument dynamic account
+    Top,
+    /// All lines from the tree and lines accounts
+    Lines,
+    /// All lines with their individual orders
+    Orders,
+}
+
+impl EBookDepth {
+    /// Read the depth from `DEXNOW_BOOK_DEPTH` (`top`, `lines` or `orders`), all lines by default
+    pub fn from_env() -> Self {
+        match std::env::var("DEXNOW_BOOK_DEPTH").ok().as_deref() {
+            Some("top") => EBookDepth::Top,
+            Some("orders") => EBookDepth::Orders,
+            Some("lines") | None => EBookDepth::Lines,
+            Some(other) => {
+                eprintln!("[WARNING][DEXnow] Unknown DEXNOW_BOOK_DEPTH '{}', using 'lines'", other);
+                EBookDepth::Lines
+            }
+        }
+    }
+}
@@ -1,9 +1,11 @@
 #![allow(dead_code)]
+use ordered_float::OrderedFloat;
 use solana_sdk::pubkey::Pubkey;
 use crate::exchange::dexnow::data_structures::line_px::LinePx;
+use crate::exchange::order_book::OrderBook;
 
 /// Contains data about futures market
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct Futures {
     pub bids_tree_account: Pubkey,
     pub asks_tree_account: Pubkey,
@@ -35,4 +37,18 @@ pub struct Futures {
     pub bid_quotes: Option<Vec<LinePx>>,
     /// Orderbook ask quotes
     pub ask_quotes: Option<Vec<LinePx>>,
-}
\ No newline at end of file
+}
+
+impl Futures {
+    /// Order book of the futures quotes, to compare them with spot or perpetual books
+    pub fn order_book(&self) -> OrderBook {
+        let mut orderbook = OrderBook::new();
+        for line_px in self.bid_quotes.iter().flatten() {
+            orderbook.bids.insert(OrderedFloat(line_px.px), line_px.qty);
+        }
+        for line_px in self.ask_quotes.iter().flatten() {
+            orderbook.asks.insert(OrderedFloat(line_px.px), line_px.qty);
+        }
+        orderbook
+    }
+}
@@ -4,7 +4,7 @@ use crate::exchange::dexnow::data_structures::derivative::Derivative;
 use crate::exchange::dexnow::data_structures::spot::Spot;
 
 /// Contains data about instrument
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct Instrument {
     pub static_account: Pubkey,
     /// Account that stores all trading data about instrument
@@ -20,6 +20,8 @@ pub struct Instrument {
     /// If instrument has derivatives refers to options pool token ID
     pub pool_token_id: Option<u64>,
     pub protocol_fees: Option<f64>,
+    /// Price step of the instrument in the `DEC` units prices are stored in
+    pub dec_factor: i64,
     /// Spot trading data
     pub spot: Spot,
     pub derivatives_count: u64,
@@ -0,0 +1,412 @@
+#![allow(dead_code)]
+//! Zero-copy views of the DEXnow accounts.
+//!
+//! The layouts mirror the on-chain structures byte for byte and are cast straight from the account data.
+//! They are packed so the cast never depends on the alignment of the RPC buffer, fields are read by value.
+use std::mem::{offset_of, size_of};
+use bytemuck::{Pod, Zeroable};
+use solana_sdk::pubkey::Pubkey;
+use crate::exchange::dexnow::data_structures::constants::*;
+use crate::exchange::solana::account_data::{check_size, AccountDecodeError};
+
+// Solana accounts are little-endian, casting them is only valid on a little-endian host
+#[cfg(not(target_endian = "little"))]
+compile_error!("DEXnow account layouts require a little-endian target");
+
+/// Fail the build when a layout drifts from the offsets of the constants module
+/// This keeps both in sync, it does not check them against the program: see the module documentation of the constants
+macro_rules! assert_layout {
+    ($layout:ty, size = $size:expr, { $($field:ident => $offset:expr),* $(,)? }) => {
+        const _: () = assert!(size_of::<$layout>() == $size);
+        $(const _: () = assert!(offset_of!($layout, $field) == $offset);)*
+    };
+}
+
+/// View the start of the account data as the layout
+pub fn cast<T: Pod>(data: &[u8]) -> Result<&T, AccountDecodeError> {
+    check_size(data, size_of::<T>())?;
+    Ok(bytemuck::from_bytes(&data[..size_of::<T>()]))
+}
+
+/// View `count` consecutive records of the layout starting at `offset`
+pub fn cast_slice<T: Pod>(data: &[u8], offset: usize, count: usize) -> Result<&[T], AccountDecodeError> {
+    let end = offset + count * size_of::<T>();
+    check_size(data, end)?;
+    Ok(bytemuck::cast_slice(&data[offset..end]))
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct RootAccountLayout {
+    pub tag: u32,
+    pub version: u32,
+    pub operator_address: [u8; 32],
+    pub holder_address: [u8; 32],
+    pub community_address: [u8; 32],
+    pub distrib_address: [u8; 32],
+    pub dexnow_mint_address: [u8; 32],
+    pub lut_address: [u8; 32],
+    pub sol_program_address: [u8; 32],
+    pub sol_mask: u32,
+    pub clients_count: u32,
+    pub tokens_count: u32,
+    pub instr_count: u32,
+}
+
+assert_layout!(RootAccountLayout, size = ROOT_ACCOUNT_SIZE, {
+    tag => ROOT_ACCOUNT_TAG_OFFSET,
+    version => ROOT_ACCOUNT_VERSION_OFFSET,
+    operator_address => ROOT_ACCOUNT_OPERATOR_ADDRESS_OFFSET,
+    holder_address => ROOT_ACCOUNT_HOLDER_ADDRESS_OFFSET,
+    community_address => ROOT_ACCOUNT_COMMUNITY_ADDRESS_OFFSET,
+    distrib_address => ROOT_ACCOUNT_DISTRIB_ADDRESS_OFFSET,
+    dexnow_mint_address => ROOT_ACCOUNT_DEXNOW_MINT_ADDRESS_OFFSET,
+    lut_address => ROOT_ACCOUNT_LUT_ADDRESS_OFFSET,
+    sol_program_address => ROOT_ACCOUNT_SOL_PROGRAM_ADDRESS_OFFSET,
+    sol_mask => ROOT_ACCOUNT_SOL_MASK_OFFSET,
+    clients_count => ROOT_ACCOUNT_CLIENTS_COUNT_OFFSET,
+    tokens_count => ROOT_ACCOUNT_TOKENS_COUNT_OFFSET,
+    instr_count => ROOT_ACCOUNT_INSTR_COUNT_OFFSET,
+});
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct TokenAccountLayout {
+    pub tag: u32,
+    pub version: u32,
+    pub root_address: [u8; 32],
+    pub address: [u8; 32],
+    pub program_address: [u8; 32],
+    pub id: u32,
+    pub mask: u32,
+    pub base_instr_id: u32,
+    pub base_crncy: u32,
+}
+
+assert_layout!(TokenAccountLayout, size = TOKEN_ACCOUNT_SIZE, {
+    tag => TOKEN_ACCOUNT_TAG_OFFSET,
+    version => TOKEN_ACCOUNT_VERSION_OFFSET,
+    root_address => TOKEN_ACCOUNT_ROOT_ADDRESS_OFFSET,
+    address => TOKEN_ACCOUNT_ADDRESS_OFFSET,
+    program_address => TOKEN_ACCOUNT_PROGRAM_ADDRESS_OFFSET,
+    id => TOKEN_ACCOUNT_ID_OFFSET,
+    mask => TOKEN_ACCOUNT_MASK_OFFSET,
+    base_instr_id => TOKEN_ACCOUNT_BASE_INSTR_ID_OFFSET,
+    base_crncy => TOKEN_ACCOUNT_BASE_CRNCY_OFFSET,
+});
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct InstrStaticAccountLayout {
+    pub tag: u32,
+    pub version: u32,
+    pub root_address: [u8; 32],
+    pub dynamic_address: [u8; 32],
+    pub id: u32,
+    pub token_id: u32,
+    pub mint_id: u32,
+    pub mask: u32,
+    pub pool_instr_id: u32,
+    pub tasks_count: u32,
+    pub token_decs_count: u32,
+    pub mint_decs_count: u32,
+    pub pool_token_id: u32,
+    pub reserved: u32,
+    pub maps_address: [u8; 32],
+    pub client_accounts_address: [u8; 32],
+    pub client_infos_address: [u8; 32],
+    pub client_infos2_address: [u8; 32],
+    pub bids_tree_address: [u8; 32],
+    pub asks_tree_address: [u8; 32],
+    pub bid_orders_address: [u8; 32],
+    pub ask_orders_address: [u8; 32],
+    pub lines_address: [u8; 32],
+    pub m1_candles_address: [u8; 32],
+    pub m15_candles_address: [u8; 32],
+    pub day_candles_address: [u8; 32],
+    pub trace_address: [u8; 32],
+    pub lut_address: [u8; 32],
+    pub dec_factor: i64,
+}
+
+assert_layout!(InstrStaticAccountLayout, size = INSTR_STATIC_ACCOUNT_SIZE, {
+    tag => INSTR_STATIC_ACCOUNT_TAG_OFFSET,
+    version => INSTR_STATIC_ACCOUNT_VERSION_OFFSET,
+    root_address => INSTR_STATIC_ACCOUNT_ROOT_ADDRESS_OFFSET,
+    dynamic_address => INSTR_STATIC_ACCOUNT_DYNAMIC_ADDRESS_OFFSET,
+    id => INSTR_STATIC_ACCOUNT_ID_OFFSET,
+    token_id => INSTR_STATIC_ACCOUNT_TOKEN_ID_OFFSET,
+    mint_id => INSTR_STATIC_ACCOUNT_MINT_ID_OFFSET,
+    mask => INSTR_STATIC_ACCOUNT_MASK_OFFSET,
+    pool_instr_id => INSTR_STATIC_ACCOUNT_POOL_INSTR_ID_OFFSET,
+    tasks_count => INSTR_STATIC_ACCOUNT_TASKS_COUNT_OFFSET,
+    token_decs_count => INSTR_STATIC_ACCOUNT_TOKEN_DECS_COUNT_OFFSET,
+    mint_decs_count => INSTR_STATIC_ACCOUNT_MINT_DECS_COUNT_OFFSET,
+    pool_token_id => INSTR_STATIC_ACCOUNT_POOL_TOKEN_ID_OFFSET,
+    reserved => INSTR_STATIC_ACCOUNT_RESERVED_OFFSET,
+    maps_address => INSTR_STATIC_ACCOUNT_MAPS_ADDRESS_OFFSET,
+    client_accounts_address => INSTR_STATIC_ACCOUNT_CLIENT_ACCOUNTS_ADDRESS_OFFSET,
+    client_infos_address => INSTR_STATIC_ACCOUNT_CLIENT_INFOS_ADDRESS_OFFSET,
+    client_infos2_address => INSTR_STATIC_ACCOUNT_CLIENT_INFOS2_ADDRESS_OFFSET,
+    bids_tree_address => INSTR_STATIC_ACCOUNT_BIDS_TREE_ADDRESS_OFFSET,
+    asks_tree_address => INSTR_STATIC_ACCOUNT_ASKS_TREE_ADDRESS_OFFSET,
+    bid_orders_address => INSTR_STATIC_ACCOUNT_BID_ORDERS_ADDRESS_OFFSET,
+    ask_orders_address => INSTR_STATIC_ACCOUNT_ASK_ORDERS_ADDRESS_OFFSET,
+    lines_address => INSTR_STATIC_ACCOUNT_LINES_ADDRESS_OFFSET,
+    m1_candles_address => INSTR_STATIC_ACCOUNT_M1_CANDLES_ADDRESS_OFFSET,
+    m15_candles_address => INSTR_STATIC_ACCOUNT_M15_CANDLES_ADDRESS_OFFSET,
+    day_candles_address => INSTR_STATIC_ACCOUNT_DAY_CANDLES_ADDRESS_OFFSET,
+    trace_address => INSTR_STATIC_ACCOUNT_TRACE_ADDRESS_OFFSET,
+    lut_address => INSTR_STATIC_ACCOUNT_LUT_ADDRESS_OFFSET,
+    dec_factor => INSTR_STATIC_ACCOUNT_DEC_FACTOR_OFFSET,
+});
+
+/// Accounts of one derivative, stored after the instrument static account
+#[repr(C, packed)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct TaskStaticLayout {
+    pub maps_address: [u8; 32],
+    pub client_accounts_address: [u8; 32],
+    pub client_infos_address: [u8; 32],
+    pub client_infos2_address: [u8; 32],
+    pub bids_tree_address: [u8; 32],
+    pub asks_tree_address: [u8; 32],
+    pub bid_orders_address: [u8; 32],
+    pub ask_orders_address: [u8; 32],
+    pub lines_address: [u8; 32],
+    pub mask: u32,
+    pub reserved: u32,
+}
+
+assert_layout!(TaskStaticLayout, size = TASK_STATIC_SIZE, {
+    maps_address => TASK_STATIC_MAPS_ADDRESS_OFFSET,
+    client_accounts_address => TASK_STATIC_CLIENT_ACCOUNTS_ADDRESS_OFFSET,
+    client_infos_address => TASK_STATIC_CLIENT_INFOS_ADDRESS_OFFSET,
+    client_infos2_address => TASK_STATIC_CLIENT_INFOS2_ADDRESS_OFFSET,
+    bids_tree_address => TASK_STATIC_BIDS_TREE_ADDRESS_OFFSET,
+    asks_tree_address => TASK_STATIC_ASKS_TREE_ADDRESS_OFFSET,
+    bid_orders_address => TASK_STATIC_BID_ORDERS_ADDRESS_OFFSET,
+    ask_orders_address => TASK_STATIC_ASK_ORDERS_ADDRESS_OFFSET,
+    lines_address => TASK_STATIC_LINES_ADDRESS_OFFSET,
+    mask => TASK_STATIC_MASK_OFFSET,
+});
+
+/// Orderbook line of the top lines section, price and quantity are raw integers
+#[repr(C, packed)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct LinePxLayout {
+    pub px: i64,
+    pub qty: i64,
+}
+
+#[repr(C, packed)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct InstrDynamicAccountLayout {
+    pub tag: u32,
+    pub version: u32,
+    pub root_address: [u8; 32],
+    pub id: u32,
+    pub reserved: u32,
+    pub pool_tokens_supply: i64,
+    pub asset_tokens_in_pool: i64,
+    pub base_crncy_tokens_in_pool: i64,
+    pub asset_token_fees_in_pool: i64,
+    pub base_crncy_token_fees_in_pool: i64,
+    pub day_sigma: i64,
+    pub day_sigma2: i64,
+    pub hour_sigma: i64,
+    pub last_hour_px: i64,
+    pub last_px: i64,
+    pub last_close_px: i64,
+    pub fixing_px: i64,
+    pub day_asset_tokens: i64,
+    pub day_base_crncy_tokens: i64,
+    pub day_trades: u64,
+    pub alltime_asset_tokens: i64,
+    pub alltime_base_crncy_tokens: i64,
+    pub alltime_trades: u64,
+    pub last_asset_tokens: i64,
+    pub last_base_crncy_tokens: i64,
+    pub last_trade_time: u32,
+    pub reserved2: [u8; 156],
+    pub options_pool_est_px: i64,
+    pub options_pool_supply: i64,
+    pub bids: [LinePxLayout; MARKET_DEPTH],
+    pub asks: [LinePxLayout; MARKET_DEPTH],
+}
+
+assert_layout!(InstrDynamicAccountLayout, size = INSTR_DYNAMIC_ACCOUNT_TASKS_OFFSET, {
+    tag => ACCOUNT_TAG_OFFSET,
+    version => ACCOUNT_VERSION_OFFSET,
+    id => INSTR_DYNAMIC_ACCOUNT_ID_OFFSET,
+    pool_tokens_supply => INSTR_DYNAMIC_ACCOUNT_POOL_TOKENS_SUPPLY_OFFSET,
+    asset_tokens_in_pool => INSTR_DYNAMIC_ACCOUNT_ASSET_TOKENS_IN_POOL_OFFSET,
+    base_crncy_tokens_in_pool => INSTR_DYNAMIC_ACCOUNT_BASE_CRNCY_TOKENS_IN_POOL_OFFSET,
+    asset_token_fees_in_pool => INSTR_DYNAMIC_ACCOUNT_ASSET_TOKEN_FEES_IN_POOL_OFFSET,
+    base_crncy_token_fees_in_pool => INSTR_DYNAMIC_ACCOUNT_BASE_CRNCY_TOKEN_FEES_IN_POOL_OFFSET,
+    day_sigma => INSTR_DYNAMIC_ACCOUNT_DAY_SIGMA_OFFSET,
+    day_sigma2 => INSTR_DYNAMIC_ACCOUNT_DAY_SIGMA2_OFFSET,
+    hour_sigma => INSTR_DYNAMIC_ACCOUNT_HOUR_SIGMA_OFFSET,
+    last_hour_px => INSTR_DYNAMIC_ACCOUNT_LAST_HOUR_PX_OFFSET,
+    last_px => INSTR_DYNAMIC_ACCOUNT_LAST_PX_OFFSET,
+    last_close_px => INSTR_DYNAMIC_ACCOUNT_LAST_CLOSE_PX_OFFSET,
+    fixing_px => INSTR_DYNAMIC_ACCOUNT_FIXING_PX_OFFSET,
+    day_asset_tokens => INSTR_DYNAMIC_ACCOUNT_DAY_ASSET_TOKENS_OFFSET,
+    day_base_crncy_tokens => INSTR_DYNAMIC_ACCOUNT_DAY_BASE_CRNCY_TOKENS_OFFSET,
+    day_trades => INSTR_DYNAMIC_ACCOUNT_DAY_TRADES_OFFSET,
+    alltime_asset_tokens => INSTR_DYNAMIC_ACCOUNT_ALLTIME_ASSET_TOKENS_OFFSET,
+    alltime_base_crncy_tokens => INSTR_DYNAMIC_ACCOUNT_ALLTIME_BASE_CRNCY_TOKENS_OFFSET,
+    alltime_trades => INSTR_DYNAMIC_ACCOUNT_ALLTIME_TRADES_OFFSET,
+    last_asset_tokens => INSTR_DYNAMIC_ACCOUNT_LAST_ASSET_TOKENS_OFFSET,
+    last_base_crncy_tokens => INSTR_DYNAMIC_ACCOUNT_LAST_BASE_CRNCY_TOKENS_OFFSET,
+    last_trade_time => INSTR_DYNAMIC_ACCOUNT_LAST_TRADE_TIME_OFFSET,
+    options_pool_est_px => INSTR_DYNAMIC_ACCOUNT_OPTIONS_POOL_EST_PX_OFFSET,
+    options_pool_supply => INSTR_DYNAMIC_ACCOUNT_OPTIONS_POOL_SUPPLY_OFFSET,
+    bids => INSTR_DYNAMIC_ACCOUNT_BIDS_OFFSET,
+    asks => INSTR_DYNAMIC_ACCOUNT_ASKS_OFFSET,
+});
+
+/// Statistics of one derivative, stored after the instrument dynamic account
+#[repr(C, packed)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct TaskDynamicLayout {
+    pub instance_id: u32,
+    pub expiration: u32,
+    pub open_int: i64,
+    pub last_px: i64,
+    pub last_close_px: i64,
+    pub last_futures: i64,
+    pub apr: i64,
+    pub last_trade_time: u32,
+    pub day_trades: u32,
+}
+
+assert_layout!(TaskDynamicLayout, size = TASK_DYNAMIC_SIZE, {
+    instance_id => TASK_DYNAMIC_INSTANCE_ID_OFFSET,
+    expiration => TASK_DYNAMIC_EXPIRATION_OFFSET,
+    open_int => TASK_DYNAMIC_FUTURES_OPEN_INT_OFFSET,
+    last_px => TASK_DYNAMIC_FUTURES_LAST_PX_OFFSET,
+    last_close_px => TASK_DYNAMIC_FUTURES_LAST_CLOSE_PX_OFFSET,
+    last_futures => TASK_DYNAMIC_FUTURES_LAST_FUTURES_OFFSET,
+    apr => TASK_DYNAMIC_FUTURES_APR_OFFSET,
+    last_trade_time => TASK_DYNAMIC_FUTURES_LAST_TRADE_TIME_OFFSET,
+    day_trades => TASK_DYNAMIC_FUTURES_DAY_TRADES_OFFSET,
+});
+
+/// Convert an address field of a layout
+pub fn pubkey(address: [u8; 32]) -> Pubkey {
+    Pubkey::new_from_array(address)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use solana_client::nonblocking::rpc_client::RpcClient;
+
+    #[test]
+    fn test_cast_reads_fields_at_constant_offsets() {
+        let mint = Pubkey::new_unique();
+        // Start at an odd address to make sure the views do not depend on the alignment of the buffer
+        let mut buffer = [0u8; TOKEN_ACCOUNT_SIZE + 1];
+        let data = &mut buffer[1..];
+        data[TOKEN_ACCOUNT_ADDRESS_OFFSET..][..32].copy_from_slice(mint.as_ref());
+        data[TOKEN_ACCOUNT_ID_OFFSET..][..4].copy_from_slice(&7u32.to_le_bytes());
+        data[TOKEN_ACCOUNT_MASK_OFFSET..][..4].copy_from_slice(&(U32_BIT_31 | 6).to_le_bytes());
+
+        let token = cast::<TokenAccountLayout>(data).unwrap();
+
+        assert_eq!({ token.id }, 7);
+        assert_eq!({ token.mask }, U32_BIT_31 | 6);
+        assert_eq!(pubkey(token.address), mint);
+    }
+
+    #[test]
+    fn test_cast_rejects_small_account() {
+        let data = vec![0u8; INSTR_DYNAMIC_ACCOUNT_ASKS_OFFSET];
+
+        let result = cast::<InstrDynamicAccountLayout>(&data);
+
+        assert_eq!(result.err(), Some(AccountDecodeError::AccountTooSmall {
+            expected: INSTR_DYNAMIC_ACCOUNT_TASKS_OFFSET,
+            actual: INSTR_DYNAMIC_ACCOUNT_ASKS_OFFSET,
+        }));
+    }
+
+    #[test]
+    fn test_cast_slice() {
+        let mut data = vec![0u8; INSTR_DYNAMIC_ACCOUNT_TASKS_OFFSET + 2 * TASK_DYNAMIC_SIZE];
+        let offset = INSTR_DYNAMIC_ACCOUNT_TASKS_OFFSET + TASK_DYNAMIC_SIZE;
+        data[offset + TASK_DYNAMIC_FUTURES_LAST_PX_OFFSET..][..8].copy_from_slice(&151i64.to_le_bytes());
+
+        let tasks = cast_slice::<TaskDynamicLayout>(&data, INSTR_DYNAMIC_ACCOUNT_TASKS_OFFSET, 2).unwrap();
+
+        assert_eq!({ tasks[0].last_px }, 0);
+        assert_eq!({ tasks[1].last_px }, 151);
+        assert!(cast_slice::<TaskDynamicLayout>(&data, INSTR_DYNAMIC_ACCOUNT_TASKS_OFFSET, 3).is_err());
+    }
+
+    fn read_u32(data: &[u8], offset: usize) -> u32 {
+        u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+    }
+
+    fn read_i64(data: &[u8], offset: usize) -> i64 {
+        i64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+    }
+
+    /// Line at the end of the tree: the rightmost for the best bid, the leftmost for the best ask
+    fn best_line(tree: &[u8], child_offset: usize) -> Option<usize> {
+        let mut node = read_u32(tree, TREE_ACCOUNT_ROOT_OFFSET);
+        let mut best = None;
+        while node != NULL_LINE {
+            assert!(node < MAX_LINES as u32, "Tree node {} is out of the lines account", node);
+            best = Some(node as usize);
+            node = read_u32(tree, BOOK_ACCOUNT_HEADER_SIZE + node as usize * TREE_NODE_SIZE + child_offset);
+        }
+        best
+    }
+
+    /// Checks the layouts against the accounts of a live instrument, the synthetic tests above only check the
+    /// layouts against the constants. Run with the static account of a spot instrument with a two-sided book:
+    /// `DEXNOW_CHECK_INSTR_STATIC_PK=<address> cargo test live_instrument -- --ignored`
+    #[tokio::test]
+    #[ignore = "reads live accounts from SOLANA_RPC_URL"]
+    async fn test_layouts_match_live_instrument_accounts() {
+        let rpc = RpcClient::new(std::env::var("SOLANA_RPC_URL").expect("SOLANA_RPC_URL must be set"));
+        let static_account = Pubkey::from_str(&std::env::var("DEXNOW_CHECK_INSTR_STATIC_PK")
+            .expect("DEXNOW_CHECK_INSTR_STATIC_PK must be set")).unwrap();
+        let data = rpc.get_account_data(&static_account).await.unwrap();
+        let instr = cast::<InstrStaticAccountLayout>(&data).unwrap();
+        assert_eq!({ instr.tag }, INSTR_STATIC_TAG);
+
+        let linked = [
+            (instr.dynamic_address, INSTR_DYNAMIC_TAG, INSTR_DYNAMIC_ACCOUNT_ID_OFFSET),
+            (instr.maps_address, SPOT_MAPS_TAG, BOOK_ACCOUNT_INSTR_ID_OFFSET),
+            (instr.bids_tree_address, SPOT_BIDS_TREE_TAG, BOOK_ACCOUNT_INSTR_ID_OFFSET),
+            (instr.asks_tree_address, SPOT_ASKS_TREE_TAG, BOOK_ACCOUNT_INSTR_ID_OFFSET),
+            (instr.bid_orders_address, SPOT_BID_ORDERS_TAG, BOOK_ACCOUNT_INSTR_ID_OFFSET),
+            (instr.ask_orders_address, SPOT_ASK_ORDERS_TAG, BOOK_ACCOUNT_INSTR_ID_OFFSET),
+            (instr.lines_address, SPOT_LINES_TAG, BOOK_ACCOUNT_INSTR_ID_OFFSET),
+            (instr.m1_candles_address, SPOT_1M_CANDLES_TAG, BOOK_ACCOUNT_INSTR_ID_OFFSET),
+            (instr.m15_candles_address, SPOT_15M_CANDLES_TAG, BOOK_ACCOUNT_INSTR_ID_OFFSET),
+            (instr.day_candles_address, SPOT_DAY_CANDLES_TAG, BOOK_ACCOUNT_INSTR_ID_OFFSET),
+            (instr.trace_address, INSTR_TRACE_TAG, BOOK_ACCOUNT_INSTR_ID_OFFSET),
+        ];
+        let addresses: Vec<Pubkey> = linked.iter().map(|(address, _, _)| pubkey(*address)).collect();
+        let accounts = rpc.get_multiple_accounts(&addresses).await.unwrap();
+        for ((address, tag, id_offset), account) in linked.iter().zip(&accounts) {
+            let data = &account.as_ref().unwrap_or_else(|| panic!("Account {} not found", pubkey(*address))).data;
+            assert_eq!(read_u32(data, ACCOUNT_TAG_OFFSET), *tag as u32, "Tag of account {}", pubkey(*address));
+            assert_eq!(read_u32(data, ACCOUNT_VERSION_OFFSET), { instr.version }, "Version of account {}", pubkey(*address));
+            assert_eq!(read_u32(data, *id_offset), { instr.id }, "Instrument ID of account {}", pubkey(*address));
+        }
+
+        // A wrong tree or line layout reads garbage prices, the live book must not be crossed
+        let data = |index: usize| accounts[index].as_ref().unwrap().data.as_slice();
+        let best_bid = best_line(data(2), TREE_NODE_RIGHT_OFFSET).expect("No bid line");
+        let best_ask = best_line(data(3), TREE_NODE_LEFT_OFFSET).expect("No ask line");
+        let line = |index: usize, field: usize| read_i64(data(6), BOOK_ACCOUNT_HEADER_SIZE + index * LINE_SIZE + field);
+        assert!(line(best_bid, LINE_QTY_OFFSET) > 0 && line(best_ask, LINE_QTY_OFFSET) > 0);
+        assert!(0 < line(best_bid, LINE_PX_OFFSET) && line(best_bid, LINE_PX_OFFSET) < line(best_ask, LINE_PX_OFFSET));
+    }
+}
@@ -1,6 +1,6 @@
 #![allow(dead_code)]
 /// Contains data about orderbook line
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct LinePx {
     /// Price
     pub px: f64,
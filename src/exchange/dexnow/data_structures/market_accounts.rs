@@ -0,0 +1,58 @@
+#![allow(dead_code)]
+use crate::exchange::dexnow::data_structures::book_history::{BookHistory, BookVersion};
+use crate::exchange::dexnow::data_structures::full_book::{BookAccounts, EBookAccount};
+
+/// Book accounts of a market with the versions kept of their recent slots
+#[derive(Default)]
+pub struct MarketAccounts {
+    /// Latest data of the accounts
+    pub accounts: BookAccounts,
+    /// Slot of the latest change
+    pub slot: u64,
+    history: BookHistory,
+}
+
+impl MarketAccounts {
+    /// Apply the change of an account in the slot
+    pub fn set(&mut self, account: EBookAccount, data: Vec<u8>, slot: u64) {
+        self.accounts.set(account, data);
+        self.slot = slot;
+    }
+
+    /// Keep the accounts as the version of their slot
+    pub fn keep_version(&mut self) {
+        self.history.push(BookVersion { slot: self.slot, accounts: self.accounts.clone() });
+    }
+
+    /// Restore the accounts of the newest kept slot before `slot`, whose fork replaces the later slots
+    /// Returns the restored slot, None if no version is kept that far back and the accounts are left as they are
+    pub fn roll_back_before(&mut self, slot: u64) -> Option<u64> {
+        let version = self.history.rollback_to(slot.saturating_sub(1))?.clone();
+        self.accounts = version.accounts;
+        self.slot = version.slot;
+        Some(version.slot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roll_back_restores_accounts_of_the_kept_slot() {
+        let mut market = MarketAccounts::default();
+        market.set(EBookAccount::BidsTree, vec![10], 10);
+        market.set(EBookAccount::Lines, vec![10], 10);
+        market.keep_version();
+        market.set(EBookAccount::BidsTree, vec![12], 12);
+        market.keep_version();
+
+        assert_eq!(market.roll_back_before(11), Some(10));
+        market.set(EBookAccount::Lines, vec![11], 11);
+
+        // The tree changed only by the abandoned fork is back to its slot 10 data
+        assert_eq!(market.accounts.bids_tree, Some(vec![10]));
+        assert_eq!(market.accounts.lines, Some(vec![11]));
+        assert_eq!(market.roll_back_before(10), None);
+    }
+}
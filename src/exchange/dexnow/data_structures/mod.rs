@@ -6,4 +6,12 @@ pub(super) mod line_px;
 pub(super) mod instrument;
 pub(super) mod spot;
 pub(super) mod constants;
-pub(super) mod instr_dynamic_account;
\ No newline at end of file
+pub(super) mod instr_dynamic_account;
+pub(super) mod full_book;
+pub(super) mod candle;pub(super) mod trace_record;
+pub(super) mod client;
+
+pub(super) mod layouts;
+pub(super) mod book_history;
+pub(super) mod pending_slots;
+pub(super) mod market_accounts;
\ No newline at end of file
@@ -1,5 +1,6 @@
 #![allow(dead_code)]
 /// Contains data about option strike
+#[derive(Debug, Clone, PartialEq)]
 pub struct OptionStrike {
     /// Strike ID
     pub id: u64,
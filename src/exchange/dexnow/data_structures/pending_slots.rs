@@ -0,0 +1,62 @@
+#![allow(dead_code)]
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Keys changed in a slot whose notifications may still be arriving, with the slot of their latest change
+/// Accounts changed in one slot are notified one by one, the slot is only complete once a later slot shows up
+#[derive(Debug)]
+pub struct PendingSlots<K> {
+    pending: HashMap<K, u64>,
+}
+
+impl<K> Default for PendingSlots<K> {
+    fn default() -> Self {
+        Self { pending: HashMap::new() }
+    }
+}
+
+impl<K: Copy + Hash + Eq> PendingSlots<K> {
+    /// Mark the key as changed in the slot
+    pub fn mark(&mut self, key: K, slot: u64) {
+        self.pending.insert(key, slot);
+    }
+
+    /// Remove the keys changed in `slot` or before, in slot order
+    pub fn settle(&mut self, slot: u64) -> Vec<(K, u64)> {
+        let mut settled: Vec<(K, u64)> = self.pending.iter()
+            .filter(|(_, pending_slot)| **pending_slot <= slot)
+            .map(|(key, pending_slot)| (*key, *pending_slot))
+            .collect();
+        settled.sort_by_key(|(_, pending_slot)| *pending_slot);
+        for (key, _) in &settled {
+            self.pending.remove(key);
+        }
+        settled
+    }
+
+    pub fn clear(&mut self) {
+        self.pending.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settle_returns_keys_of_completed_slots() {
+        let mut pending = PendingSlots::default();
+        pending.mark("spot", 10);
+        pending.mark("futures", 9);
+        pending.mark("spot", 11);
+
+        assert_eq!(pending.settle(10), vec![("futures", 9)]);
+        assert_eq!(pending.settle(10), vec![]);
+        assert_eq!(pending.settle(11), vec![("spot", 11)]);
+        assert_eq!(pending.len(), 0);
+    }
+}
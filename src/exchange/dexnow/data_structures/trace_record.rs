@@ -0,0 +1,9 @@
+use crate::exchange::trade_tape::Trade;
+
+/// A trade of the spot market read from the instrument trace account
+#[derive(Debug, Clone)]
+pub struct TraceRecord {
+    /// Sequence number of the trade, increasing across the instrument lifetime
+    pub id: u64,
+    pub trade: Trade,
+}
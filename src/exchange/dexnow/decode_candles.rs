@@ -0,0 +1,133 @@
+use crate::exchange::dexnow::data_structures::candle::{Candle, ECandleInterval};
+use crate::exchange::dexnow::data_structures::constants::*;
+use crate::exchange::dexnow::dexnow_engine::DEXnowEngine;
+use crate::exchange::solana::account_data::{check_size, read_i64, read_u32, AccountDecodeError};
+
+impl DEXnowEngine {
+    /// Load the spot bars of the instrument from its candle account, oldest first
+    pub async fn candles(&self, instr_id: u64, interval: ECandleInterval) -> Result<Vec<Candle>, Box<dyn std::error::Error>> {
+        let instrument = self.instruments.get(&instr_id).ok_or("Instrument not loaded")?;
+        let (account, tag) = match interval {
+            ECandleInterval::M1 => (&instrument.spot.m1_candles_account, SPOT_1M_CANDLES_TAG),
+            ECandleInterval::M15 => (&instrument.spot.m15_candles_account, SPOT_15M_CANDLES_TAG),
+            ECandleInterval::Day => (&instrument.spot.day_candles_account, SPOT_DAY_CANDLES_TAG),
+        };
+        let data = self.connection().get_account_data(account).await?;
+        self.check_account_header(&data, tag)?;
+        let candles = decode_candles(
+            &data,
+            interval.capacity(),
+            self.token_dec(instrument.asset_token_id)?,
+            self.token_dec(instrument.base_crncy_token_id)?,
+        )?;
+        Ok(candles)
+    }
+}
+
+/// Read the ring buffer of bars in chronological order
+/// `head` is the slot the next bar is written to and `count` the number of filled slots
+fn decode_candles(
+    data: &[u8],
+    capacity: usize,
+    asset_token_dec: f64,
+    base_crncy_token_dec: f64,
+) -> Result<Vec<Candle>, AccountDecodeError> {
+    check_size(data, CANDLES_ACCOUNT_HEADER_SIZE + capacity * CANDLE_SIZE)?;
+    let head = read_u32(data, CANDLES_ACCOUNT_HEAD_OFFSET);
+    let count = read_u32(data, CANDLES_ACCOUNT_COUNT_OFFSET) as usize;
+    if head as usize >= capacity || count > capacity {
+        return Err(AccountDecodeError::CorruptedLinks { index: head });
+    }
+    let px = |offset: usize| read_i64(data, offset) as f64 / DEC as f64;
+
+    let oldest = (head as usize + capacity - count) % capacity;
+    let candles = (0..count)
+        .map(|i| CANDLES_ACCOUNT_HEADER_SIZE + (oldest + i) % capacity * CANDLE_SIZE)
+        .map(|offset| Candle {
+            time: read_u32(data, offset + CANDLE_TIME_OFFSET) as u64,
+            open: px(offset + CANDLE_OPEN_OFFSET),
+            high: px(offset + CANDLE_HIGH_OFFSET),
+            low: px(offset + CANDLE_LOW_OFFSET),
+            close: px(offset + CANDLE_CLOSE_OFFSET),
+            volume: read_i64(data, offset + CANDLE_VOLUME_OFFSET) as f64 / asset_token_dec,
+            turnover: read_i64(data, offset + CANDLE_TURNOVER_OFFSET) as f64 / base_crncy_token_dec,
+            trades: read_u32(data, offset + CANDLE_TRADES_OFFSET),
+        })
+        .filter(|candle| candle.time != 0)
+        .collect();
+    Ok(candles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CAPACITY: usize = 4;
+
+    fn write_u32(data: &mut [u8], offset: usize, value: u32) {
+        data[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+    }
+
+    fn write_i64(data: &mut [u8], offset: usize, value: i64) {
+        data[offset..offset + 8].copy_from_slice(&value.to_le_bytes());
+    }
+
+    fn candles_account(head: u32, count: u32, times: &[(usize, u32)]) -> Vec<u8> {
+        let mut data = vec![0u8; CANDLES_ACCOUNT_HEADER_SIZE + CAPACITY * CANDLE_SIZE];
+        write_u32(&mut data, CANDLES_ACCOUNT_HEAD_OFFSET, head);
+        write_u32(&mut data, CANDLES_ACCOUNT_COUNT_OFFSET, count);
+        for (slot, time) in times {
+            let offset = CANDLES_ACCOUNT_HEADER_SIZE + slot * CANDLE_SIZE;
+            write_u32(&mut data, offset + CANDLE_TIME_OFFSET, *time);
+            write_u32(&mut data, offset + CANDLE_TRADES_OFFSET, 3);
+            write_i64(&mut data, offset + CANDLE_OPEN_OFFSET, 150_000_000_000);
+            write_i64(&mut data, offset + CANDLE_HIGH_OFFSET, 151_000_000_000);
+            write_i64(&mut data, offset + CANDLE_LOW_OFFSET, 149_500_000_000);
+            write_i64(&mut data, offset + CANDLE_CLOSE_OFFSET, 150_500_000_000);
+            write_i64(&mut data, offset + CANDLE_VOLUME_OFFSET, 2_000_000_000);
+            write_i64(&mut data, offset + CANDLE_TURNOVER_OFFSET, 301_000_000);
+        }
+        data
+    }
+
+    fn times(candles: &[Candle]) -> Vec<u64> {
+        candles.iter().map(|candle| candle.time).collect()
+    }
+
+    #[test]
+    fn test_decode_candles() {
+        let data = candles_account(2, 2, &[(0, 60), (1, 120)]);
+
+        let candles = decode_candles(&data, CAPACITY, 1e9, 1e6).unwrap();
+
+        assert_eq!(candles[0], Candle {
+            time: 60,
+            open: 150.0,
+            high: 151.0,
+            low: 149.5,
+            close: 150.5,
+            volume: 2.0,
+            turnover: 301.0,
+            trades: 3,
+        });
+        assert_eq!(times(&candles), vec![60, 120]);
+    }
+
+    #[test]
+    fn test_decode_candles_wraps_around() {
+        let data = candles_account(1, 4, &[(0, 240), (1, 60), (2, 120), (3, 180)]);
+
+        let candles = decode_candles(&data, CAPACITY, 1e9, 1e6).unwrap();
+
+        assert_eq!(times(&candles), vec![60, 120, 180, 240]);
+    }
+
+    #[test]
+    fn test_decode_candles_rejects_invalid_head() {
+        let data = candles_account(4, 1, &[]);
+
+        let result = decode_candles(&data, CAPACITY, 1e9, 1e6);
+
+        assert_eq!(result, Err(AccountDecodeError::CorruptedLinks { index: 4 }));
+    }
+}
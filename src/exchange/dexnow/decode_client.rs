@@ -0,0 +1,163 @@
+use solana_sdk::pubkey::Pubkey;
+use crate::exchange::dexnow::data_structures::client::{Client, ClientBalance, ClientOrder, EOrderSide};
+use crate::exchange::dexnow::data_structures::constants::*;
+use crate::exchange::dexnow::data_structures::full_book::{BookLine, FullBook};
+use crate::exchange::dexnow::decode_error::DecodeError;
+use crate::exchange::dexnow::dexnow_engine::DEXnowEngine;
+use crate::exchange::solana::account_data::{check_size, read_i64, read_pubkey, read_u32, AccountDecodeError};
+
+impl DEXnowEngine {
+    /// Address of the client primary account of the wallet
+    pub fn client_primary_account_address(&self, wallet: &Pubkey) -> Pubkey {
+        let mut buf = [0u8; 8];
+        buf[0..4].copy_from_slice(&(self.version as u32).to_le_bytes());
+        buf[4..8].copy_from_slice(&(CLIENT_PRIMARY_TAG as u32).to_le_bytes());
+        Pubkey::find_program_address(&[&buf, &wallet.to_bytes()], &self.program_id).0
+    }
+
+    /// Find the client of the wallet and load its accounts and token balances
+    pub async fn load_client(&mut self, wallet: &Pubkey) -> Result<(), Box<dyn std::error::Error>> {
+        let primary_account = self.client_primary_account_address(wallet);
+        let data = self.connection().get_account_data(&primary_account).await
+            .map_err(|e| format!("Client primary account {} of wallet {} not found: {}", primary_account, wallet, e))?;
+        let mut client = self.decode_client_primary_account(&data)?;
+        client.primary_account = primary_account;
+
+        self.original_client_id = Some(client.id as u64);
+        self.client_primary_account = Some(primary_account);
+        self.client_drv_account = Some(client.drv_account);
+        self.client_dexnow_account = Some(client.dexnow_account);
+        self.client_lut_account = client.lut_account;
+        println!("[INFO][DEXnow] Loaded client {} of wallet {} with {} token balances", client.id, wallet, client.balances.len());
+        *self.client.get_mut() = Some(client);
+        Ok(())
+    }
+
+    /// Decode the client primary account, balances of tokens unknown to the engine are skipped
+    pub fn decode_client_primary_account(&self, data: &[u8]) -> Result<Client, DecodeError> {
+        self.check_account_header(data, CLIENT_PRIMARY_TAG)?;
+        Ok(decode_client_primary_account(data, |token_id| self.token_dec(token_id).ok())?)
+    }
+}
+
+fn decode_client_primary_account(
+    data: &[u8],
+    token_dec: impl Fn(u64) -> Option<f64>,
+) -> Result<Client, AccountDecodeError> {
+    check_size(data, CLIENT_PRIMARY_ACCOUNT_HEADER_SIZE)?;
+    let assets_count = read_u32(data, CLIENT_PRIMARY_ACCOUNT_ASSETS_COUNT_OFFSET) as usize;
+    check_size(data, CLIENT_PRIMARY_ACCOUNT_HEADER_SIZE + assets_count * CLIENT_ASSET_SIZE)?;
+
+    let balances = (0..assets_count)
+        .map(|i| CLIENT_PRIMARY_ACCOUNT_HEADER_SIZE + i * CLIENT_ASSET_SIZE)
+        .filter_map(|offset| {
+            let token_id = read_u32(data, offset + CLIENT_ASSET_TOKEN_ID_OFFSET) as u64;
+            let amount = read_i64(data, offset + CLIENT_ASSET_AMOUNT_OFFSET) as f64 / token_dec(token_id)?;
+            Some(ClientBalance { token_id, amount })
+        })
+        .collect();
+    let lut_account = read_pubkey(data, CLIENT_PRIMARY_ACCOUNT_LUT_ADDRESS_OFFSET);
+    Ok(Client {
+        wallet: read_pubkey(data, CLIENT_PRIMARY_ACCOUNT_WALLET_ADDRESS_OFFSET),
+        id: read_u32(data, CLIENT_PRIMARY_ACCOUNT_ID_OFFSET),
+        drv_account: read_pubkey(data, CLIENT_PRIMARY_ACCOUNT_DRV_ADDRESS_OFFSET),
+        dexnow_account: read_pubkey(data, CLIENT_PRIMARY_ACCOUNT_DEXNOW_ADDRESS_OFFSET),
+        lut_account: (lut_account != Pubkey::default()).then_some(lut_account),
+        balances,
+        ..Client::default()
+    })
+}
+
+/// Orders of the client in the full book of the instrument, bids from the best price first, then asks
+pub fn client_open_orders(client_id: u32, instr_id: u64, book: &FullBook) -> Vec<ClientOrder> {
+    let side_orders = |lines: &[BookLine], side: EOrderSide| -> Vec<ClientOrder> {
+        lines.iter()
+            .flat_map(|book_line| book_line.orders.iter().map(move |order| (book_line.line.px, order)))
+            .filter(|(_, order)| order.client_id == client_id)
+            .map(|(px, order)| ClientOrder { instr_id, side, id: order.id, px, qty: order.qty, time: order.time })
+            .collect()
+    };
+    let mut orders = side_orders(&book.bids, EOrderSide::Bid);
+    orders.extend(side_orders(&book.asks, EOrderSide::Ask));
+    orders
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exchange::dexnow::data_structures::full_book::BookOrder;
+    use crate::exchange::dexnow::data_structures::line_px::LinePx;
+
+    fn write_u32(data: &mut [u8], offset: usize, value: u32) {
+        data[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+    }
+
+    fn write_i64(data: &mut [u8], offset: usize, value: i64) {
+        data[offset..offset + 8].copy_from_slice(&value.to_le_bytes());
+    }
+
+    fn primary_account(balances: &[(u32, i64)]) -> Vec<u8> {
+        let mut data = vec![0u8; CLIENT_PRIMARY_ACCOUNT_HEADER_SIZE + balances.len() * CLIENT_ASSET_SIZE];
+        write_u32(&mut data, CLIENT_PRIMARY_ACCOUNT_ID_OFFSET, 77);
+        write_u32(&mut data, CLIENT_PRIMARY_ACCOUNT_ASSETS_COUNT_OFFSET, balances.len() as u32);
+        data[CLIENT_PRIMARY_ACCOUNT_DRV_ADDRESS_OFFSET..][..32].copy_from_slice(&[3u8; 32]);
+        for (i, (token_id, amount)) in balances.iter().enumerate() {
+            let offset = CLIENT_PRIMARY_ACCOUNT_HEADER_SIZE + i * CLIENT_ASSET_SIZE;
+            write_u32(&mut data, offset + CLIENT_ASSET_TOKEN_ID_OFFSET, *token_id);
+            write_i64(&mut data, offset + CLIENT_ASSET_AMOUNT_OFFSET, *amount);
+        }
+        data
+    }
+
+    fn token_dec(token_id: u64) -> Option<f64> {
+        match token_id {
+            0 => Some(1e9),
+            1 => Some(1e6),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_decode_client_primary_account() {
+        let data = primary_account(&[(0, 2_500_000_000), (1, 1_000_000_000), (9, 1)]);
+
+        let client = decode_client_primary_account(&data, token_dec).unwrap();
+
+        assert_eq!(client.id, 77);
+        assert_eq!(client.drv_account, Pubkey::new_from_array([3u8; 32]));
+        assert_eq!(client.lut_account, None);
+        assert_eq!(client.balances, vec![
+            ClientBalance { token_id: 0, amount: 2.5 },
+            ClientBalance { token_id: 1, amount: 1000.0 },
+        ]);
+    }
+
+    #[test]
+    fn test_decode_client_primary_account_rejects_missing_assets() {
+        let mut data = primary_account(&[(0, 1)]);
+        write_u32(&mut data, CLIENT_PRIMARY_ACCOUNT_ASSETS_COUNT_OFFSET, 2);
+
+        let result = decode_client_primary_account(&data, token_dec);
+
+        assert_eq!(result, Err(AccountDecodeError::AccountTooSmall {
+            expected: CLIENT_PRIMARY_ACCOUNT_HEADER_SIZE + 2 * CLIENT_ASSET_SIZE,
+            actual: data.len(),
+        }));
+    }
+
+    #[test]
+    fn test_client_open_orders() {
+        let order = |id: u64, client_id: u32| BookOrder { id, client_id, qty: 1.5, time: 1_700_000_000 };
+        let book = FullBook {
+            bids: vec![BookLine { line: LinePx { px: 150.0, qty: 3.0 }, orders: vec![order(1, 5), order(2, 77)] }],
+            asks: vec![BookLine { line: LinePx { px: 151.0, qty: 1.5 }, orders: vec![order(3, 77)] }],
+        };
+
+        let orders = client_open_orders(77, 4, &book);
+
+        assert_eq!(orders, vec![
+            ClientOrder { instr_id: 4, side: EOrderSide::Bid, id: 2, px: 150.0, qty: 1.5, time: 1_700_000_000 },
+            ClientOrder { instr_id: 4, side: EOrderSide::Ask, id: 3, px: 151.0, qty: 1.5, time: 1_700_000_000 },
+        ]);
+    }
+}
@@ -0,0 +1,62 @@
+use thiserror::Error;
+use crate::exchange::dexnow::data_structures::constants::{ACCOUNT_TAG_OFFSET, ACCOUNT_VERSION_OFFSET};
+use crate::exchange::dexnow::dexnow_engine::DEXnowEngine;
+use crate::exchange::solana::account_data::{check_size, read_u32, AccountDecodeError};
+
+/// Reasons a DEXnow account can not be decoded, the driver logs them and skips the update
+#[derive(Error, Debug, PartialEq)]
+pub enum DecodeError {
+    #[error(transparent)]
+    Account(#[from] AccountDecodeError),
+    #[error("Unexpected account tag: expected {expected}, got {actual}")]
+    UnexpectedTag { expected: u32, actual: u32 },
+    #[error("Unsupported account version: expected {expected}, got {actual}")]
+    UnsupportedVersion { expected: u32, actual: u32 },
+    #[error("Instrument {0} not found")]
+    UnknownInstrument(u64),
+    #[error("Token {0} not found")]
+    UnknownToken(u64),
+}
+
+impl DEXnowEngine {
+    /// Make sure the account has the expected tag and the version of the root account
+    pub fn check_account_header(&self, data: &[u8], tag: u8) -> Result<(), DecodeError> {
+        check_account_header(data, tag as u32, self.version as u32)
+    }
+}
+
+fn check_account_header(data: &[u8], tag: u32, version: u32) -> Result<(), DecodeError> {
+    check_size(data, ACCOUNT_VERSION_OFFSET + 4)?;
+    let actual_tag = read_u32(data, ACCOUNT_TAG_OFFSET);
+    if actual_tag != tag {
+        return Err(DecodeError::UnexpectedTag { expected: tag, actual: actual_tag });
+    }
+    let actual_version = read_u32(data, ACCOUNT_VERSION_OFFSET);
+    if actual_version != version {
+        return Err(DecodeError::UnsupportedVersion { expected: version, actual: actual_version });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(tag: u32, version: u32) -> Vec<u8> {
+        [tag.to_le_bytes(), version.to_le_bytes()].concat()
+    }
+
+    #[test]
+    fn test_check_account_header() {
+        assert_eq!(check_account_header(&header(7, 1), 7, 1), Ok(()));
+        assert_eq!(check_account_header(&header(8, 1), 7, 1), Err(DecodeError::UnexpectedTag { expected: 7, actual: 8 }));
+        assert_eq!(check_account_header(&header(7, 2), 7, 1), Err(DecodeError::UnsupportedVersion { expected: 1, actual: 2 }));
+    }
+
+    #[test]
+    fn test_check_account_header_rejects_short_data() {
+        let result = check_account_header(&[7, 0, 0], 7, 1);
+
+        assert_eq!(result, Err(DecodeError::Account(AccountDecodeError::AccountTooSmall { expected: 8, actual: 3 })));
+    }
+}
@@ -0,0 +1,252 @@
+use crate::exchange::dexnow::data_structures::constants::*;
+use crate::exchange::dexnow::data_structures::instrument::Instrument;
+use crate::exchange::dexnow::data_structures::line_px::LinePx;
+use crate::exchange::dexnow::data_structures::full_book::{BookAccounts, BookLine, BookOrder, FullBook};
+use crate::exchange::dexnow::decode_error::DecodeError;
+use crate::exchange::dexnow::dexnow_engine::DEXnowEngine;
+use crate::exchange::solana::account_data::{check_size, read_i64, read_u32, read_u64, AccountDecodeError};
+
+impl DEXnowEngine {
+    /// Rebuild the complete spot or futures book of the instrument, None until both trees and the lines account are known
+    /// Orders are decoded only if both orders accounts are known
+    pub fn decode_full_book(
+        &self,
+        instrument: &Instrument,
+        accounts: &BookAccounts,
+    ) -> Result<Option<FullBook>, DecodeError> {
+        let (Some(bids_tree), Some(asks_tree), Some(lines)) = (&accounts.bids_tree, &accounts.asks_tree, &accounts.lines) else {
+            return Ok(None);
+        };
+        let (bid_orders, ask_orders) = match (&accounts.bid_orders, &accounts.ask_orders) {
+            (Some(bid_orders), Some(ask_orders)) => (Some(bid_orders.as_slice()), Some(ask_orders.as_slice())),
+            _ => (None, None),
+        };
+        let asset_token_dec = self.token_dec(instrument.asset_token_id)?;
+
+        let mut bids = decode_book_side(bids_tree, lines, bid_orders, asset_token_dec)?;
+        let mut asks = decode_book_side(asks_tree, lines, ask_orders, asset_token_dec)?;
+        bids.sort_by(|a, b| b.line.px.total_cmp(&a.line.px));
+        asks.sort_by(|a, b| a.line.px.total_cmp(&b.line.px));
+        Ok(Some(FullBook { bids, asks }))
+    }
+}
+
+/// Decode the lines of one side of the book in tree order, with their orders if the orders account is given
+/// Lines without price or quantity are skipped: the tree may already reference a line whose
+/// account notification has not arrived yet
+fn decode_book_side(
+    tree: &[u8],
+    lines: &[u8],
+    orders: Option<&[u8]>,
+    asset_token_dec: f64,
+) -> Result<Vec<BookLine>, AccountDecodeError> {
+    let mut book_lines = Vec::new();
+    for line_index in tree_line_indices(tree)? {
+        let offset = record_offset(lines, line_index, LINE_SIZE)?;
+        let px = read_i64(lines, offset + LINE_PX_OFFSET) as f64 / DEC as f64;
+        let qty = read_i64(lines, offset + LINE_QTY_OFFSET) as f64 / asset_token_dec;
+        if px <= 0.0 || qty <= 0.0 {
+            continue;
+        }
+        let orders = match orders {
+            Some(orders) => decode_line_orders(orders, read_u32(lines, offset + LINE_SREF_OFFSET), asset_token_dec)?,
+            None => Vec::new(),
+        };
+        book_lines.push(BookLine {
+            line: LinePx { px, qty },
+            orders,
+        });
+    }
+    Ok(book_lines)
+}
+
+/// Indices of the lines referenced by the tree, every tree node has the index of its line
+fn tree_line_indices(tree: &[u8]) -> Result<Vec<u32>, AccountDecodeError> {
+    check_size(tree, BOOK_ACCOUNT_HEADER_SIZE)?;
+    let capacity = (tree.len() - BOOK_ACCOUNT_HEADER_SIZE) / TREE_NODE_SIZE;
+    let mut visited = vec![false; capacity];
+    let mut indices = Vec::new();
+    let mut stack = vec![read_u32(tree, TREE_ACCOUNT_ROOT_OFFSET)];
+
+    while let Some(index) = stack.pop() {
+        if index == NULL_LINE {
+            continue;
+        }
+        let offset = record_offset(tree, index, TREE_NODE_SIZE)?;
+        if std::mem::replace(&mut visited[index as usize], true) {
+            return Err(AccountDecodeError::CorruptedLinks { index });
+        }
+        indices.push(index);
+        stack.push(read_u32(tree, offset + TREE_NODE_RIGHT_OFFSET));
+        stack.push(read_u32(tree, offset + TREE_NODE_LEFT_OFFSET));
+    }
+    Ok(indices)
+}
+
+/// Follow the queue of orders of a line starting from its first order
+fn decode_line_orders(orders: &[u8], first: u32, asset_token_dec: f64) -> Result<Vec<BookOrder>, AccountDecodeError> {
+    check_size(orders, BOOK_ACCOUNT_HEADER_SIZE)?;
+    let capacity = (orders.len() - BOOK_ACCOUNT_HEADER_SIZE) / ORDER_SIZE;
+    let mut book_orders = Vec::new();
+    let mut index = first;
+
+    while index != NULL_ORDER as u32 {
+        if book_orders.len() >= capacity {
+            return Err(AccountDecodeError::CorruptedLinks { index });
+        }
+        let offset = record_offset(orders, index, ORDER_SIZE)?;
+        book_orders.push(BookOrder {
+            id: read_u64(orders, offset + ORDER_ID_OFFSET),
+            client_id: read_u32(orders, offset + ORDER_CLIENT_ID_OFFSET),
+            qty: read_i64(orders, offset + ORDER_QTY_OFFSET) as f64 / asset_token_dec,
+            time: read_u32(orders, offset + ORDER_TIME_OFFSET),
+        });
+        index = read_u32(orders, offset + ORDER_NEXT_OFFSET);
+    }
+    Ok(book_orders)
+}
+
+/// Offset of the record with the given index after the account header, checked against the account size
+fn record_offset(data: &[u8], index: u32, record_size: usize) -> Result<usize, AccountDecodeError> {
+    let offset = BOOK_ACCOUNT_HEADER_SIZE + index as usize * record_size;
+    check_size(data, offset + record_size)?;
+    Ok(offset)
+}
+
+/// Book accounts built from their records, for the tests of the book decoding and publishing
+#[cfg(test)]
+pub(crate) mod book_fixtures {
+    use crate::exchange::dexnow::data_structures::constants::*;
+
+    pub const ASSET_TOKEN_DEC: f64 = 1e9;
+
+    pub fn account(records: usize, record_size: usize) -> Vec<u8> {
+        vec![0u8; BOOK_ACCOUNT_HEADER_SIZE + records * record_size]
+    }
+
+    pub fn write_u32(data: &mut [u8], offset: usize, value: u32) {
+        data[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn write_i64(data: &mut [u8], offset: usize, value: i64) {
+        data[offset..offset + 8].copy_from_slice(&value.to_le_bytes());
+    }
+
+    /// Tree with `root` and its `left` and `right` children
+    pub fn tree(root: u32, left: u32, right: u32) -> Vec<u8> {
+        let mut tree = account(4, TREE_NODE_SIZE);
+        write_u32(&mut tree, TREE_ACCOUNT_ROOT_OFFSET, root);
+        for index in 0..4 {
+            let offset = BOOK_ACCOUNT_HEADER_SIZE + index * TREE_NODE_SIZE;
+            write_u32(&mut tree, offset + TREE_NODE_LEFT_OFFSET, NULL_LINE);
+            write_u32(&mut tree, offset + TREE_NODE_RIGHT_OFFSET, NULL_LINE);
+        }
+        let root_offset = BOOK_ACCOUNT_HEADER_SIZE + root as usize * TREE_NODE_SIZE;
+        write_u32(&mut tree, root_offset + TREE_NODE_LEFT_OFFSET, left);
+        write_u32(&mut tree, root_offset + TREE_NODE_RIGHT_OFFSET, right);
+        tree
+    }
+
+    pub fn write_line(lines: &mut [u8], index: usize, px: f64, qty: f64, first_order: u32) {
+        let offset = BOOK_ACCOUNT_HEADER_SIZE + index * LINE_SIZE;
+        write_i64(lines, offset + LINE_PX_OFFSET, (px * DEC as f64) as i64);
+        write_i64(lines, offset + LINE_QTY_OFFSET, (qty * ASSET_TOKEN_DEC) as i64);
+        write_u32(lines, offset + LINE_SREF_OFFSET, first_order);
+    }
+
+    pub fn write_order(orders: &mut [u8], index: usize, id: u64, qty: f64, next: u32) {
+        let offset = BOOK_ACCOUNT_HEADER_SIZE + index * ORDER_SIZE;
+        write_i64(orders, offset + ORDER_QTY_OFFSET, (qty * ASSET_TOKEN_DEC) as i64);
+        write_u32(orders, offset + ORDER_CLIENT_ID_OFFSET, 7);
+        write_i64(orders, offset + ORDER_ID_OFFSET, id as i64);
+        write_u32(orders, offset + ORDER_NEXT_OFFSET, next);
+    }
+
+    /// Tree without any line
+    pub fn empty_tree() -> Vec<u8> {
+        let mut tree = account(1, TREE_NODE_SIZE);
+        write_u32(&mut tree, TREE_ACCOUNT_ROOT_OFFSET, NULL_LINE);
+        tree
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::book_fixtures::*;
+
+    #[test]
+    fn test_decode_book_side() {
+        let mut lines = account(4, LINE_SIZE);
+        write_line(&mut lines, 1, 150.5, 2.0, NULL_ORDER as u32);
+        write_line(&mut lines, 2, 150.0, 1.5, NULL_ORDER as u32);
+        write_line(&mut lines, 3, 151.0, 0.5, NULL_ORDER as u32);
+
+        let book_lines = decode_book_side(&tree(1, 2, 3), &lines, None, ASSET_TOKEN_DEC).unwrap();
+
+        let prices: Vec<f64> = book_lines.iter().map(|book_line| book_line.line.px).collect();
+        assert_eq!(prices, vec![150.5, 150.0, 151.0]);
+        assert_eq!(book_lines[0].line.qty, 2.0);
+        assert!(book_lines.iter().all(|book_line| book_line.orders.is_empty()));
+    }
+
+    #[test]
+    fn test_decode_book_side_with_orders() {
+        let mut lines = account(4, LINE_SIZE);
+        write_line(&mut lines, 1, 150.5, 2.0, 3);
+        let mut orders = account(4, ORDER_SIZE);
+        write_order(&mut orders, 3, 30, 1.25, 0);
+        write_order(&mut orders, 0, 10, 0.75, NULL_ORDER as u32);
+
+        let book_lines = decode_book_side(&tree(1, NULL_LINE, NULL_LINE), &lines, Some(&orders), ASSET_TOKEN_DEC).unwrap();
+
+        let order_ids: Vec<u64> = book_lines[0].orders.iter().map(|order| order.id).collect();
+        assert_eq!(order_ids, vec![30, 10]);
+        assert_eq!(book_lines[0].orders[0].qty, 1.25);
+        assert_eq!(book_lines[0].orders[0].client_id, 7);
+    }
+
+    #[test]
+    fn test_decode_book_side_skips_empty_lines() {
+        let mut lines = account(4, LINE_SIZE);
+        write_line(&mut lines, 1, 150.5, 2.0, NULL_ORDER as u32);
+
+        let book_lines = decode_book_side(&tree(1, 2, NULL_LINE), &lines, None, ASSET_TOKEN_DEC).unwrap();
+
+        assert_eq!(book_lines.len(), 1);
+    }
+
+    #[test]
+    fn test_decode_book_side_rejects_out_of_range_line() {
+        let lines = account(2, LINE_SIZE);
+
+        let result = decode_book_side(&tree(3, NULL_LINE, NULL_LINE), &lines, None, ASSET_TOKEN_DEC);
+
+        assert_eq!(result, Err(AccountDecodeError::AccountTooSmall {
+            expected: BOOK_ACCOUNT_HEADER_SIZE + 4 * LINE_SIZE,
+            actual: BOOK_ACCOUNT_HEADER_SIZE + 2 * LINE_SIZE,
+        }));
+    }
+
+    #[test]
+    fn test_decode_book_side_rejects_cycles() {
+        let lines = account(4, LINE_SIZE);
+        // The left child of the root points back to the root
+        let tree = tree(1, 1, NULL_LINE);
+
+        let result = decode_book_side(&tree, &lines, None, ASSET_TOKEN_DEC);
+
+        assert_eq!(result, Err(AccountDecodeError::CorruptedLinks { index: 1 }));
+    }
+
+    #[test]
+    fn test_decode_line_orders_rejects_cycles() {
+        let mut orders = account(2, ORDER_SIZE);
+        write_order(&mut orders, 0, 1, 1.0, 1);
+        write_order(&mut orders, 1, 2, 1.0, 0);
+
+        let result = decode_line_orders(&orders, 0, ASSET_TOKEN_DEC);
+
+        assert_eq!(result, Err(AccountDecodeError::CorruptedLinks { index: 0 }));
+    }
+}
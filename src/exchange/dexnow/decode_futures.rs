@@ -0,0 +1,106 @@
+use crate::exchange::dexnow::data_structures::constants::*;
+use crate::exchange::dexnow::data_structures::full_book::FullBook;
+use crate::exchange::dexnow::data_structures::derivative::Derivative;
+use crate::exchange::dexnow::data_structures::futures::Futures;
+use crate::exchange::dexnow::data_structures::instrument::Instrument;
+use crate::exchange::dexnow::decode_error::DecodeError;
+use crate::exchange::dexnow::dexnow_engine::DEXnowEngine;
+use crate::exchange::dexnow::data_structures::layouts::{cast_slice, TaskDynamicLayout};
+use crate::exchange::solana::account_data::AccountDecodeError;
+
+impl DEXnowEngine {
+    /// Fill the statistics of every futures market from the derivatives section of the instrument dynamic account
+    pub fn decode_futures_stats(&self, data: &[u8], instrument: &mut Instrument) -> Result<(), DecodeError> {
+        self.check_account_header(data, INSTR_DYNAMIC_TAG)?;
+        let asset_token_dec = self.token_dec(instrument.asset_token_id)?;
+        Ok(decode_futures_stats(data, &mut instrument.derivatives, asset_token_dec)?)
+    }
+}
+
+/// Replace the quotes of the futures market with the lines of its full book
+pub fn apply_futures_book(futures: &mut Futures, book: FullBook) {
+    futures.bid_quotes = Some(book.bids.into_iter().map(|book_line| book_line.line).collect());
+    futures.ask_quotes = Some(book.asks.into_iter().map(|book_line| book_line.line).collect());
+}
+
+fn decode_futures_stats(data: &[u8], derivatives: &mut [Derivative], asset_token_dec: f64) -> Result<(), AccountDecodeError> {
+    let tasks = cast_slice::<TaskDynamicLayout>(data, INSTR_DYNAMIC_ACCOUNT_TASKS_OFFSET, derivatives.len())?;
+    let read_px = |value: i64| {
+        let px = value as f64 / DEC as f64;
+        if px != 0.0 { Some(px) } else { None }
+    };
+
+    for (derivative, task) in derivatives.iter_mut().zip(tasks) {
+        let futures = &mut derivative.futures;
+        futures.open_int = Some(task.open_int as f64 / asset_token_dec);
+        futures.last_px = read_px(task.last_px);
+        futures.last_close_px = read_px(task.last_close_px);
+        futures.last_futures = Some(task.last_futures as f64 / asset_token_dec);
+        futures.apr = Some(task.apr as f64 / DEC as f64);
+        futures.last_trade_time = match task.last_trade_time {
+            0 => None,
+            time => Some(time as u64),
+        };
+        futures.day_trades = Some(task.day_trades as u64);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exchange::dexnow::data_structures::full_book::BookLine;
+    use crate::exchange::dexnow::data_structures::line_px::LinePx;
+
+    fn write_i64(data: &mut [u8], offset: usize, value: i64) {
+        data[offset..offset + 8].copy_from_slice(&value.to_le_bytes());
+    }
+
+    #[test]
+    fn test_decode_futures_stats() {
+        let mut data = vec![0u8; INSTR_DYNAMIC_ACCOUNT_TASKS_OFFSET + 2 * TASK_DYNAMIC_SIZE];
+        let offset = INSTR_DYNAMIC_ACCOUNT_TASKS_OFFSET + TASK_DYNAMIC_SIZE;
+        write_i64(&mut data, offset + TASK_DYNAMIC_FUTURES_OPEN_INT_OFFSET, 12_500_000_000);
+        write_i64(&mut data, offset + TASK_DYNAMIC_FUTURES_LAST_PX_OFFSET, 151_250_000_000);
+        write_i64(&mut data, offset + TASK_DYNAMIC_FUTURES_APR_OFFSET, 85_000_000);
+        data[offset + TASK_DYNAMIC_FUTURES_LAST_TRADE_TIME_OFFSET..][..4].copy_from_slice(&1_700_000_000u32.to_le_bytes());
+        let mut derivatives = vec![Derivative::default(), Derivative::default()];
+
+        decode_futures_stats(&data, &mut derivatives, 1e9).unwrap();
+
+        assert_eq!(derivatives[0].futures.last_px, None);
+        assert_eq!(derivatives[0].futures.last_trade_time, None);
+        assert_eq!(derivatives[1].futures.open_int, Some(12.5));
+        assert_eq!(derivatives[1].futures.last_px, Some(151.25));
+        assert_eq!(derivatives[1].futures.apr, Some(0.085));
+        assert_eq!(derivatives[1].futures.last_trade_time, Some(1_700_000_000));
+    }
+
+    #[test]
+    fn test_decode_futures_stats_rejects_small_account() {
+        let data = vec![0u8; INSTR_DYNAMIC_ACCOUNT_TASKS_OFFSET + TASK_DYNAMIC_SIZE];
+        let mut derivatives = vec![Derivative::default(), Derivative::default()];
+
+        let result = decode_futures_stats(&data, &mut derivatives, 1e9);
+
+        assert_eq!(result, Err(AccountDecodeError::AccountTooSmall {
+            expected: INSTR_DYNAMIC_ACCOUNT_TASKS_OFFSET + 2 * TASK_DYNAMIC_SIZE,
+            actual: data.len(),
+        }));
+    }
+
+    #[test]
+    fn test_apply_futures_book() {
+        let mut futures = Futures::default();
+        let book = FullBook {
+            bids: vec![BookLine { line: LinePx { px: 150.0, qty: 2.0 }, orders: Vec::new() }],
+            asks: vec![BookLine { line: LinePx { px: 151.0, qty: 1.0 }, orders: Vec::new() }],
+        };
+
+        apply_futures_book(&mut futures, book);
+
+        let orderbook = futures.order_book();
+        assert_eq!(orderbook.get_best_bid(), Some(150.0.into()));
+        assert_eq!(orderbook.get_best_ask(), Some(151.0.into()));
+    }
+}
@@ -1,38 +1,69 @@
 use crate::exchange::dexnow::data_structures::constants::*;
 use crate::exchange::dexnow::data_structures::instr_dynamic_account::InstrDynamicAccount;
 use crate::exchange::dexnow::data_structures::line_px::LinePx;
+use crate::exchange::dexnow::decode_error::DecodeError;
 use crate::exchange::dexnow::dexnow_engine::DEXnowEngine;
-use crate::exchange::dexnow::utils::read_basic_types::{read_u32, read_i64};
+use crate::exchange::dexnow::data_structures::layouts::{cast, InstrDynamicAccountLayout, LinePxLayout};
+use crate::exchange::solana::account_data::AccountDecodeError;
 
 impl DEXnowEngine {
-    pub fn decode_instr_dynamic_account(&self, data: &Vec<u8>) -> InstrDynamicAccount {
-        let instr_id = read_u32(data, INSTR_DYNAMIC_ACCOUNT_ID_OFFSET) as u64;
-        let instr = self.instruments.get(&instr_id).expect("Instrument not found");
-        let asset_token_dec = self.token_dec(instr.asset_token_id);
-
-        fn read_orders(data: &Vec<u8>, start_offset: usize, asset_token_dec: f64) -> Vec<LinePx> {
-            let mut orders = Vec::new();
-            for i in 0..MARKET_DEPTH {
-                let offset = start_offset + i * 16;
-                let px = read_i64(data, offset) as f64 / DEC as f64;
-                if px == 0.0 {
-                    break;
-                }
-                let qty = read_i64(data, offset + 8) as f64 / asset_token_dec;
-                orders.push(LinePx {
-                    px,
-                    qty,
-                });
-            }
-            orders
-        }
-
-        let spot_bids = read_orders(data, INSTR_DYNAMIC_ACCOUNT_BIDS_OFFSET, asset_token_dec);
-        let spot_asks = read_orders(data, INSTR_DYNAMIC_ACCOUNT_ASKS_OFFSET, asset_token_dec);
-
-        InstrDynamicAccount {
-            spot_bids,
-            spot_asks,
-        }
+    pub fn decode_instr_dynamic_account(&self, data: &[u8]) -> Result<InstrDynamicAccount, DecodeError> {
+        self.check_account_header(data, INSTR_DYNAMIC_TAG)?;
+        let instr_id = cast::<InstrDynamicAccountLayout>(data)?.id as u64;
+        let instr = self.instruments.get(&instr_id).ok_or(DecodeError::UnknownInstrument(instr_id))?;
+        let asset_token_dec = self.token_dec(instr.asset_token_id)?;
+        Ok(decode_top_lines(data, asset_token_dec)?)
     }
-}
\ No newline at end of file
+}
+
+fn decode_top_lines(data: &[u8], asset_token_dec: f64) -> Result<InstrDynamicAccount, AccountDecodeError> {
+    let account = cast::<InstrDynamicAccountLayout>(data)?;
+
+    fn read_orders(lines: &[LinePxLayout], asset_token_dec: f64) -> Vec<LinePx> {
+        lines
+            .iter()
+            .take_while(|line| line.px != 0)
+            .map(|line| LinePx {
+                px: line.px as f64 / DEC as f64,
+                qty: line.qty as f64 / asset_token_dec,
+            })
+            .collect()
+    }
+
+    let spot_bids = read_orders(&account.bids, asset_token_dec);
+    let spot_asks = read_orders(&account.asks, asset_token_dec);
+
+    Ok(InstrDynamicAccount {
+        spot_bids,
+        spot_asks,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_top_lines() {
+        let mut data = vec![0u8; INSTR_DYNAMIC_ACCOUNT_ASKS_OFFSET + MARKET_DEPTH * 16];
+        data[INSTR_DYNAMIC_ACCOUNT_BIDS_OFFSET..][..8].copy_from_slice(&150_000_000_000i64.to_le_bytes());
+        data[INSTR_DYNAMIC_ACCOUNT_BIDS_OFFSET + 8..][..8].copy_from_slice(&2_000_000_000i64.to_le_bytes());
+
+        let decoded_account = decode_top_lines(&data, 1e9).unwrap();
+
+        assert_eq!(decoded_account.spot_bids, vec![LinePx { px: 150.0, qty: 2.0 }]);
+        assert!(decoded_account.spot_asks.is_empty());
+    }
+
+    #[test]
+    fn test_decode_top_lines_rejects_truncated_account() {
+        let data = vec![0u8; INSTR_DYNAMIC_ACCOUNT_ASKS_OFFSET];
+
+        let result = decode_top_lines(&data, 1e9);
+
+        assert_eq!(result.unwrap_err(), AccountDecodeError::AccountTooSmall {
+            expected: INSTR_DYNAMIC_ACCOUNT_ASKS_OFFSET + MARKET_DEPTH * 16,
+            actual: INSTR_DYNAMIC_ACCOUNT_ASKS_OFFSET,
+        });
+    }
+}
@@ -0,0 +1,159 @@
+use crate::exchange::dexnow::data_structures::constants::*;
+use crate::exchange::dexnow::data_structures::derivative::Derivative;
+use crate::exchange::dexnow::data_structures::instrument::Instrument;
+use crate::exchange::dexnow::data_structures::option_strike::OptionStrike;
+use crate::exchange::dexnow::data_structures::layouts::{cast, InstrDynamicAccountLayout};
+use crate::exchange::dexnow::decode_error::DecodeError;
+use crate::exchange::dexnow::dexnow_engine::DEXnowEngine;
+use crate::exchange::solana::account_data::{check_size, read_i64, read_u32, read_u64, AccountDecodeError};
+
+impl DEXnowEngine {
+    /// Fill the options data and strikes of the derivative from its maps account
+    pub fn decode_derivative_options(
+        &self,
+        instrument: &Instrument,
+        data: &[u8],
+        derivative: &mut Derivative,
+    ) -> Result<(), DecodeError> {
+        self.check_account_header(data, FUTURES_MAPS_TAG)?;
+        let asset_token_dec = self.token_dec(instrument.asset_token_id)?;
+        let base_crncy_token_dec = self.token_dec(instrument.base_crncy_token_id)?;
+        Ok(decode_derivative_options(data, derivative, asset_token_dec, base_crncy_token_dec)?)
+    }
+
+    /// Fill the estimated price and supply of the options pool token from the instrument dynamic account
+    pub fn decode_options_pool(&self, data: &[u8], instrument: &mut Instrument) -> Result<(), DecodeError> {
+        self.check_account_header(data, INSTR_DYNAMIC_TAG)?;
+        let account = cast::<InstrDynamicAccountLayout>(data)?;
+        if instrument.derivatives_count == 0 {
+            return Ok(());
+        }
+        instrument.options_pool_est_px = Some(account.options_pool_est_px as f64 / DEC as f64);
+        let pool_token = instrument.pool_token_id.and_then(|token_id| self.tokens.get(&token_id));
+        instrument.options_pool_supply = pool_token.map(|token| {
+            account.options_pool_supply as f64 / 10f64.powi(token.decimals as i32)
+        });
+        Ok(())
+    }
+}
+
+fn decode_derivative_options(
+    data: &[u8],
+    derivative: &mut Derivative,
+    asset_token_dec: f64,
+    base_crncy_token_dec: f64,
+) -> Result<(), AccountDecodeError> {
+    check_size(data, MAPS_ACCOUNT_STRIKES_OFFSET)?;
+    let px = |offset: usize| read_i64(data, offset) as f64 / DEC as f64;
+    let asset = |offset: usize| read_i64(data, offset) as f64 / asset_token_dec;
+    let base_crncy = |offset: usize| read_i64(data, offset) as f64 / base_crncy_token_dec;
+    let optional_px = |offset: usize| Some(px(offset)).filter(|px| *px != 0.0);
+
+    let strikes_count = (read_u32(data, MAPS_ACCOUNT_STRIKES_COUNT_OFFSET) as usize).min(STRIKES_COUNT as usize);
+    check_size(data, MAPS_ACCOUNT_STRIKES_OFFSET + strikes_count * STRIKE_SIZE)?;
+    let strikes = (0..strikes_count)
+        .map(|i| {
+            let offset = MAPS_ACCOUNT_STRIKES_OFFSET + i * STRIKE_SIZE;
+            OptionStrike {
+                id: read_u32(data, offset + STRIKE_ID_OFFSET) as u64,
+                price: px(offset + STRIKE_PX_OFFSET),
+                calls: asset(offset + STRIKE_CALLS_OFFSET),
+                puts: asset(offset + STRIKE_PUTS_OFFSET),
+                height: px(offset + STRIKE_HEIGHT_OFFSET),
+                call_px: optional_px(offset + STRIKE_CALL_PX_OFFSET),
+                put_px: optional_px(offset + STRIKE_PUT_PX_OFFSET),
+            }
+        })
+        .collect();
+
+    derivative.instance_id = Some(read_u32(data, MAPS_ACCOUNT_INSTANCE_ID_OFFSET) as u64);
+    derivative.expiration = Some(read_u64(data, MAPS_ACCOUNT_EXPIRATION_OFFSET));
+    derivative.px_granular = Some(px(MAPS_ACCOUNT_PX_GRANULAR_OFFSET));
+    derivative.contract_size = Some(asset(MAPS_ACCOUNT_CONTRACT_SIZE_OFFSET));
+    derivative.options_cashflow = Some(base_crncy(MAPS_ACCOUNT_OPTIONS_CASHFLOW_OFFSET));
+    derivative.options_hedge_collateral = Some(base_crncy(MAPS_ACCOUNT_OPTIONS_HEDGE_COLLATERAL_OFFSET));
+    derivative.options_hedge_pos = Some(asset(MAPS_ACCOUNT_OPTIONS_HEDGE_POS_OFFSET));
+    derivative.options_hedge_result = Some(base_crncy(MAPS_ACCOUNT_OPTIONS_HEDGE_RESULT_OFFSET));
+    derivative.options_hedge_edge = Some(px(MAPS_ACCOUNT_OPTIONS_HEDGE_EDGE_OFFSET));
+    derivative.min_px = Some(px(MAPS_ACCOUNT_MIN_PX_OFFSET));
+    derivative.max_px = Some(px(MAPS_ACCOUNT_MAX_PX_OFFSET));
+    derivative.min_px_height = Some(px(MAPS_ACCOUNT_MIN_PX_HEIGHT_OFFSET));
+    derivative.max_px_height = Some(px(MAPS_ACCOUNT_MAX_PX_HEIGHT_OFFSET));
+    derivative.options_day_notional_volume = Some(base_crncy(MAPS_ACCOUNT_OPTIONS_DAY_NOTIONAL_VOLUME_OFFSET));
+    derivative.options_day_market_volume = Some(asset(MAPS_ACCOUNT_OPTIONS_DAY_MARKET_VOLUME_OFFSET));
+    derivative.options_day_premium_volume = Some(base_crncy(MAPS_ACCOUNT_OPTIONS_DAY_PREMIUM_VOLUME_OFFSET));
+    derivative.options_day_trades = Some(read_u64(data, MAPS_ACCOUNT_OPTIONS_DAY_TRADES_OFFSET));
+    derivative.options_alltime_notional_volume = Some(base_crncy(MAPS_ACCOUNT_OPTIONS_ALLTIME_NOTIONAL_VOLUME_OFFSET));
+    derivative.options_alltime_market_volume = Some(asset(MAPS_ACCOUNT_OPTIONS_ALLTIME_MARKET_VOLUME_OFFSET));
+    derivative.options_alltime_premium_volume = Some(base_crncy(MAPS_ACCOUNT_OPTIONS_ALLTIME_PREMIUM_VOLUME_OFFSET));
+    derivative.options_alltime_trades = Some(read_u64(data, MAPS_ACCOUNT_OPTIONS_ALLTIME_TRADES_OFFSET));
+    derivative.options = Some(strikes);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_i64(data: &mut [u8], offset: usize, value: i64) {
+        data[offset..offset + 8].copy_from_slice(&value.to_le_bytes());
+    }
+
+    fn write_u32(data: &mut [u8], offset: usize, value: u32) {
+        data[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+    }
+
+    fn maps_account(strikes: usize) -> Vec<u8> {
+        vec![0u8; MAPS_ACCOUNT_STRIKES_OFFSET + strikes * STRIKE_SIZE]
+    }
+
+    #[test]
+    fn test_decode_derivative_options() {
+        let mut data = maps_account(2);
+        write_u32(&mut data, MAPS_ACCOUNT_STRIKES_COUNT_OFFSET, 2);
+        write_u32(&mut data, MAPS_ACCOUNT_INSTANCE_ID_OFFSET, 3);
+        data[MAPS_ACCOUNT_EXPIRATION_OFFSET..][..8].copy_from_slice(&1_700_000_000u64.to_le_bytes());
+        write_i64(&mut data, MAPS_ACCOUNT_MIN_PX_OFFSET, 100_000_000_000);
+        write_i64(&mut data, MAPS_ACCOUNT_OPTIONS_DAY_PREMIUM_VOLUME_OFFSET, 2_500_000);
+        let strike = MAPS_ACCOUNT_STRIKES_OFFSET + STRIKE_SIZE;
+        write_u32(&mut data, strike + STRIKE_ID_OFFSET, 7);
+        write_i64(&mut data, strike + STRIKE_PX_OFFSET, 160_000_000_000);
+        write_i64(&mut data, strike + STRIKE_CALLS_OFFSET, 4_000_000_000);
+        write_i64(&mut data, strike + STRIKE_PUTS_OFFSET, 1_500_000_000);
+        write_i64(&mut data, strike + STRIKE_CALL_PX_OFFSET, 3_250_000_000);
+        let mut derivative = Derivative::default();
+
+        decode_derivative_options(&data, &mut derivative, 1e9, 1e6).unwrap();
+
+        assert_eq!(derivative.instance_id, Some(3));
+        assert_eq!(derivative.expiration, Some(1_700_000_000));
+        assert_eq!(derivative.min_px, Some(100.0));
+        assert_eq!(derivative.options_day_premium_volume, Some(2.5));
+        let strikes = derivative.options.unwrap();
+        assert_eq!(strikes.len(), 2);
+        assert_eq!(strikes[1], OptionStrike {
+            id: 7,
+            price: 160.0,
+            calls: 4.0,
+            puts: 1.5,
+            height: 0.0,
+            call_px: Some(3.25),
+            put_px: None,
+        });
+    }
+
+    #[test]
+    fn test_decode_derivative_options_rejects_missing_strikes() {
+        let mut data = maps_account(1);
+        write_u32(&mut data, MAPS_ACCOUNT_STRIKES_COUNT_OFFSET, 2);
+        let mut derivative = Derivative::default();
+
+        let result = decode_derivative_options(&data, &mut derivative, 1e9, 1e6);
+
+        assert_eq!(result, Err(AccountDecodeError::AccountTooSmall {
+            expected: MAPS_ACCOUNT_STRIKES_OFFSET + 2 * STRIKE_SIZE,
+            actual: data.len(),
+        }));
+        assert_eq!(derivative.options, None);
+    }
+}
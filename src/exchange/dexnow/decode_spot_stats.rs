@@ -0,0 +1,120 @@
+use crate::exchange::dexnow::data_structures::constants::*;
+use crate::exchange::dexnow::data_structures::instrument::Instrument;
+use crate::exchange::dexnow::data_structures::spot::Spot;
+use crate::exchange::dexnow::decode_error::DecodeError;
+use crate::exchange::dexnow::dexnow_engine::DEXnowEngine;
+use crate::exchange::market_stats::MarketStats;
+use crate::exchange::dexnow::data_structures::layouts::{cast, InstrDynamicAccountLayout};
+use crate::exchange::solana::account_data::AccountDecodeError;
+
+impl DEXnowEngine {
+    /// Fill the spot statistics and top quotes of the instrument from its dynamic account
+    pub fn decode_spot_stats(&self, data: &[u8], instrument: &mut Instrument) -> Result<(), DecodeError> {
+        let decoded_account = self.decode_instr_dynamic_account(data)?;
+        let asset_token_dec = self.token_dec(instrument.asset_token_id)?;
+        let base_crncy_token_dec = self.token_dec(instrument.base_crncy_token_id)?;
+        decode_spot_stats(data, &mut instrument.spot, asset_token_dec, base_crncy_token_dec)?;
+
+        instrument.spot.bid_quotes = Some(decoded_account.spot_bids);
+        instrument.spot.ask_quotes = Some(decoded_account.spot_asks);
+        Ok(())
+    }
+}
+
+impl Spot {
+    /// Statistics reported to the arbitrage manager
+    pub fn market_stats(&self) -> MarketStats {
+        MarketStats {
+            last_px: self.last_px,
+            fixing_px: self.fixing_px,
+            day_sigma: self.day_sigma,
+            hour_sigma: self.hour_sigma,
+            day_volume: self.day_base_crncy_tokens,
+            day_trades: self.day_trades,
+        }
+    }
+}
+
+fn decode_spot_stats(
+    data: &[u8],
+    spot: &mut Spot,
+    asset_token_dec: f64,
+    base_crncy_token_dec: f64,
+) -> Result<(), AccountDecodeError> {
+    let account = cast::<InstrDynamicAccountLayout>(data)?;
+    let px = |value: i64| value as f64 / DEC as f64;
+    let optional_px = |value: i64| Some(px(value)).filter(|px| *px != 0.0);
+    let asset = |value: i64| value as f64 / asset_token_dec;
+    let base_crncy = |value: i64| value as f64 / base_crncy_token_dec;
+
+    spot.pool_tokens_supply = Some(asset(account.pool_tokens_supply));
+    spot.asset_tokens_in_pool = Some(asset(account.asset_tokens_in_pool));
+    spot.base_crncy_tokens_in_pool = Some(base_crncy(account.base_crncy_tokens_in_pool));
+    spot.asset_token_fees_in_pool = Some(asset(account.asset_token_fees_in_pool));
+    spot.base_crncy_token_fees_in_pool = Some(base_crncy(account.base_crncy_token_fees_in_pool));
+    spot.day_sigma = Some(px(account.day_sigma));
+    spot.day_sigma2 = Some(px(account.day_sigma2));
+    spot.hour_sigma = Some(px(account.hour_sigma));
+    spot.last_hour_px = optional_px(account.last_hour_px);
+    spot.last_px = optional_px(account.last_px);
+    spot.last_close_px = optional_px(account.last_close_px);
+    spot.fixing_px = optional_px(account.fixing_px);
+    spot.day_asset_tokens = Some(asset(account.day_asset_tokens));
+    spot.day_base_crncy_tokens = Some(base_crncy(account.day_base_crncy_tokens));
+    spot.day_trades = Some(account.day_trades);
+    spot.alltime_asset_tokens = Some(asset(account.alltime_asset_tokens));
+    spot.alltime_base_crncy_tokens = Some(base_crncy(account.alltime_base_crncy_tokens));
+    spot.alltime_trades = Some(account.alltime_trades);
+    spot.last_asset_tokens = Some(asset(account.last_asset_tokens));
+    spot.last_base_crncy_tokens = Some(base_crncy(account.last_base_crncy_tokens));
+    spot.last_trade_time = match account.last_trade_time {
+        0 => None,
+        time => Some(time as u64),
+    };
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_i64(data: &mut [u8], offset: usize, value: i64) {
+        data[offset..offset + 8].copy_from_slice(&value.to_le_bytes());
+    }
+
+    #[test]
+    fn test_decode_spot_stats() {
+        let mut data = vec![0u8; INSTR_DYNAMIC_ACCOUNT_TASKS_OFFSET];
+        write_i64(&mut data, INSTR_DYNAMIC_ACCOUNT_LAST_PX_OFFSET, 150_250_000_000);
+        write_i64(&mut data, INSTR_DYNAMIC_ACCOUNT_HOUR_SIGMA_OFFSET, 4_000_000);
+        write_i64(&mut data, INSTR_DYNAMIC_ACCOUNT_DAY_ASSET_TOKENS_OFFSET, 12_000_000_000);
+        write_i64(&mut data, INSTR_DYNAMIC_ACCOUNT_DAY_BASE_CRNCY_TOKENS_OFFSET, 1_803_000_000);
+        write_i64(&mut data, INSTR_DYNAMIC_ACCOUNT_DAY_TRADES_OFFSET, 42);
+        let mut spot = Spot::default();
+
+        decode_spot_stats(&data, &mut spot, 1e9, 1e6).unwrap();
+
+        assert_eq!(spot.market_stats(), MarketStats {
+            last_px: Some(150.25),
+            fixing_px: None,
+            day_sigma: Some(0.0),
+            hour_sigma: Some(0.004),
+            day_volume: Some(1803.0),
+            day_trades: Some(42),
+        });
+        assert_eq!(spot.day_asset_tokens, Some(12.0));
+        assert_eq!(spot.last_trade_time, None);
+    }
+
+    #[test]
+    fn test_decode_spot_stats_rejects_small_account() {
+        let data = vec![0u8; INSTR_DYNAMIC_ACCOUNT_BIDS_OFFSET];
+
+        let result = decode_spot_stats(&data, &mut Spot::default(), 1e9, 1e6);
+
+        assert_eq!(result, Err(AccountDecodeError::AccountTooSmall {
+            expected: INSTR_DYNAMIC_ACCOUNT_ASKS_OFFSET + MARKET_DEPTH * 16,
+            actual: INSTR_DYNAMIC_ACCOUNT_BIDS_OFFSET,
+        }));
+    }
+}
@@ -0,0 +1,132 @@
+use chrono::DateTime;
+use ordered_float::OrderedFloat;
+use crate::exchange::dexnow::data_structures::constants::*;
+use crate::exchange::dexnow::data_structures::instrument::Instrument;
+use crate::exchange::dexnow::data_structures::trace_record::TraceRecord;
+use crate::exchange::dexnow::decode_error::DecodeError;
+use crate::exchange::dexnow::dexnow_engine::DEXnowEngine;
+use crate::exchange::solana::account_data::{check_size, read_i64, read_u32, read_u64, read_u8, AccountDecodeError};
+use crate::exchange::trade_tape::{EAggressorSide, Trade};
+
+impl DEXnowEngine {
+    /// Read the recent spot trades of the instrument from its trace account, oldest first
+    pub fn decode_trace(&self, instrument: &Instrument, data: &[u8]) -> Result<Vec<TraceRecord>, DecodeError> {
+        self.check_account_header(data, INSTR_TRACE_TAG)?;
+        Ok(decode_trace(data, TRACE_CAPACITY as usize, self.token_dec(instrument.asset_token_id)?)?)
+    }
+}
+
+/// Read the ring buffer of trades in execution order
+/// `head` is the slot the next trade is written to and `count` the number of filled slots
+fn decode_trace(data: &[u8], capacity: usize, asset_token_dec: f64) -> Result<Vec<TraceRecord>, AccountDecodeError> {
+    check_size(data, TRACE_ACCOUNT_HEADER_SIZE + capacity * TRACE_RECORD_SIZE)?;
+    let head = read_u32(data, TRACE_ACCOUNT_HEAD_OFFSET);
+    let count = read_u32(data, TRACE_ACCOUNT_COUNT_OFFSET) as usize;
+    if head as usize >= capacity || count > capacity {
+        return Err(AccountDecodeError::CorruptedLinks { index: head });
+    }
+
+    let oldest = (head as usize + capacity - count) % capacity;
+    let records = (0..count)
+        .map(|i| TRACE_ACCOUNT_HEADER_SIZE + (oldest + i) % capacity * TRACE_RECORD_SIZE)
+        .filter(|offset| read_u32(data, offset + TRACE_RECORD_TIME_OFFSET) != 0)
+        .map(|offset| TraceRecord {
+            id: read_u64(data, offset + TRACE_RECORD_ID_OFFSET),
+            trade: Trade {
+                price: OrderedFloat(read_i64(data, offset + TRACE_RECORD_PX_OFFSET) as f64 / DEC as f64),
+                size: read_i64(data, offset + TRACE_RECORD_QTY_OFFSET) as f64 / asset_token_dec,
+                side: match read_u8(data, offset + TRACE_RECORD_SIDE_OFFSET) {
+                    0 => EAggressorSide::Buy,
+                    _ => EAggressorSide::Sell,
+                },
+                timestamp: DateTime::from_timestamp(read_u32(data, offset + TRACE_RECORD_TIME_OFFSET) as i64, 0).unwrap_or_default(),
+                slot: Some(read_u64(data, offset + TRACE_RECORD_SLOT_OFFSET)),
+            },
+        })
+        .collect();
+    Ok(records)
+}
+
+/// Records of the trace newer than the trade with `last_id`, all of them if nothing was seen yet
+pub fn new_trace_records(records: Vec<TraceRecord>, last_id: Option<u64>) -> impl Iterator<Item = TraceRecord> {
+    records.into_iter().filter(move |record| last_id.is_none_or(|last_id| record.id > last_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CAPACITY: usize = 4;
+
+    fn write_u32(data: &mut [u8], offset: usize, value: u32) {
+        data[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+    }
+
+    fn write_u64(data: &mut [u8], offset: usize, value: u64) {
+        data[offset..offset + 8].copy_from_slice(&value.to_le_bytes());
+    }
+
+    /// Trace account with the trades `(ring slot, trade id)`, odd ids are sells
+    fn trace_account(head: u32, count: u32, trades: &[(usize, u64)]) -> Vec<u8> {
+        let mut data = vec![0u8; TRACE_ACCOUNT_HEADER_SIZE + CAPACITY * TRACE_RECORD_SIZE];
+        write_u32(&mut data, TRACE_ACCOUNT_HEAD_OFFSET, head);
+        write_u32(&mut data, TRACE_ACCOUNT_COUNT_OFFSET, count);
+        for (slot, id) in trades {
+            let offset = TRACE_ACCOUNT_HEADER_SIZE + slot * TRACE_RECORD_SIZE;
+            write_u64(&mut data, offset + TRACE_RECORD_ID_OFFSET, *id);
+            write_u32(&mut data, offset + TRACE_RECORD_TIME_OFFSET, 1_700_000_000 + *id as u32);
+            data[offset + TRACE_RECORD_SIDE_OFFSET] = (*id % 2) as u8;
+            write_u64(&mut data, offset + TRACE_RECORD_PX_OFFSET, 150_250_000_000);
+            write_u64(&mut data, offset + TRACE_RECORD_QTY_OFFSET, 1_500_000_000);
+            write_u64(&mut data, offset + TRACE_RECORD_SLOT_OFFSET, 280_000_000 + *id);
+        }
+        data
+    }
+
+    fn ids(records: &[TraceRecord]) -> Vec<u64> {
+        records.iter().map(|record| record.id).collect()
+    }
+
+    #[test]
+    fn test_decode_trace() {
+        let data = trace_account(2, 2, &[(0, 10), (1, 11)]);
+
+        let records = decode_trace(&data, CAPACITY, 1e9).unwrap();
+
+        assert_eq!(ids(&records), vec![10, 11]);
+        let trade = &records[1].trade;
+        assert_eq!(trade.price, OrderedFloat(150.25));
+        assert_eq!(trade.size, 1.5);
+        assert_eq!(trade.side, EAggressorSide::Sell);
+        assert_eq!(trade.timestamp.timestamp(), 1_700_000_011);
+        assert_eq!(trade.slot, Some(280_000_011));
+        assert_eq!(records[0].trade.side, EAggressorSide::Buy);
+    }
+
+    #[test]
+    fn test_decode_trace_wraps_around() {
+        let data = trace_account(1, 4, &[(0, 13), (1, 10), (2, 11), (3, 12)]);
+
+        let records = decode_trace(&data, CAPACITY, 1e9).unwrap();
+
+        assert_eq!(ids(&records), vec![10, 11, 12, 13]);
+    }
+
+    #[test]
+    fn test_decode_trace_rejects_invalid_count() {
+        let data = trace_account(0, 5, &[]);
+
+        let result = decode_trace(&data, CAPACITY, 1e9);
+
+        assert_eq!(result.unwrap_err(), AccountDecodeError::CorruptedLinks { index: 0 });
+    }
+
+    #[test]
+    fn test_new_trace_records() {
+        let data = trace_account(0, 4, &[(0, 10), (1, 11), (2, 12), (3, 13)]);
+        let records = decode_trace(&data, CAPACITY, 1e9).unwrap();
+
+        assert_eq!(ids(&new_trace_records(records.clone(), Some(11)).collect::<Vec<_>>()), vec![12, 13]);
+        assert_eq!(ids(&new_trace_records(records, None).collect::<Vec<_>>()), vec![10, 11, 12, 13]);
+    }
+}
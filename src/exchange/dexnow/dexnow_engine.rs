@@ -1,24 +1,65 @@
 #![allow(dead_code)]
 
+use std::env;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use chrono::{DateTime, Utc};
 use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_sdk::{
+    commitment_config::CommitmentLevel,
     pubkey::Pubkey,
     signature::Keypair,
 };
 use tokio::sync::mpsc::Sender;
 use tokio::sync::RwLock;
+use crate::exchange::dexnow::data_structures::client::Client;
 use crate::exchange::dexnow::data_structures::instrument::Instrument;
+use crate::exchange::dexnow::data_structures::full_book::{EBookDepth, FullBook};
 use crate::exchange::dexnow::data_structures::token::Token;
+use crate::exchange::dexnow::pda_cache::{PdaCache, DEFAULT_PDA_CACHE_PATH};
 use crate::exchange::exchange_update::ExchangeUpdate;
+use crate::exchange::instrument_rules::InstrumentRules;
 use crate::exchange::order_book::OrderBook;
+use crate::exchange::solana::commitment::commitment_from_env;
+use crate::exchange::solana::endpoint_stats::EndpointStats;
+use crate::exchange::solana::endpoint_urls::endpoint_urls_from_env;
+use crate::exchange::solana::rpc_pool::RpcPool;
+use crate::exchange::solana::slot_clock::SlotClock;
+use crate::exchange::solana::ws_endpoints::WsEndpoints;
+use crate::exchange::trade_tape::TradeTape;
 
 pub struct DEXnowEngine {
     pub(super) name: String,
     pub orderbook: Arc<RwLock<OrderBook>>,
+    /// Trading rules of the instrument the engine listens to
+    pub instrument_rules: Arc<RwLock<Option<InstrumentRules>>>,
     pub update_sender: Option<Sender<ExchangeUpdate>>,
+    /// Recent spot trades read from the instrument trace account
+    pub trade_tape: Arc<RwLock<TradeTape>>,
+    /// How deep the spot book is rebuilt
+    pub book_depth: EBookDepth,
+    /// Commitment of the subscriptions and account loads, from `DEXNOW_COMMITMENT`
+    pub commitment: CommitmentLevel,
+    /// Estimated production times of the slots, fed by the slot subscription
+    pub slot_clock: std::sync::Mutex<SlotClock>,
+    /// Newest rooted slot reported by the slot subscription, books of processed slots up to it are confirmed
+    pub root_slot: AtomicU64,
+    /// Futures books published under their own name, by derivative index, from `DEXNOW_FUTURES_BOOKS`
+    pub futures_books: Vec<(String, Arc<RwLock<OrderBook>>)>,
+    /// Latest full-depth spot book, with individual orders at `EBookDepth::Orders`
+    pub spot_book: RwLock<FullBook>,
+    /// Instrument the engine listens to, filled with the live data of its futures and options markets
+    pub live_instrument: RwLock<Option<Instrument>>,
+    /// Client of the configured wallet with its balances and open spot orders
+    pub client: RwLock<Option<Client>>,
     pub version: u8,
-    pub connection: RpcClient,
+    /// RPC endpoints from `SOLANA_RPC_URLS`, health checked before every load
+    pub rpc: RpcPool,
+    /// PubSub endpoints from `SOLANA_WS_URLS`
+    pub ws_endpoints: WsEndpoints,
+    /// Subscribe on every PubSub endpoint at once and apply the first notification of each slot, from `DEXNOW_RACE_MODE`
+    pub race_mode: bool,
     pub program_id: Pubkey,
     pub dexnow_authority: Pubkey,
     pub root_account: Pubkey,
@@ -31,17 +72,22 @@ pub struct DEXnowEngine {
     pub client_drv_account: Option<Pubkey>,
     pub client_dexnow_account: Option<Pubkey>,
     pub client_lut_account: Option<Pubkey>,
+    /// Token and instrument IDs resolved from program addresses, stored at `pda_cache_path`
+    pub pda_cache: std::sync::Mutex<PdaCache>,
+    pub pda_cache_path: PathBuf,
     pub tokens: std::collections::HashMap<u64, Token>,
     pub instruments: std::collections::HashMap<u64, Instrument>,
 }
 
 impl DEXnowEngine {
     pub fn new(
-        connection: RpcClient,
+        rpc: RpcPool,
         root_account: Pubkey,
         name: String,
         program_id: Pubkey,
         orderbook: Arc<RwLock<OrderBook>>,
+        instrument_rules: Arc<RwLock<Option<InstrumentRules>>>,
+        trade_tape: Arc<RwLock<TradeTape>>,
     ) -> Self {
         let dexnow_authority = Pubkey::find_program_address(&[b"ndxnt"], &program_id).0;
 
@@ -49,8 +95,20 @@ impl DEXnowEngine {
             name,
             update_sender: None,
             orderbook,
+            instrument_rules,
+            trade_tape,
+            book_depth: EBookDepth::from_env(),
+            commitment: commitment_from_env("DEXnow", "DEXNOW_COMMITMENT"),
+            slot_clock: std::sync::Mutex::new(SlotClock::default()),
+            root_slot: AtomicU64::new(0),
+            futures_books: Vec::new(),
+            spot_book: RwLock::new(FullBook::default()),
+            live_instrument: RwLock::new(None),
+            client: RwLock::new(None),
             version: 1,
-            connection,
+            rpc,
+            ws_endpoints: WsEndpoints::new(endpoint_urls_from_env("SOLANA_WS_URLS", "SOLANA_WS_URL")),
+            race_mode: matches!(env::var("DEXNOW_RACE_MODE").ok().as_deref(), Some("true" | "1")),
             program_id,
             dexnow_authority,
             root_account,
@@ -63,10 +121,29 @@ impl DEXnowEngine {
             client_drv_account: None,
             client_dexnow_account: None,
             client_lut_account: None,
+            pda_cache: std::sync::Mutex::new(PdaCache::default()),
+            pda_cache_path: PathBuf::from(env::var("DEXNOW_PDA_CACHE_PATH").unwrap_or(DEFAULT_PDA_CACHE_PATH.to_string())),
             tokens: std::collections::HashMap::new(),
             instruments: std::collections::HashMap::new(),
         }
     }
+
+    /// Client of the active RPC endpoint
+    pub fn connection(&self) -> &RpcClient {
+        self.rpc.client()
+    }
+
+    /// Health and latency of the RPC endpoints and of the PubSub endpoints
+    pub fn endpoint_stats(&self) -> (Vec<EndpointStats>, Vec<EndpointStats>) {
+        (self.rpc.stats(), self.ws_endpoints.stats())
+    }
+
+    /// Estimated production time of the slot, the current time if the slots are not known yet
+    /// The estimate is capped at the current time, account notifications may be ahead of the slot notifications
+    pub fn slot_time(&self, slot: u64) -> DateTime<Utc> {
+        let now = Utc::now();
+        self.slot_clock.lock().unwrap().slot_time(slot).map_or(now, |time| time.min(now))
+    }
 }
 
 
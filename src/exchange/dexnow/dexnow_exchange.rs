@@ -1,36 +1,86 @@
 use std::env;
+use std::path::Path;
 use std::str::FromStr;
+use chrono::TimeDelta;
 use super::dexnow_engine::DEXnowEngine;
+use super::dexnow_futures_exchange::DexnowFuturesExchange;
+use super::data_structures::candle::ECandleInterval;
+use super::list_instruments::EListFormat;
 use crate::exchange::exchange::Exchange;
 use crate::exchange::order_book::OrderBook;
 use async_trait::async_trait;
 use std::sync::Arc;
-use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_sdk::pubkey::Pubkey;
-use tokio::sync::{Mutex, RwLock};
+use tokio::sync::RwLock;
 use tokio::sync::mpsc::Sender;
 use crate::exchange::exchange_update::ExchangeUpdate;
+use crate::exchange::instrument_rules::InstrumentRules;
+use crate::exchange::market_stats::MarketStats;
+use crate::exchange::solana::endpoint_urls::endpoint_urls_from_env;
+use crate::exchange::solana::rpc_pool::RpcPool;
+use crate::exchange::trade_tape::TradeTape;
 use crate::trading_pair::ETradingPair;
 
+/// How long the trades of the trace account are kept in the trade tape
+const TRADE_TAPE_MAX_AGE: TimeDelta = TimeDelta::minutes(5);
+
 pub struct DexnowExchange {
     name: String,
-    engine: Arc<Mutex<DEXnowEngine>>,
+    engine: Arc<RwLock<DEXnowEngine>>,
     orderbook: Arc<RwLock<OrderBook>>,
+    instrument_rules: Arc<RwLock<Option<InstrumentRules>>>,
+    trade_tape: Arc<RwLock<TradeTape>>,
+    futures_exchanges: Vec<Arc<DexnowFuturesExchange>>,
+}
+
+impl DexnowExchange {
+    /// Get a reference to the engine locker object to query the live futures and options data
+    /// The engine is only write-locked while it initializes
+    #[allow(dead_code)]
+    pub fn get_engine(&self) -> Arc<RwLock<DEXnowEngine>> {
+        self.engine.clone()
+    }
+
+    /// Futures books of the first `DEXNOW_FUTURES_BOOKS` derivatives, to be added to the manager beside the spot book
+    pub fn futures_exchanges(&self) -> Vec<Arc<DexnowFuturesExchange>> {
+        self.futures_exchanges.clone()
+    }
+
+    /// Load every token and instrument and render them for the `list-instruments` command
+    pub async fn list_instruments(&self, format: EListFormat) -> Result<String, Box<dyn std::error::Error>> {
+        let mut engine = self.engine.write().await;
+        engine.load_markets().await?;
+        Ok(engine.markets_listing().render(format)?)
+    }
 }
 
 #[async_trait]
 impl Exchange for DexnowExchange {
     fn new() -> Self {
-        let rpc_client = RpcClient::new(env::var("SOLANA_RPC_URL").unwrap());
-        let root_account = Pubkey::from_str(&*env::var("ROOT_ACCOUNT_PK").unwrap()).unwrap();
-        let program_id = Pubkey::from_str(&*env::var("PROGRAM_ID_PK").unwrap()).unwrap();
+        let rpc_urls = endpoint_urls_from_env("SOLANA_RPC_URLS", "SOLANA_RPC_URL");
+        assert!(!rpc_urls.is_empty(), "SOLANA_RPC_URLS or SOLANA_RPC_URL must be set");
+        let root_account = Pubkey::from_str(&env::var("ROOT_ACCOUNT_PK").unwrap()).unwrap();
+        let program_id = Pubkey::from_str(&env::var("PROGRAM_ID_PK").unwrap()).unwrap();
 
         let orderbook = Arc::new(RwLock::new(OrderBook::new()));
+        let instrument_rules = Arc::new(RwLock::new(None));
+        let trade_tape = Arc::new(RwLock::new(TradeTape::new(TRADE_TAPE_MAX_AGE)));
         let name = "DEXnow".to_string();
+        let futures_books = env::var("DEXNOW_FUTURES_BOOKS").ok().and_then(|count| count.parse().ok()).unwrap_or(0);
+        let futures_exchanges: Vec<Arc<DexnowFuturesExchange>> = (0..futures_books)
+            .map(|derivative_index| Arc::new(DexnowFuturesExchange::for_derivative(derivative_index)))
+            .collect();
+        let mut engine = DEXnowEngine::new(RpcPool::new(rpc_urls), root_account, name.clone(), program_id, orderbook.clone(), instrument_rules.clone(), trade_tape.clone());
+        engine.futures_books = futures_exchanges.iter()
+            .map(|exchange| (exchange.name(), exchange.get_order_book()))
+            .collect();
         DexnowExchange {
-            name: name.clone(),
-            engine: Arc::new(Mutex::new(DEXnowEngine::new(rpc_client, root_account, name, program_id, orderbook.clone()))),
+            name,
+            engine: Arc::new(RwLock::new(engine)),
             orderbook,
+            instrument_rules,
+            trade_tape,
+            futures_exchanges,
         }
     }
 
@@ -39,11 +89,43 @@ impl Exchange for DexnowExchange {
     }
 
     async fn start(&self, trading_pair: ETradingPair, update_sender: Sender<ExchangeUpdate>) {
-        let mut engine = self.engine.lock().await;
-        engine.initialize(trading_pair, update_sender).await.expect("[ERROR][DEXnow] Failed to initialize engine, please, reboot the application");
+        let mut engine = self.engine.write().await;
+        let instr_id = match engine.initialize(trading_pair, update_sender).await {
+            Ok(instr_id) => instr_id,
+            Err(e) => {
+                eprintln!("[ERROR][DEXnow] DEXnow engine has stopped: {}", e);
+                return;
+            }
+        };
+        // Listen with a read lock, so the live market data can be queried meanwhile
+        let engine = engine.downgrade();
+        if let Ok(dir) = env::var("DEXNOW_CANDLES_CSV_DIR") {
+            for interval in [ECandleInterval::M1, ECandleInterval::M15, ECandleInterval::Day] {
+                if let Err(e) = engine.export_candles_csv(instr_id, interval, Path::new(&dir)).await {
+                    eprintln!("[ERROR][DEXnow] Failed to export {} candles: {}", interval, e);
+                }
+            }
+        }
+        if let Err(e) = engine.listen(instr_id).await {
+            eprintln!("[ERROR][DEXnow] DEXnow engine has stopped: {}", e);
+        }
     }
 
     fn get_order_book(&self) -> Arc<RwLock<OrderBook>> {
         self.orderbook.clone()
     }
+
+    fn get_trade_tape(&self) -> Option<Arc<RwLock<TradeTape>>> {
+        Some(self.trade_tape.clone())
+    }
+
+    async fn get_instrument_rules(&self) -> Option<InstrumentRules> {
+        self.instrument_rules.read().await.clone()
+    }
+
+    async fn get_market_stats(&self) -> Option<MarketStats> {
+        // The engine is write-locked while it initializes, there are no statistics yet
+        let engine = self.engine.try_read().ok()?;
+        Some(engine.spot().await?.market_stats())
+    }
 }
\ No newline at end of file
@@ -0,0 +1,43 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use tokio::sync::RwLock;
+use tokio::sync::mpsc::Sender;
+use crate::exchange::exchange::Exchange;
+use crate::exchange::exchange_update::ExchangeUpdate;
+use crate::exchange::order_book::OrderBook;
+use crate::trading_pair::ETradingPair;
+
+/// Futures book of one derivative of the DEXnow instrument, published as its own book
+/// The DEXnow engine listens to the futures accounts and feeds the book, the exchange itself runs nothing
+pub struct DexnowFuturesExchange {
+    name: String,
+    orderbook: Arc<RwLock<OrderBook>>,
+}
+
+impl DexnowFuturesExchange {
+    /// Book of the futures market of the derivative with the given index
+    pub fn for_derivative(derivative_index: usize) -> Self {
+        DexnowFuturesExchange {
+            name: format!("DEXnow Futures {}", derivative_index),
+            orderbook: Arc::new(RwLock::new(OrderBook::new())),
+        }
+    }
+}
+
+#[async_trait]
+impl Exchange for DexnowFuturesExchange {
+    /// Book of the first derivative
+    fn new() -> Self {
+        Self::for_derivative(0)
+    }
+
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    async fn start(&self, _trading_pair: ETradingPair, _update_sender: Sender<ExchangeUpdate>) {}
+
+    fn get_order_book(&self) -> Arc<RwLock<OrderBook>> {
+        self.orderbook.clone()
+    }
+}
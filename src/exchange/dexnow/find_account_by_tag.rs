@@ -28,7 +28,7 @@ impl DEXnowEngine {
             sort_results: None,
         };
 
-        let accounts = self.connection.get_program_accounts_with_config(&self.program_id, config).await?;
+        let accounts = self.connection().get_program_accounts_with_config(&self.program_id, config).await?;
         Ok(accounts)
     }
 }
\ No newline at end of file
@@ -0,0 +1,59 @@
+use solana_account_decoder::{UiAccountEncoding, UiDataSliceConfig};
+use solana_client::rpc_config::RpcAccountInfoConfig;
+use std::time::Duration;
+use solana_sdk::account::Account;
+use solana_sdk::pubkey::Pubkey;
+use crate::exchange::dexnow::data_structures::constants::MAX_MULTIPLE_ACCOUNTS;
+use crate::exchange::dexnow::dexnow_engine::DEXnowEngine;
+
+/// Attempts of each getMultipleAccounts batch before the lookup fails
+const BATCH_ATTEMPTS: usize = 3;
+/// Pause between the attempts of a batch
+const BATCH_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+impl DEXnowEngine {
+    /// Read the u32 ID at `id_offset` of the first existing program address of the seed, bumps from 255 down
+    /// The candidate addresses are derived locally and fetched in getMultipleAccounts batches, a failed batch
+    /// is retried rather than skipped, a skipped batch could hide the address of the ID
+    pub(super) async fn find_pda_id(&self, seed: &[u8], id_offset: usize) -> Result<Option<u32>, Box<dyn std::error::Error>> {
+        let authority = self.dexnow_authority.to_bytes();
+        let candidates: Vec<Pubkey> = (0..=255u8).rev()
+            .filter_map(|bump| Pubkey::create_program_address(&[seed, &authority, &[bump]], &self.program_id).ok())
+            .collect();
+        let config = RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            data_slice: Some(UiDataSliceConfig {
+                offset: id_offset,
+                length: 4,
+            }),
+            commitment: None,
+            min_context_slot: None,
+        };
+
+        for candidates in candidates.chunks(MAX_MULTIPLE_ACCOUNTS) {
+            let accounts = self.fetch_batch(candidates, &config).await?;
+            let id = accounts.into_iter()
+                .flatten()
+                .find_map(|account| Some(u32::from_le_bytes(account.data.get(..4)?.try_into().ok()?)));
+            if id.is_some() {
+                return Ok(id);
+            }
+        }
+        Ok(None)
+    }
+
+    async fn fetch_batch(&self, candidates: &[Pubkey], config: &RpcAccountInfoConfig) -> Result<Vec<Option<Account>>, Box<dyn std::error::Error>> {
+        let mut attempt = 1;
+        loop {
+            match self.connection().get_multiple_accounts_with_config(candidates, config.clone()).await {
+                Ok(response) => return Ok(response.value),
+                Err(e) if attempt < BATCH_ATTEMPTS => {
+                    println!("[WARNING][DEXnow] Failed to fetch program addresses (attempt {}/{}): {}", attempt, BATCH_ATTEMPTS, e);
+                    tokio::time::sleep(BATCH_RETRY_DELAY).await;
+                    attempt += 1;
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+}
@@ -1,7 +1,6 @@
-use solana_account_decoder::UiDataSliceConfig;
-use solana_sdk::{pubkey::Pubkey};
 use crate::exchange::dexnow::data_structures::constants::*;
 use crate::exchange::dexnow::dexnow_engine::DEXnowEngine;
+use crate::exchange::dexnow::pda_cache::PdaCache;
 
 pub struct GetInstrIdArgs {
     pub asset_token_id: u32,
@@ -10,6 +9,7 @@ pub struct GetInstrIdArgs {
 
 impl DEXnowEngine {
     /// Get instrument ID from asset and base currency token IDs
+    /// Looked up in the PDA cache and the loaded instruments first, the instrument account is only fetched if both miss
     ///
     /// # Arguments
     ///
@@ -20,44 +20,27 @@ impl DEXnowEngine {
     ///
     /// Returns Token ID if this token registered on DEXnow.io
     pub async fn get_instr_id(&self, args: GetInstrIdArgs) -> Result<Option<u32>, Box<dyn std::error::Error>> {
-        let mut buf = [0u8; 16];
-        buf[0..4].copy_from_slice(&(self.version as u32).to_le_bytes());
-        buf[4..8].copy_from_slice(&INSTR_STATIC_TAG.to_le_bytes());
-        buf[8..12].copy_from_slice(&args.asset_token_id.to_le_bytes());
-        buf[12..16].copy_from_slice(&args.base_crncy_token_id.to_le_bytes());
-
-        for i in (0..=255u8).rev() {
-            let seeds = &[
-                &buf[..],
-                &self.dexnow_authority.to_bytes(),
-                &[i],
-            ];
-
-            match Pubkey::create_program_address(seeds, &self.program_id) {
-                Ok(pk) => {
-                    let config = solana_client::rpc_config::RpcAccountInfoConfig {
-                        encoding: None,
-                        data_slice: Some(UiDataSliceConfig {
-                            offset: INSTR_STATIC_ACCOUNT_ID_OFFSET,
-                            length: 4,
-                        }),
-                        commitment: None,
-                        min_context_slot: None,
-                    };
-
-                    let account_info = self.connection.get_account_with_config(&pk, config).await?;
-                    if let Some(info) = account_info.value {
-                        let data = &info.data;
-                            if data.len() >= 4 {
-                                return Ok(Some(u32::from_le_bytes([data[0], data[1], data[2], data[3]])));
-                            }
-
-                    }
-                },
-                Err(_) => continue,
+        let key = PdaCache::instrument_key(args.asset_token_id, args.base_crncy_token_id);
+        if let Some(id) = self.pda_cache.lock().unwrap().instruments.get(&key) {
+            return Ok(Some(*id));
+        }
+        let loaded = self.instruments.values().find(|instrument| {
+            instrument.asset_token_id == args.asset_token_id as u64 && instrument.base_crncy_token_id == args.base_crncy_token_id as u64
+        });
+        let id = match loaded {
+            Some(instrument) => Some(instrument.id as u32),
+            None => {
+                let mut buf = [0u8; 16];
+                buf[0..4].copy_from_slice(&(self.version as u32).to_le_bytes());
+                buf[4..8].copy_from_slice(&INSTR_STATIC_TAG.to_le_bytes());
+                buf[8..12].copy_from_slice(&args.asset_token_id.to_le_bytes());
+                buf[12..16].copy_from_slice(&args.base_crncy_token_id.to_le_bytes());
+                self.find_pda_id(&buf, INSTR_STATIC_ACCOUNT_ID_OFFSET).await?
             }
+        };
+        if let Some(id) = id {
+            self.remember_instr_id(args.asset_token_id, args.base_crncy_token_id, id);
         }
-
-        Ok(None)
+        Ok(id)
     }
-}
\ No newline at end of file
+}
@@ -1,11 +1,10 @@
-use solana_account_decoder::UiDataSliceConfig;
-use solana_client::rpc_config::RpcAccountInfoConfig;
 use solana_sdk::pubkey::Pubkey;
 use crate::exchange::dexnow::data_structures::constants::TOKEN_ACCOUNT_ID_OFFSET;
 use crate::exchange::dexnow::dexnow_engine::DEXnowEngine;
 
 impl DEXnowEngine {
     /// Get Token ID from mint public key if this token registered on DEXnow.io
+    /// Looked up in the PDA cache and the loaded tokens first, the token account is only fetched if both miss
     ///
     /// # Arguments
     ///
@@ -15,44 +14,20 @@ impl DEXnowEngine {
     ///
     /// Token ID
     pub async fn get_token_id(&self, mint: &Pubkey) -> Result<Option<u32>, Box<dyn std::error::Error>> {
-        let mut buf = mint.to_bytes();
-        buf[28..32].copy_from_slice(&(self.version as u32).to_le_bytes());
-
-        for i in (0..=255u8).rev() {
-            let seeds = &[
-                &buf[..],
-                &self.dexnow_authority.to_bytes(),
-                &[i],
-            ];
-
-            let pk = match Pubkey::create_program_address(seeds, &self.program_id) {
-                Ok(pk) => {
-                    pk
-                }
-                Err(_) => continue,
-            };
-
-            let config = RpcAccountInfoConfig {
-                encoding: None,
-                data_slice: Some(UiDataSliceConfig {
-                    offset: TOKEN_ACCOUNT_ID_OFFSET,
-                    length: 4,
-                }),
-                commitment: None,
-                min_context_slot: None,
-            };
-
-            match self.connection.get_account_with_config(&pk, config).await {
-                Ok(opt_account) => {
-                    if let Some(account) = opt_account.value {
-                        let id = u32::from_le_bytes(account.data[..4].try_into()?);
-                        return Ok(Some(id));
-                    }
-                }
-                Err(_) => continue,
+        if let Some(id) = self.pda_cache.lock().unwrap().tokens.get(&mint.to_string()) {
+            return Ok(Some(*id));
+        }
+        let id = match self.tokens.values().find(|token| token.mint == *mint) {
+            Some(token) => Some(token.id as u32),
+            None => {
+                let mut buf = mint.to_bytes();
+                buf[28..32].copy_from_slice(&(self.version as u32).to_le_bytes());
+                self.find_pda_id(&buf, TOKEN_ACCOUNT_ID_OFFSET).await?
             }
+        };
+        if let Some(id) = id {
+            self.remember_token_id(mint, id);
         }
-
-        Ok(None)
+        Ok(id)
     }
-}
\ No newline at end of file
+}
@@ -1,51 +1,64 @@
 use std::collections::HashMap;
+use std::env;
 use std::str::FromStr;
 use solana_sdk::pubkey::Pubkey;
-use solana_sdk::{system_program};
 use tokio::sync::mpsc::Sender;
 use crate::exchange::dexnow::data_structures::constants::*;
 use crate::exchange::dexnow::data_structures::derivative::Derivative;
 use crate::exchange::dexnow::data_structures::futures::Futures;
 use crate::exchange::dexnow::data_structures::instrument::Instrument;
+use crate::exchange::dexnow::data_structures::layouts::{cast, cast_slice, pubkey, InstrStaticAccountLayout, RootAccountLayout, TaskStaticLayout, TokenAccountLayout};
 use crate::exchange::dexnow::data_structures::spot::Spot;
 use crate::exchange::dexnow::data_structures::token::Token;
+use crate::exchange::dexnow::decode_error::DecodeError;
 use crate::exchange::dexnow::dexnow_engine::DEXnowEngine;
-use crate::exchange::dexnow::get_instrument_id::GetInstrIdArgs;
-use crate::exchange::dexnow::utils::read_basic_types::{read_pubkey, read_u32};
+use crate::exchange::dexnow::resolve_pair::SOL_TOKEN_ID;
 use crate::exchange::exchange_update::ExchangeUpdate;
 use crate::trading_pair::ETradingPair;
 
-const SOL_TOKEN_ID: u32 = 0;
-
 impl DEXnowEngine {
+    /// Load the tokens and instruments and return the ID of the instrument of the trading pair
     pub async fn initialize(
         &mut self,
         trading_pair: ETradingPair,
         update_sender: Sender<ExchangeUpdate>,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Result<u64, Box<dyn std::error::Error>> {
         self.update_sender = Some(update_sender);
-        let token_pubkey = Pubkey::from_str(match trading_pair {
-            ETradingPair::BtcUsdc => return Err("DEXnow don't support BTC now".into()),
-            ETradingPair::SolUsdc => "A2Pz6rVyXuadFkKnhMXd1w9xgSrZd8m8sEGpuGuyFhaj",
-        })?;
-
-        let root_info = self.connection.get_account(&self.root_account).await?;
-        if root_info.data.len() < ROOT_ACCOUNT_SIZE {
-            return Err("Invalid Root Account".into());
+        self.load_markets().await?;
+
+        if let Ok(wallet) = env::var("DEXNOW_WALLET_PK") {
+            if let Err(e) = self.load_client(&Pubkey::from_str(&wallet)?).await {
+                eprintln!("[WARNING][DEXnow] Client balances and open orders are not tracked: {}", e);
+            }
         }
 
-        self.version = read_u32(&root_info.data, ROOT_ACCOUNT_VERSION_OFFSET) as u8;
-        self.distrib_account = read_pubkey(&root_info.data, ROOT_ACCOUNT_DISTRIB_ADDRESS_OFFSET)?;
-        self.community_account = read_pubkey(&root_info.data, ROOT_ACCOUNT_COMMUNITY_ADDRESS_OFFSET)?;
-        let sol_program_address = read_pubkey(&root_info.data, ROOT_ACCOUNT_SOL_PROGRAM_ADDRESS_OFFSET)?;
+        let instr_id = self.resolve_pair(&trading_pair).await?;
+        let target_instrument = self.instruments.get(&instr_id).ok_or("Instrument of the trading pair not loaded")?;
+        println!("Target instrument: {:?}", target_instrument.dynamic_account);
+        *self.instrument_rules.write().await = Some(self.instrument_rules(target_instrument)?);
+        Ok(instr_id)
+    }
+
+    /// Load the root account, every registered token and every instrument
+    pub async fn load_markets(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        self.rpc.health_check().await?;
+        let root_info = self.connection().get_account(&self.root_account).await?;
+        let root = cast::<RootAccountLayout>(&root_info.data).map_err(|e| format!("Invalid Root Account: {}", e))?;
+
+        self.version = root.version as u8;
+        self.load_pda_cache();
+        self.distrib_account = pubkey(root.distrib_address);
+        self.community_account = pubkey(root.community_address);
+        let sol_program_address = pubkey(root.sol_program_address);
 
         let token_accounts = self.find_accounts_by_tag(TOKEN_TAG).await?;
 
         self.tokens = HashMap::new();
-        self.tokens.insert(0, Token {
-            id: 0,
-            account: system_program::id(),
-            mint: system_program::id(),
+        self.tokens.insert(SOL_TOKEN_ID as u64, Token {
+            id: SOL_TOKEN_ID as u64,
+            // System program
+            account: Pubkey::default(),
+            mint: Pubkey::default(),
             program_address: sol_program_address,
             decimals: 9,
             base_crncy: false,
@@ -57,99 +70,105 @@ impl DEXnowEngine {
         self.instruments = HashMap::new();
 
         for (pubkey, account) in token_accounts {
-            let id = read_u32(&account.data, TOKEN_ACCOUNT_ID_OFFSET) as u64;
-            let mask = read_u32(&account.data, TOKEN_ACCOUNT_MASK_OFFSET);
-            let token_2022 = (mask & U32_BIT_31) != 0;
-            let main_instr_id = read_u32(&account.data, TOKEN_ACCOUNT_BASE_INSTR_ID_OFFSET);
-
-            self.tokens.insert(id, Token {
-                id,
-                account: pubkey,
-                mint: read_pubkey(&account.data, TOKEN_ACCOUNT_ADDRESS_OFFSET)?,
-                program_address: read_pubkey(&account.data, TOKEN_ACCOUNT_PROGRAM_ADDRESS_OFFSET)?,
-                decimals: (mask & 0xF) as u8,
-                base_crncy: (mask & U32_BIT_30) != 0,
-                pool: main_instr_id != NULL_INSTR,
-                token_2022,
-                main_instr_id: if main_instr_id != NULL_INSTR { Some(main_instr_id as u64) } else { None },
-            });
+            match decode_token_account(pubkey, &account.data) {
+                Ok(token) => {
+                    self.tokens.insert(token.id, token);
+                }
+                Err(e) => eprintln!("[WARNING][DEXnow] Skipped token account {}: {}", pubkey, e),
+            }
         }
 
         let instr_accounts = self.find_accounts_by_tag(INSTR_STATIC_TAG).await?;
 
         for (pubkey, account) in instr_accounts {
-            let id = read_u32(&account.data, INSTR_STATIC_ACCOUNT_ID_OFFSET) as u64;
-            let mask = read_u32(&account.data, INSTR_STATIC_ACCOUNT_MASK_OFFSET);
-            let pool = (mask & U32_BIT_31) != 0;
-            let derivatives_count = read_u32(&account.data, INSTR_STATIC_ACCOUNT_TASKS_COUNT_OFFSET) as u64;
-
-            let mut derivatives = Vec::new();
-            for j in 0..derivatives_count {
-                let offset = INSTR_STATIC_ACCOUNT_SIZE + j as usize * TASK_STATIC_SIZE;
-                derivatives.push(Derivative {
-                    futures: Futures {
-                        bids_tree_account: read_pubkey(&account.data, offset + TASK_STATIC_BIDS_TREE_ADDRESS_OFFSET)?,
-                        asks_tree_account: read_pubkey(&account.data, offset + TASK_STATIC_ASKS_TREE_ADDRESS_OFFSET)?,
-                        bid_orders_account: read_pubkey(&account.data, offset + TASK_STATIC_BID_ORDERS_ADDRESS_OFFSET)?,
-                        ask_orders_account: read_pubkey(&account.data, offset + TASK_STATIC_ASK_ORDERS_ADDRESS_OFFSET)?,
-                        lines_account: read_pubkey(&account.data, offset + TASK_STATIC_LINES_ADDRESS_OFFSET)?,
-                        maps_account: read_pubkey(&account.data, offset + TASK_STATIC_MAPS_ADDRESS_OFFSET)?,
-                        client_infos_account: read_pubkey(&account.data, offset + TASK_STATIC_CLIENT_INFOS_ADDRESS_OFFSET)?,
-                        client_infos2_account: read_pubkey(&account.data, offset + TASK_STATIC_CLIENT_INFOS2_ADDRESS_OFFSET)?,
-                        client_accounts_account: read_pubkey(&account.data, offset + TASK_STATIC_CLIENT_ACCOUNTS_ADDRESS_OFFSET)?,
-                        ..Default::default()
-                    },
-                    ..Default::default()
-                });
-            }
-
-            self.instruments.insert(id, Instrument {
-                id,
-                static_account: pubkey,
-                dynamic_account: read_pubkey(&account.data, INSTR_STATIC_ACCOUNT_DYNAMIC_ADDRESS_OFFSET)?,
-                trace_account: Some(read_pubkey(&account.data, INSTR_STATIC_ACCOUNT_TRACE_ADDRESS_OFFSET)?),
-                lut_account: Some(read_pubkey(&account.data, INSTR_STATIC_ACCOUNT_LUT_ADDRESS_OFFSET)?),
-                asset_token_id: read_u32(&account.data, INSTR_STATIC_ACCOUNT_TOKEN_ID_OFFSET) as u64,
-                derivatives_count,
-                base_crncy_token_id: read_u32(&account.data, INSTR_STATIC_ACCOUNT_MINT_ID_OFFSET) as u64,
-                pool,
-                pool_token_id: if !pool { Some(read_u32(&account.data, INSTR_STATIC_ACCOUNT_POOL_TOKEN_ID_OFFSET) as u64) } else { None },
-                spot: Spot {
-                    bids_tree_account: read_pubkey(&account.data, INSTR_STATIC_ACCOUNT_BIDS_TREE_ADDRESS_OFFSET)?,
-                    asks_tree_account: read_pubkey(&account.data, INSTR_STATIC_ACCOUNT_ASKS_TREE_ADDRESS_OFFSET)?,
-                    bid_orders_account: read_pubkey(&account.data, INSTR_STATIC_ACCOUNT_BID_ORDERS_ADDRESS_OFFSET)?,
-                    ask_orders_account: read_pubkey(&account.data, INSTR_STATIC_ACCOUNT_ASK_ORDERS_ADDRESS_OFFSET)?,
-                    lines_account: read_pubkey(&account.data, INSTR_STATIC_ACCOUNT_LINES_ADDRESS_OFFSET)?,
-                    maps_account: read_pubkey(&account.data, INSTR_STATIC_ACCOUNT_MAPS_ADDRESS_OFFSET)?,
-                    client_infos_account: read_pubkey(&account.data, INSTR_STATIC_ACCOUNT_CLIENT_INFOS_ADDRESS_OFFSET)?,
-                    client_infos2_account: read_pubkey(&account.data, INSTR_STATIC_ACCOUNT_CLIENT_INFOS2_ADDRESS_OFFSET)?,
-                    client_accounts_account: read_pubkey(&account.data, INSTR_STATIC_ACCOUNT_CLIENT_ACCOUNTS_ADDRESS_OFFSET)?,
-                    m1_candles_account: read_pubkey(&account.data, INSTR_STATIC_ACCOUNT_M1_CANDLES_ADDRESS_OFFSET)?,
-                    m15_candles_account: read_pubkey(&account.data, INSTR_STATIC_ACCOUNT_M15_CANDLES_ADDRESS_OFFSET)?,
-                    day_candles_account: read_pubkey(&account.data, INSTR_STATIC_ACCOUNT_DAY_CANDLES_ADDRESS_OFFSET)?,
-                    ..Default::default()
-                },
-                derivatives,
-                ..Default::default()
-            });
-        }
-
-        let usdc_token_id = self.get_token_id(&token_pubkey).await.unwrap();
-
-        let instr_id = self.get_instr_id(GetInstrIdArgs {
-            base_crncy_token_id: usdc_token_id.unwrap(),
-            asset_token_id: SOL_TOKEN_ID,
-        }).await.unwrap();
-        // println!("USDC dyn acc: {:?}, instr id: {:?}", usdc_token_id, instr_id);
-
-        if let Some(instr_id) = instr_id {
-            let target_instrument =
-                self.instruments.values().find(|instr| instr.id == instr_id as u64);
-            if let Some(target_instrument) = target_instrument {
-                println!("Target instrument: {:?}", target_instrument.dynamic_account);
-                self.connect_and_listen(&target_instrument.dynamic_account).await?;
+            match decode_instr_static_account(pubkey, &account.data) {
+                Ok(instrument) => {
+                    self.instruments.insert(instrument.id, instrument);
+                }
+                Err(e) => eprintln!("[WARNING][DEXnow] Skipped instrument account {}: {}", pubkey, e),
             }
         }
         Ok(())
     }
-}
\ No newline at end of file
+
+    /// Listen to the instrument until the reconnect budget is exhausted
+    pub async fn listen(&self, instr_id: u64) -> Result<(), Box<dyn std::error::Error>> {
+        let instrument = self.instruments.get(&instr_id).ok_or("Instrument not loaded")?;
+        self.connect_and_listen(instrument).await
+    }
+}
+
+fn decode_token_account(account: Pubkey, data: &[u8]) -> Result<Token, DecodeError> {
+    let token = cast::<TokenAccountLayout>(data)?;
+    let mask = token.mask;
+    let main_instr_id = token.base_instr_id;
+
+    Ok(Token {
+        id: token.id as u64,
+        account,
+        mint: pubkey(token.address),
+        program_address: pubkey(token.program_address),
+        decimals: (mask & 0xF) as u8,
+        base_crncy: (mask & U32_BIT_30) != 0,
+        pool: main_instr_id != NULL_INSTR,
+        token_2022: (mask & U32_BIT_31) != 0,
+        main_instr_id: if main_instr_id != NULL_INSTR { Some(main_instr_id as u64) } else { None },
+    })
+}
+
+fn decode_instr_static_account(account: Pubkey, data: &[u8]) -> Result<Instrument, DecodeError> {
+    let instr = cast::<InstrStaticAccountLayout>(data)?;
+    let pool = (instr.mask & U32_BIT_31) != 0;
+    let derivatives_count = instr.tasks_count as u64;
+    let tasks = cast_slice::<TaskStaticLayout>(data, INSTR_STATIC_ACCOUNT_SIZE, derivatives_count as usize)?;
+
+    let derivatives = tasks
+        .iter()
+        .map(|task| Derivative {
+            futures: Futures {
+                bids_tree_account: pubkey(task.bids_tree_address),
+                asks_tree_account: pubkey(task.asks_tree_address),
+                bid_orders_account: pubkey(task.bid_orders_address),
+                ask_orders_account: pubkey(task.ask_orders_address),
+                lines_account: pubkey(task.lines_address),
+                maps_account: pubkey(task.maps_address),
+                client_infos_account: pubkey(task.client_infos_address),
+                client_infos2_account: pubkey(task.client_infos2_address),
+                client_accounts_account: pubkey(task.client_accounts_address),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .collect();
+
+    Ok(Instrument {
+        id: instr.id as u64,
+        static_account: account,
+        dynamic_account: pubkey(instr.dynamic_address),
+        trace_account: Some(pubkey(instr.trace_address)),
+        lut_account: Some(pubkey(instr.lut_address)),
+        asset_token_id: instr.token_id as u64,
+        derivatives_count,
+        base_crncy_token_id: instr.mint_id as u64,
+        pool,
+        pool_token_id: if !pool { Some(instr.pool_token_id as u64) } else { None },
+        dec_factor: instr.dec_factor,
+        spot: Spot {
+            bids_tree_account: pubkey(instr.bids_tree_address),
+            asks_tree_account: pubkey(instr.asks_tree_address),
+            bid_orders_account: pubkey(instr.bid_orders_address),
+            ask_orders_account: pubkey(instr.ask_orders_address),
+            lines_account: pubkey(instr.lines_address),
+            maps_account: pubkey(instr.maps_address),
+            client_infos_account: pubkey(instr.client_infos_address),
+            client_infos2_account: pubkey(instr.client_infos2_address),
+            client_accounts_account: pubkey(instr.client_accounts_address),
+            m1_candles_account: pubkey(instr.m1_candles_address),
+            m15_candles_account: pubkey(instr.m15_candles_address),
+            day_candles_account: pubkey(instr.day_candles_address),
+            ..Default::default()
+        },
+        derivatives,
+        ..Default::default()
+    })
+}
@@ -0,0 +1,37 @@
+use crate::exchange::dexnow::data_structures::constants::DEC;
+use crate::exchange::dexnow::data_structures::instrument::Instrument;
+use crate::exchange::dexnow::decode_error::DecodeError;
+use crate::exchange::dexnow::dexnow_engine::DEXnowEngine;
+use crate::exchange::instrument_rules::InstrumentRules;
+
+impl DEXnowEngine {
+    /// Build the trading rules of the instrument from its static account and the asset token decimals
+    ///
+    /// The tick is the `dec_factor` price step of the static account, quantities are stored in the smallest
+    /// units of the asset token, which define the lot size and the minimum quantity. DEXnow has no minimum notional
+    pub fn instrument_rules(&self, instrument: &Instrument) -> Result<InstrumentRules, DecodeError> {
+        let lot_size = 1.0 / self.token_dec(instrument.asset_token_id)?;
+        Ok(InstrumentRules {
+            tick_size: tick_size(instrument.dec_factor),
+            lot_size,
+            min_qty: lot_size,
+            min_notional: 0.0,
+        })
+    }
+}
+
+/// Tick of a `dec_factor` price step, a step of zero leaves the one unit prices are stored in
+fn tick_size(dec_factor: i64) -> f64 {
+    dec_factor.max(1) as f64 / DEC as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tick_size_from_dec_factor() {
+        assert_eq!(tick_size(10_000_000), 0.01);
+        assert_eq!(tick_size(0), 1e-9);
+    }
+}
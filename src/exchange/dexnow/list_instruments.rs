@@ -0,0 +1,169 @@
+use std::fmt;
+use serde::Serialize;
+use crate::exchange::dexnow::dexnow_engine::DEXnowEngine;
+
+/// Output formats of the `list-instruments` command
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EListFormat {
+    Table,
+    Json,
+}
+
+/// Registered DEXnow token as printed by `list-instruments`
+#[derive(Debug, Serialize)]
+pub struct TokenListing {
+    pub id: u64,
+    pub mint: String,
+    pub decimals: u8,
+    pub token_2022: bool,
+    pub base_crncy: bool,
+    /// True if options pool token
+    pub pool: bool,
+}
+
+/// DEXnow instrument as printed by `list-instruments`
+#[derive(Debug, Serialize)]
+pub struct InstrumentListing {
+    pub id: u64,
+    pub asset_token_id: u64,
+    pub asset_mint: Option<String>,
+    pub base_crncy_token_id: u64,
+    pub base_crncy_mint: Option<String>,
+    pub static_account: String,
+    pub dynamic_account: String,
+    pub derivatives_count: u64,
+    /// True if the instrument trades an options pool token
+    pub pool: bool,
+    /// Options pool token of the instrument derivatives
+    pub pool_token_id: Option<u64>,
+}
+
+/// Every loaded token and instrument, ordered by ID
+#[derive(Debug, Serialize)]
+pub struct MarketsListing {
+    pub tokens: Vec<TokenListing>,
+    pub instruments: Vec<InstrumentListing>,
+}
+
+impl DEXnowEngine {
+    /// List the tokens and instruments loaded by `load_markets`
+    pub fn markets_listing(&self) -> MarketsListing {
+        let mint = |token_id: u64| self.tokens.get(&token_id).map(|token| token.mint.to_string());
+        let mut tokens: Vec<TokenListing> = self.tokens.values()
+            .map(|token| TokenListing {
+                id: token.id,
+                mint: token.mint.to_string(),
+                decimals: token.decimals,
+                token_2022: token.token_2022,
+                base_crncy: token.base_crncy,
+                pool: token.pool,
+            })
+            .collect();
+        tokens.sort_by_key(|token| token.id);
+        let mut instruments: Vec<InstrumentListing> = self.instruments.values()
+            .map(|instrument| InstrumentListing {
+                id: instrument.id,
+                asset_token_id: instrument.asset_token_id,
+                asset_mint: mint(instrument.asset_token_id),
+                base_crncy_token_id: instrument.base_crncy_token_id,
+                base_crncy_mint: mint(instrument.base_crncy_token_id),
+                static_account: instrument.static_account.to_string(),
+                dynamic_account: instrument.dynamic_account.to_string(),
+                derivatives_count: instrument.derivatives_count,
+                pool: instrument.pool,
+                pool_token_id: instrument.pool_token_id,
+            })
+            .collect();
+        instruments.sort_by_key(|instrument| instrument.id);
+        MarketsListing { tokens, instruments }
+    }
+}
+
+impl MarketsListing {
+    pub fn render(&self, format: EListFormat) -> Result<String, serde_json::Error> {
+        match format {
+            EListFormat::Table => Ok(self.to_string()),
+            EListFormat::Json => serde_json::to_string_pretty(self),
+        }
+    }
+}
+
+fn flag(value: bool) -> &'static str {
+    if value { "yes" } else { "no" }
+}
+
+impl fmt::Display for MarketsListing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Tokens:")?;
+        writeln!(f, "{:>5}  {:<44}  {:>8}  {:<10}  {:<9}  {:<4}", "ID", "MINT", "DECIMALS", "TOKEN-2022", "BASE CRNCY", "POOL")?;
+        for token in &self.tokens {
+            writeln!(f, "{:>5}  {:<44}  {:>8}  {:<10}  {:<9}  {:<4}",
+                     token.id, token.mint, token.decimals, flag(token.token_2022), flag(token.base_crncy), flag(token.pool))?;
+        }
+        writeln!(f)?;
+        writeln!(f, "Instruments:")?;
+        writeln!(f, "{:>5}  {:<11}  {:<44}  {:<44}  {:>11}  {:<4}  {:<10}",
+                 "ID", "ASSET/BASE", "STATIC ACCOUNT", "DYNAMIC ACCOUNT", "DERIVATIVES", "POOL", "POOL TOKEN")?;
+        for instrument in &self.instruments {
+            writeln!(f, "{:>5}  {:<11}  {:<44}  {:<44}  {:>11}  {:<4}  {:<10}",
+                     instrument.id,
+                     format!("{}/{}", instrument.asset_token_id, instrument.base_crncy_token_id),
+                     instrument.static_account,
+                     instrument.dynamic_account,
+                     instrument.derivatives_count,
+                     flag(instrument.pool),
+                     instrument.pool_token_id.map(|id| id.to_string()).unwrap_or_else(|| "-".to_string()))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn listing() -> MarketsListing {
+        MarketsListing {
+            tokens: vec![TokenListing {
+                id: 1,
+                mint: "A2Pz6rVyXuadFkKnhMXd1w9xgSrZd8m8sEGpuGuyFhaj".to_string(),
+                decimals: 6,
+                token_2022: false,
+                base_crncy: true,
+                pool: false,
+            }],
+            instruments: vec![InstrumentListing {
+                id: 3,
+                asset_token_id: 0,
+                asset_mint: Some("11111111111111111111111111111111".to_string()),
+                base_crncy_token_id: 1,
+                base_crncy_mint: Some("A2Pz6rVyXuadFkKnhMXd1w9xgSrZd8m8sEGpuGuyFhaj".to_string()),
+                static_account: "StaticAccount".to_string(),
+                dynamic_account: "DynamicAccount".to_string(),
+                derivatives_count: 2,
+                pool: false,
+                pool_token_id: Some(5),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_render_table() {
+        let table = listing().render(EListFormat::Table).unwrap();
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert_eq!(lines[2], "    1  A2Pz6rVyXuadFkKnhMXd1w9xgSrZd8m8sEGpuGuyFhaj         6  no          yes        no  ");
+        assert!(lines[6].starts_with("    3  0/1          StaticAccount"));
+        assert!(lines[6].ends_with("          2  no    5         "));
+    }
+
+    #[test]
+    fn test_render_json() {
+        let json = listing().render(EListFormat::Json).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value["tokens"][0]["base_crncy"], true);
+        assert_eq!(value["instruments"][0]["derivatives_count"], 2);
+        assert_eq!(value["instruments"][0]["pool_token_id"], 5);
+    }
+}
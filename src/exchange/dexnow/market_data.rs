@@ -0,0 +1,118 @@
+#![allow(dead_code)]
+use crate::exchange::dexnow::data_structures::derivative::Derivative;
+use crate::exchange::dexnow::data_structures::futures::Futures;
+use crate::exchange::dexnow::data_structures::option_strike::OptionStrike;
+use crate::exchange::dexnow::data_structures::spot::Spot;
+use crate::exchange::dexnow::dexnow_engine::DEXnowEngine;
+
+/// Estimated price and supply of the options pool token of the instrument
+#[derive(Debug, Clone, PartialEq)]
+pub struct OptionsPool {
+    pub est_px: Option<f64>,
+    pub supply: Option<f64>,
+}
+
+impl DEXnowEngine {
+    /// Spot market of the listened instrument with its live statistics
+    pub async fn spot(&self) -> Option<Spot> {
+        self.live_instrument.read().await.as_ref().map(|instrument| instrument.spot.clone())
+    }
+
+    /// Derivatives of the listened instrument with their live futures and options data
+    pub async fn derivatives(&self) -> Vec<Derivative> {
+        self.live_instrument.read().await.as_ref()
+            .map(|instrument| instrument.derivatives.clone())
+            .unwrap_or_default()
+    }
+
+    /// Futures market of the derivative with the given index
+    /// Its book is sent to the manager only through the `DexnowFuturesExchange` of the derivative
+    pub async fn futures(&self, derivative_index: usize) -> Option<Futures> {
+        self.live_instrument.read().await.as_ref()?
+            .derivatives.get(derivative_index)
+            .map(|derivative| derivative.futures.clone())
+    }
+
+    /// Option strikes of the derivative with the given index, None until its maps account is decoded
+    pub async fn option_strikes(&self, derivative_index: usize) -> Option<Vec<OptionStrike>> {
+        self.live_instrument.read().await.as_ref()?
+            .derivatives.get(derivative_index)?
+            .options.clone()
+    }
+
+    /// Option strike closest to the given price over all derivatives, with the index of its derivative
+    pub async fn nearest_strike(&self, px: f64) -> Option<(usize, OptionStrike)> {
+        let live_instrument = self.live_instrument.read().await;
+        live_instrument.as_ref()?
+            .derivatives.iter()
+            .enumerate()
+            .flat_map(|(i, derivative)| derivative.options.iter().flatten().map(move |strike| (i, strike)))
+            .min_by(|(_, a), (_, b)| (a.price - px).abs().total_cmp(&(b.price - px).abs()))
+            .map(|(i, strike)| (i, strike.clone()))
+    }
+
+    /// Options pool token data, None if the instrument has no derivatives
+    pub async fn options_pool(&self) -> Option<OptionsPool> {
+        let live_instrument = self.live_instrument.read().await;
+        let instrument = live_instrument.as_ref().filter(|instrument| instrument.derivatives_count > 0)?;
+        Some(OptionsPool {
+            est_px: instrument.options_pool_est_px,
+            supply: instrument.options_pool_supply,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use crate::exchange::solana::rpc_pool::RpcPool;
+    use solana_sdk::pubkey::Pubkey;
+    use tokio::sync::RwLock;
+    use crate::exchange::dexnow::data_structures::instrument::Instrument;
+    use chrono::TimeDelta;
+    use crate::exchange::order_book::OrderBook;
+    use crate::exchange::trade_tape::TradeTape;
+    use super::*;
+
+    fn strike(id: u64, price: f64) -> OptionStrike {
+        OptionStrike { id, price, calls: 1.0, puts: 2.0, height: 0.0, call_px: None, put_px: None }
+    }
+
+    async fn engine() -> DEXnowEngine {
+        let engine = DEXnowEngine::new(
+            RpcPool::new(vec!["http://localhost:8899".to_string()]),
+            Pubkey::new_unique(),
+            "DEXnow".to_string(),
+            Pubkey::new_unique(),
+            Arc::new(RwLock::new(OrderBook::new())),
+            Arc::new(RwLock::new(None)),
+            Arc::new(RwLock::new(TradeTape::new(TimeDelta::minutes(5)))),
+        );
+        *engine.live_instrument.write().await = Some(Instrument {
+            derivatives_count: 2,
+            options_pool_est_px: Some(1.05),
+            derivatives: vec![
+                Derivative { options: Some(vec![strike(1, 140.0), strike(2, 160.0)]), ..Derivative::default() },
+                Derivative { options: Some(vec![strike(3, 155.0)]), ..Derivative::default() },
+            ],
+            ..Instrument::default()
+        });
+        engine
+    }
+
+    #[tokio::test]
+    async fn test_option_strikes() {
+        let engine = engine().await;
+
+        assert_eq!(engine.option_strikes(1).await, Some(vec![strike(3, 155.0)]));
+        assert_eq!(engine.option_strikes(2).await, None);
+        assert_eq!(engine.nearest_strike(158.0).await, Some((0, strike(2, 160.0))));
+    }
+
+    #[tokio::test]
+    async fn test_options_pool() {
+        let engine = engine().await;
+
+        assert_eq!(engine.options_pool().await, Some(OptionsPool { est_px: Some(1.05), supply: None }));
+    }
+}
@@ -1,13 +1,28 @@
 mod dexnow_engine;
 pub mod dexnow_exchange;
+pub mod dexnow_futures_exchange;
 mod data_structures;
 mod initialize_engine;
 mod get_token_id;
 mod get_instrument_id;
+mod find_pda_id;
+mod pda_cache;
 mod token_dec;
+mod decode_error;
 mod decode_instr_dynamic_account;
-mod utils;
+mod decode_full_book;
+mod decode_futures;
+mod decode_options;
+mod decode_spot_stats;
+mod decode_candles;
+mod decode_trace;
+mod decode_client;
+pub mod list_instruments;
+mod resolve_pair;
+mod candles_csv;
+mod market_data;
 mod find_account_by_tag;
 mod connect_and_listen;
-mod solana;
-pub(super) mod process_orderbook_update;
\ No newline at end of file
+pub(super) mod process_orderbook_update;
+mod instrument_rules;
+
@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use crate::exchange::dexnow::dexnow_engine::DEXnowEngine;
+
+/// Where the resolved IDs are stored when `DEXNOW_PDA_CACHE_PATH` is not set
+pub const DEFAULT_PDA_CACHE_PATH: &str = "dexnow_pda_cache.json";
+
+/// Token and instrument IDs resolved from program addresses, valid for one version of the root account
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PdaCache {
+    pub root_account: String,
+    pub version: u8,
+    /// Token IDs by mint
+    pub tokens: HashMap<String, u32>,
+    /// Instrument IDs by `<asset token ID>/<base currency token ID>`
+    pub instruments: HashMap<String, u32>,
+}
+
+impl PdaCache {
+    pub fn new(root_account: &Pubkey, version: u8) -> Self {
+        PdaCache {
+            root_account: root_account.to_string(),
+            version,
+            ..PdaCache::default()
+        }
+    }
+
+    /// Read the cache of the root account version, an empty one if the file is missing, unreadable or stale
+    pub fn load(path: &Path, root_account: &Pubkey, version: u8) -> Self {
+        let cache = fs::read_to_string(path).ok()
+            .and_then(|json| serde_json::from_str::<PdaCache>(&json).ok());
+        match cache {
+            Some(cache) if cache.root_account == root_account.to_string() && cache.version == version => cache,
+            _ => PdaCache::new(root_account, version),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn instrument_key(asset_token_id: u32, base_crncy_token_id: u32) -> String {
+        format!("{}/{}", asset_token_id, base_crncy_token_id)
+    }
+}
+
+impl DEXnowEngine {
+    /// Replace the cache with the one stored for the current root account version
+    pub(super) fn load_pda_cache(&mut self) {
+        let cache = PdaCache::load(&self.pda_cache_path, &self.root_account, self.version);
+        *self.pda_cache.get_mut().unwrap() = cache;
+    }
+
+    pub(super) fn remember_token_id(&self, mint: &Pubkey, token_id: u32) {
+        let mut cache = self.pda_cache.lock().unwrap();
+        if cache.tokens.insert(mint.to_string(), token_id) != Some(token_id) {
+            self.save_pda_cache(&cache);
+        }
+    }
+
+    pub(super) fn remember_instr_id(&self, asset_token_id: u32, base_crncy_token_id: u32, instr_id: u32) {
+        let mut cache = self.pda_cache.lock().unwrap();
+        let key = PdaCache::instrument_key(asset_token_id, base_crncy_token_id);
+        if cache.instruments.insert(key, instr_id) != Some(instr_id) {
+            self.save_pda_cache(&cache);
+        }
+    }
+
+    fn save_pda_cache(&self, cache: &PdaCache) {
+        if let Err(e) = cache.save(&self.pda_cache_path) {
+            eprintln!("[WARNING][DEXnow] Failed to save PDA cache to {}: {}", self.pda_cache_path.display(), e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("dexnow_pda_cache_{}_{}.json", name, std::process::id()))
+    }
+
+    #[test]
+    fn test_pda_cache_round_trip() {
+        let path = cache_path("round_trip");
+        let root_account = Pubkey::new_unique();
+        let mut cache = PdaCache::new(&root_account, 3);
+        cache.tokens.insert(Pubkey::new_unique().to_string(), 1);
+        cache.instruments.insert(PdaCache::instrument_key(0, 1), 4);
+        cache.save(&path).unwrap();
+
+        let loaded = PdaCache::load(&path, &root_account, 3);
+
+        fs::remove_file(&path).unwrap();
+        assert_eq!(loaded, cache);
+    }
+
+    #[test]
+    fn test_pda_cache_discards_other_version() {
+        let path = cache_path("other_version");
+        let root_account = Pubkey::new_unique();
+        let mut cache = PdaCache::new(&root_account, 3);
+        cache.instruments.insert(PdaCache::instrument_key(0, 1), 4);
+        cache.save(&path).unwrap();
+
+        let loaded = PdaCache::load(&path, &root_account, 4);
+
+        fs::remove_file(&path).unwrap();
+        assert_eq!(loaded, PdaCache::new(&root_account, 4));
+    }
+
+    #[test]
+    fn test_pda_cache_missing_file() {
+        let root_account = Pubkey::new_unique();
+
+        let loaded = PdaCache::load(&cache_path("missing"), &root_account, 1);
+
+        assert_eq!(loaded, PdaCache::new(&root_account, 1));
+    }
+}
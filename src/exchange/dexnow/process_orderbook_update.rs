@@ -1,8 +1,11 @@
+use std::sync::atomic::Ordering;
 use chrono::{Utc};
 use ordered_float::{OrderedFloat};
+use solana_sdk::commitment_config::CommitmentLevel;
 use thiserror::Error;
-use crate::exchange::dexnow::data_structures::instr_dynamic_account::InstrDynamicAccount;
+use crate::exchange::dexnow::data_structures::line_px::LinePx;
 use crate::exchange::dexnow::dexnow_engine::DEXnowEngine;
+use tokio::sync::RwLock;
 use crate::exchange::order_book::{OrderBook};
 use crate::exchange::send_orderbook_update::send_orderbook_update;
 
@@ -13,48 +16,257 @@ pub enum ProcessOrderbookError {
 }
 
 impl DEXnowEngine {
-    pub async fn process_orderbook_update(
+    /// Replace the spot order book with the given lines
+    pub async fn process_orderbook_update<'a>(
         &self,
-        instr_dynamic_account: &InstrDynamicAccount,
+        bids: impl IntoIterator<Item = &'a LinePx>,
+        asks: impl IntoIterator<Item = &'a LinePx>,
+        slot: u64,
+    ) -> Result<(), Box<dyn std::error::Error>>
+    {
+        self.replace_book(&self.name, &self.orderbook, bids, asks, slot).await
+    }
+
+    /// Replace the futures order book of the derivative with the given lines, if it is published
+    pub async fn process_futures_orderbook_update<'a>(
+        &self,
+        derivative_index: usize,
+        bids: impl IntoIterator<Item = &'a LinePx>,
+        asks: impl IntoIterator<Item = &'a LinePx>,
+        slot: u64,
+    ) -> Result<(), Box<dyn std::error::Error>>
+    {
+        let Some((name, orderbook)) = self.futures_books.get(derivative_index) else {
+            return Ok(());
+        };
+        self.replace_book(name, orderbook, bids, asks, slot).await
+    }
+
+    /// Replace the named order book with the given lines and send its best prices to the manager
+    /// With processed commitment a slot lower than the current one means the fork of the newer slots
+    /// has been abandoned: the lines are rebuilt from the accounts restored before the slot and replace
+    /// the book. Processed books are marked as unconfirmed until their slot is rooted
+    async fn replace_book<'a>(
+        &self,
+        name: &String,
+        orderbook: &RwLock<OrderBook>,
+        bids: impl IntoIterator<Item = &'a LinePx>,
+        asks: impl IntoIterator<Item = &'a LinePx>,
         slot: u64,
     ) -> Result<(), Box<dyn std::error::Error>>
     {
         let update_sender = self.update_sender.as_ref()
             .ok_or(ProcessOrderbookError::UpdateSenderNotInitialized)?;
-        // println!("[INFO][DEXnow] Received orderbook update: {:?}", instr_dynamic_account);
-        let mut orderbook = self.orderbook.write().await;
+        let processed = self.commitment == CommitmentLevel::Processed;
+        let mut orderbook = orderbook.write().await;
         // Zero slot means a new orderbook (got from HTTP request instead of WS)
         // Clear the current orderbook and start from the provided slot.
         if slot == 0 {
-            println!("[INFO][DEXnow] Created new orderbook");
+            println!("[INFO][{}] Created new orderbook", name);
             *orderbook = OrderBook::new();
+        } else if slot < orderbook.sequence && processed {
+            println!("[WARNING][{}] Fork abandoned: received slot {} after slot {}, replacing the book", name, slot, orderbook.sequence);
+            orderbook.sequence = slot;
         } else if slot < orderbook.sequence {
-            println!("[WARNING][DEXnow] Skipping outdated update: received slot {}, current slot {}", slot, orderbook.sequence);
+            println!("[WARNING][{}] Skipping outdated update: received slot {}, current slot {}", name, slot, orderbook.sequence);
             return Ok(());
         } else {
             orderbook.sequence = slot;
             // println!("[INFO][DEXnow] Applied orderbook {slot}");
         }
-        orderbook.last_updated = Utc::now();
+        // Freshness is measured from when the slot was produced, not from when the notification arrived
+        orderbook.last_updated = if slot == 0 { Utc::now() } else { self.slot_time(slot) };
+        orderbook.confirmed = !processed || (slot != 0 && slot <= self.root_slot.load(Ordering::SeqCst));
         orderbook.asks.clear();
         orderbook.bids.clear();
-        for line_px in instr_dynamic_account.spot_asks.iter() {
+        for line_px in asks {
             let price = OrderedFloat(line_px.px);
             orderbook.asks.insert(price, line_px.qty);
         }
-        for line_px in instr_dynamic_account.spot_bids.iter() {
+        for line_px in bids {
             let price = OrderedFloat(line_px.px);
             orderbook.bids.insert(price, line_px.qty);
         }
 
         // *** Notify subscribers about the updated orderbook ***
         send_orderbook_update(
-            &update_sender,
-            &self.name,
+            update_sender,
+            name,
             &orderbook.get_best_bid(),
             &orderbook.get_best_ask(),
+            orderbook.last_updated,
         ).await;
 
         Ok(())
     }
-}
\ No newline at end of file
+
+    /// Record the newest rooted slot and mark the books of the slots up to it as confirmed
+    /// Books loaded over HTTP have no slot and stay unconfirmed until they are replaced
+    pub async fn confirm_rooted_books(&self, root: u64) {
+        let root = self.root_slot.fetch_max(root, Ordering::SeqCst).max(root);
+        let futures_books = self.futures_books.iter().map(|(_, orderbook)| orderbook);
+        for orderbook in std::iter::once(&self.orderbook).chain(futures_books) {
+            let mut orderbook = orderbook.write().await;
+            if !orderbook.confirmed && orderbook.sequence != 0 && orderbook.sequence <= root {
+                orderbook.confirmed = true;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use chrono::TimeDelta;
+    use crate::exchange::solana::rpc_pool::RpcPool;
+    use solana_sdk::pubkey::Pubkey;
+    use tokio::sync::{mpsc, RwLock};
+    use crate::exchange::dexnow::data_structures::constants::{LINE_SIZE, NULL_LINE, NULL_ORDER};
+    use crate::exchange::dexnow::data_structures::full_book::EBookAccount;
+    use crate::exchange::dexnow::data_structures::instrument::Instrument;
+    use crate::exchange::dexnow::data_structures::market_accounts::MarketAccounts;
+    use crate::exchange::dexnow::data_structures::token::Token;
+    use crate::exchange::dexnow::decode_full_book::book_fixtures::{account, empty_tree, tree, write_line};
+    use crate::exchange::exchange_update::{EUpdatePayload, ExchangeUpdate};
+    use crate::exchange::trade_tape::TradeTape;
+    use super::*;
+
+    fn engine(commitment: CommitmentLevel) -> (DEXnowEngine, mpsc::Receiver<ExchangeUpdate>) {
+        let mut engine = DEXnowEngine::new(
+            RpcPool::new(vec!["http://localhost:8899".to_string()]),
+            Pubkey::new_unique(),
+            "DEXnow".to_string(),
+            Pubkey::new_unique(),
+            Arc::new(RwLock::new(OrderBook::new())),
+            Arc::new(RwLock::new(None)),
+            Arc::new(RwLock::new(TradeTape::new(TimeDelta::minutes(5)))),
+        );
+        let (tx, rx) = mpsc::channel(10);
+        engine.update_sender = Some(tx);
+        engine.commitment = commitment;
+        (engine, rx)
+    }
+
+    fn bid(px: f64) -> Vec<LinePx> {
+        vec![LinePx { px, qty: 1.0 }]
+    }
+
+    /// Lines account with the bid lines at the given indices
+    fn lines(bids: &[(usize, f64)]) -> Vec<u8> {
+        let mut lines = account(4, LINE_SIZE);
+        for (index, px) in bids {
+            write_line(&mut lines, *index, *px, 1.0, NULL_ORDER as u32);
+        }
+        lines
+    }
+
+    /// Publish the spot book like the listener does: kept as the version of its slot, rebuilt from the accounts
+    async fn publish(engine: &DEXnowEngine, market: &mut MarketAccounts) {
+        market.keep_version();
+        let book = engine.decode_full_book(&Instrument::default(), &market.accounts).unwrap().unwrap();
+        let bids = book.bids.iter().map(|book_line| &book_line.line);
+        let asks = book.asks.iter().map(|book_line| &book_line.line);
+        engine.process_orderbook_update(bids, asks, market.slot).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_processed_commitment_rolls_back_abandoned_fork() {
+        let (mut engine, mut rx) = engine(CommitmentLevel::Processed);
+        engine.tokens.insert(0, Token {
+            account: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            program_address: Pubkey::new_unique(),
+            id: 0,
+            decimals: 9,
+            base_crncy: false,
+            pool: false,
+            token_2022: false,
+            main_instr_id: None,
+        });
+        let mut market = MarketAccounts::default();
+        market.set(EBookAccount::AsksTree, empty_tree(), 10);
+        market.set(EBookAccount::BidsTree, tree(1, NULL_LINE, NULL_LINE), 10);
+        market.set(EBookAccount::Lines, lines(&[(1, 100.0)]), 10);
+        publish(&engine, &mut market).await;
+        // The fork abandoned later moves the best bid to another line
+        market.set(EBookAccount::BidsTree, tree(2, NULL_LINE, NULL_LINE), 12);
+        market.set(EBookAccount::Lines, lines(&[(1, 100.0), (2, 101.0)]), 12);
+        publish(&engine, &mut market).await;
+
+        // The new fork only changes the lines account, the bids tree must come back from slot 10
+        assert_eq!(market.roll_back_before(11), Some(10));
+        market.set(EBookAccount::Lines, lines(&[(1, 99.0)]), 11);
+        publish(&engine, &mut market).await;
+
+        let orderbook = engine.orderbook.read().await;
+        assert_eq!(orderbook.sequence, 11);
+        assert_eq!(orderbook.get_best_bid(), Some(OrderedFloat(99.0)));
+        assert!(!orderbook.confirmed);
+        let mut published_bids = Vec::new();
+        while let Ok(update) = rx.try_recv() {
+            if let EUpdatePayload::BestPrices(best_prices) = update.payload {
+                published_bids.push(best_prices.best_bid);
+            }
+        }
+        assert_eq!(published_bids, vec![OrderedFloat(100.0), OrderedFloat(101.0), OrderedFloat(99.0)]);
+    }
+
+    #[tokio::test]
+    async fn test_futures_book_is_published_under_its_own_name() {
+        let (mut engine, mut rx) = engine(CommitmentLevel::Confirmed);
+        let futures_book = Arc::new(RwLock::new(OrderBook::new()));
+        engine.futures_books = vec![("DEXnow Futures 0".to_string(), futures_book.clone())];
+
+        engine.process_futures_orderbook_update(0, &bid(152.0), &[], 12).await.unwrap();
+        // Derivatives without a published book are skipped
+        engine.process_futures_orderbook_update(1, &bid(153.0), &[], 12).await.unwrap();
+
+        assert_eq!(futures_book.read().await.get_best_bid(), Some(OrderedFloat(152.0)));
+        assert_eq!(engine.orderbook.read().await.get_best_bid(), None);
+        let update = rx.try_recv().unwrap();
+        assert_eq!(update.exchange_name, "DEXnow Futures 0");
+        assert!(matches!(update.payload, EUpdatePayload::BestPrices(best_prices) if best_prices.best_bid == OrderedFloat(152.0)));
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_processed_book_is_confirmed_once_its_slot_is_rooted() {
+        let (engine, _rx) = engine(CommitmentLevel::Processed);
+        engine.confirm_rooted_books(10).await;
+        engine.process_orderbook_update(&bid(101.0), &[], 10).await.unwrap();
+        assert!(engine.orderbook.read().await.confirmed);
+
+        engine.process_orderbook_update(&bid(102.0), &[], 12).await.unwrap();
+        assert!(!engine.orderbook.read().await.confirmed);
+
+        engine.confirm_rooted_books(11).await;
+        assert!(!engine.orderbook.read().await.confirmed);
+        engine.confirm_rooted_books(12).await;
+        assert!(engine.orderbook.read().await.confirmed);
+    }
+
+    #[tokio::test]
+    async fn test_confirmed_commitment_skips_outdated_slot() {
+        let (engine, _rx) = engine(CommitmentLevel::Confirmed);
+        engine.process_orderbook_update(&bid(101.0), &[], 12).await.unwrap();
+
+        engine.process_orderbook_update(&bid(99.0), &[], 11).await.unwrap();
+
+        let orderbook = engine.orderbook.read().await;
+        assert_eq!(orderbook.sequence, 12);
+        assert_eq!(orderbook.get_best_bid(), Some(OrderedFloat(101.0)));
+        assert!(orderbook.confirmed);
+    }
+
+    #[tokio::test]
+    async fn test_book_is_dated_by_its_slot() {
+        let (engine, _rx) = engine(CommitmentLevel::Confirmed);
+        let slot_received = Utc::now() - TimeDelta::seconds(1);
+        engine.slot_clock.lock().unwrap().on_slot(200, slot_received);
+
+        engine.process_orderbook_update(&bid(101.0), &[], 195).await.unwrap();
+
+        let orderbook = engine.orderbook.read().await;
+        assert_eq!(orderbook.last_updated, slot_received - TimeDelta::milliseconds(2000));
+    }
+}
@@ -0,0 +1,134 @@
+use std::env;
+use std::str::FromStr;
+use solana_sdk::pubkey::Pubkey;
+use thiserror::Error;
+use crate::exchange::dexnow::dexnow_engine::DEXnowEngine;
+use crate::exchange::dexnow::get_instrument_id::GetInstrIdArgs;
+use crate::trading_pair::ETradingPair;
+
+/// SOL is not an SPL token, DEXnow registers it with a fixed ID
+pub const SOL_TOKEN_ID: u32 = 0;
+/// Wrapped SOL mint, resolved to `SOL_TOKEN_ID`
+const NATIVE_SOL_MINT: &str = "So11111111111111111111111111111111111111112";
+/// Mints used when no `DEXNOW_<SYMBOL>_MINT` variable is set
+const DEFAULT_MINTS: [(&str, &str); 2] = [
+    ("SOL", NATIVE_SOL_MINT),
+    ("USDC", "A2Pz6rVyXuadFkKnhMXd1w9xgSrZd8m8sEGpuGuyFhaj"),
+];
+
+#[derive(Error, Debug, PartialEq)]
+pub enum PairResolutionError {
+    #[error("No mint known for {0}, set DEXNOW_{0}_MINT")]
+    UnknownSymbol(String),
+    #[error("Invalid mint {mint} configured for {symbol}")]
+    InvalidMint { symbol: String, mint: String },
+    #[error("Token {symbol} ({mint}) is not registered on DEXnow")]
+    TokenNotRegistered { symbol: String, mint: Pubkey },
+    #[error("Pair {asset}/{quote} is not listed on DEXnow")]
+    PairNotListed { asset: String, quote: String },
+    #[error("Failed to query DEXnow accounts: {0}")]
+    Rpc(String),
+}
+
+impl DEXnowEngine {
+    /// Find the instrument of the trading pair
+    /// `DEXNOW_ASSET_MINT` and `DEXNOW_QUOTE_MINT` select any listed instrument instead of the pair symbols
+    pub async fn resolve_pair(&self, trading_pair: &ETradingPair) -> Result<u64, PairResolutionError> {
+        let lookup_env = |name: &str| env::var(name).ok();
+        let (asset_symbol, asset_mint) = pair_token_mint("ASSET", trading_pair.asset_symbol(), lookup_env)?;
+        let (quote_symbol, quote_mint) = pair_token_mint("QUOTE", trading_pair.quote_symbol(), lookup_env)?;
+        let asset_token_id = self.resolve_token_id(&asset_symbol, &asset_mint).await?;
+        let quote_token_id = self.resolve_token_id(&quote_symbol, &quote_mint).await?;
+
+        let instr_id = self.get_instr_id(GetInstrIdArgs {
+            asset_token_id,
+            base_crncy_token_id: quote_token_id,
+        }).await.map_err(|e| PairResolutionError::Rpc(e.to_string()))?;
+        let instr_id = instr_id.ok_or(PairResolutionError::PairNotListed {
+            asset: asset_symbol.clone(),
+            quote: quote_symbol.clone(),
+        })?;
+        println!("[INFO][DEXnow] Resolved {}/{} to instrument {}", asset_symbol, quote_symbol, instr_id);
+        Ok(instr_id as u64)
+    }
+
+    /// Token ID of the mint, SOL has a fixed ID
+    async fn resolve_token_id(&self, symbol: &str, mint: &Pubkey) -> Result<u32, PairResolutionError> {
+        if *mint == Pubkey::from_str(NATIVE_SOL_MINT).unwrap() {
+            return Ok(SOL_TOKEN_ID);
+        }
+        self.get_token_id(mint).await
+            .map_err(|e| PairResolutionError::Rpc(e.to_string()))?
+            .ok_or(PairResolutionError::TokenNotRegistered { symbol: symbol.to_string(), mint: *mint })
+    }
+}
+
+/// Symbol and mint of one token of the pair: `DEXNOW_<ASSET|QUOTE>_MINT` if set, otherwise the mint of the symbol
+fn pair_token_mint(
+    role: &str,
+    symbol: &str,
+    lookup_env: impl Fn(&str) -> Option<String>,
+) -> Result<(String, Pubkey), PairResolutionError> {
+    match lookup_env(&format!("DEXNOW_{}_MINT", role)) {
+        Some(mint) => Ok((mint.clone(), parse_mint(&mint, &mint)?)),
+        None => Ok((symbol.to_string(), symbol_mint(symbol, lookup_env)?)),
+    }
+}
+
+/// Mint of the symbol from `DEXNOW_<SYMBOL>_MINT` or the default mints
+fn symbol_mint(symbol: &str, lookup_env: impl Fn(&str) -> Option<String>) -> Result<Pubkey, PairResolutionError> {
+    let mint = lookup_env(&format!("DEXNOW_{}_MINT", symbol))
+        .or_else(|| DEFAULT_MINTS.iter().find(|(known, _)| *known == symbol).map(|(_, mint)| mint.to_string()))
+        .ok_or_else(|| PairResolutionError::UnknownSymbol(symbol.to_string()))?;
+    parse_mint(symbol, &mint)
+}
+
+fn parse_mint(symbol: &str, mint: &str) -> Result<Pubkey, PairResolutionError> {
+    Pubkey::from_str(mint).map_err(|_| PairResolutionError::InvalidMint {
+        symbol: symbol.to_string(),
+        mint: mint.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BTC_MINT: &str = "3NZ9JMVBmGAqocybic2c7LQCJScmgsAZ6vQqTDzcqmJh";
+
+    #[test]
+    fn test_symbol_mint_defaults() {
+        let mint = symbol_mint("USDC", |_| None).unwrap();
+
+        assert_eq!(mint.to_string(), "A2Pz6rVyXuadFkKnhMXd1w9xgSrZd8m8sEGpuGuyFhaj");
+    }
+
+    #[test]
+    fn test_symbol_mint_from_env() {
+        let lookup_env = |name: &str| (name == "DEXNOW_BTC_MINT").then(|| BTC_MINT.to_string());
+
+        assert_eq!(symbol_mint("BTC", lookup_env).unwrap().to_string(), BTC_MINT);
+        assert_eq!(symbol_mint("ETH", lookup_env), Err(PairResolutionError::UnknownSymbol("ETH".to_string())));
+    }
+
+    #[test]
+    fn test_symbol_mint_rejects_invalid_mint() {
+        let result = symbol_mint("BTC", |_| Some("not-a-mint".to_string()));
+
+        assert_eq!(result, Err(PairResolutionError::InvalidMint {
+            symbol: "BTC".to_string(),
+            mint: "not-a-mint".to_string(),
+        }));
+    }
+
+    #[test]
+    fn test_pair_token_mint_override() {
+        let lookup_env = |name: &str| (name == "DEXNOW_ASSET_MINT").then(|| BTC_MINT.to_string());
+
+        let (symbol, mint) = pair_token_mint("ASSET", "SOL", lookup_env).unwrap();
+
+        assert_eq!(symbol, BTC_MINT);
+        assert_eq!(mint.to_string(), BTC_MINT);
+        assert_eq!(pair_token_mint("QUOTE", "USDC", lookup_env).unwrap().0, "USDC");
+    }
+}
@@ -1,3 +0,0 @@
-pub(super) mod account_notification;
-pub(super) mod subscription_response;
-pub(super) mod account_subscribe;
\ No newline at end of file
@@ -1,8 +1,9 @@
+use crate::exchange::dexnow::decode_error::DecodeError;
 use crate::exchange::dexnow::dexnow_engine::DEXnowEngine;
 
 impl DEXnowEngine {
-    pub fn token_dec(&self, token_id: u64) -> f64 {
-        let token = self.tokens.get(&token_id).expect("Token not found");
-        10f64.powi(token.decimals as i32)
+    pub fn token_dec(&self, token_id: u64) -> Result<f64, DecodeError> {
+        let token = self.tokens.get(&token_id).ok_or(DecodeError::UnknownToken(token_id))?;
+        Ok(10f64.powi(token.decimals as i32))
     }
-}
\ No newline at end of file
+}
@@ -1 +0,0 @@
-pub(super) mod read_basic_types;
\ No newline at end of file
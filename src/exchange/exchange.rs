@@ -3,7 +3,10 @@ use tokio::sync::mpsc::Sender;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use crate::exchange::exchange_update::ExchangeUpdate;
+use crate::exchange::instrument_rules::InstrumentRules;
+use crate::exchange::market_stats::MarketStats;
 use crate::exchange::order_book::OrderBook;
+use crate::exchange::trade_tape::TradeTape;
 use crate::trading_pair::ETradingPair;
 
 /// A trait for all exchanges to implement
@@ -20,4 +23,19 @@ pub trait Exchange: Send + Sync {
 
     /// Get a reference to the order book locker object
     fn get_order_book(&self) -> Arc<RwLock<OrderBook>>;
+
+    /// Get a reference to the trade tape locker object if the exchange streams public trades
+    fn get_trade_tape(&self) -> Option<Arc<RwLock<TradeTape>>> {
+        None
+    }
+
+    /// Get the trading rules of the instrument if they have been loaded
+    async fn get_instrument_rules(&self) -> Option<InstrumentRules> {
+        None
+    }
+
+    /// Get the latest market statistics if the exchange reports them
+    async fn get_market_stats(&self) -> Option<MarketStats> {
+        None
+    }
 }
\ No newline at end of file
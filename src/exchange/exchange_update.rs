@@ -1,15 +1,31 @@
+use chrono::{DateTime, Utc};
+use crate::exchange::connection_stats::ConnectionStats;
 use crate::exchange::order_book::TPrice;
+use crate::exchange::trade_tape::Trade;
 
 /// Best prices for a specific exchange
 #[derive(Debug)]
 pub struct BestPrices {
     pub best_bid: TPrice,
     pub best_ask: TPrice,
+    /// Exchange (or slot) time of the order book update
+    pub timestamp: DateTime<Utc>,
 }
 
-/// Represents an update to the best prices of an exchange
+/// Represents an update sent by an exchange to the arbitrage manager
 #[derive(Debug)]
 pub struct ExchangeUpdate {
     pub exchange_name: String,
-    pub best_prices: BestPrices,
-}
\ No newline at end of file
+    pub payload: EUpdatePayload,
+}
+
+/// The content of an exchange update
+#[derive(Debug)]
+pub enum EUpdatePayload {
+    /// Best prices of the order book have changed
+    BestPrices(BestPrices),
+    /// A public trade has been printed
+    Trade(Trade),
+    /// Connection state or attempt statistics have changed
+    ConnectionStats(ConnectionStats),
+}
@@ -0,0 +1,93 @@
+/// Trading rules of an instrument that every order must satisfy
+#[derive(Clone, Debug, PartialEq)]
+pub struct InstrumentRules {
+    /// Minimum price increment
+    pub tick_size: f64,
+    /// Minimum quantity increment
+    pub lot_size: f64,
+    /// Minimum order quantity
+    pub min_qty: f64,
+    /// Minimum order value in quote currency
+    pub min_notional: f64,
+}
+
+/// Tolerance for floating point errors when rounding to a step
+const STEP_EPSILON: f64 = 1e-9;
+
+impl InstrumentRules {
+    /// Rounds the quantity down to the lot size
+    pub fn round_qty(&self, qty: f64) -> f64 {
+        floor_to_step(qty, self.lot_size)
+    }
+
+    /// Rounds the price down to the tick size, used for sell orders
+    pub fn round_price_down(&self, price: f64) -> f64 {
+        floor_to_step(price, self.tick_size)
+    }
+
+    /// Rounds the price up to the tick size, used for buy orders
+    pub fn round_price_up(&self, price: f64) -> f64 {
+        ceil_to_step(price, self.tick_size)
+    }
+
+    /// Checks whether an order with the given quantity and price satisfies the minimum order size
+    pub fn is_order_allowed(&self, qty: f64, price: f64) -> bool {
+        qty + STEP_EPSILON >= self.min_qty && qty * price + STEP_EPSILON >= self.min_notional
+    }
+}
+
+fn floor_to_step(value: f64, step: f64) -> f64 {
+    if step <= 0.0 {
+        return value;
+    }
+    round_to_step_precision((value / step + STEP_EPSILON).floor() * step, step)
+}
+
+fn ceil_to_step(value: f64, step: f64) -> f64 {
+    if step <= 0.0 {
+        return value;
+    }
+    round_to_step_precision((value / step - STEP_EPSILON).ceil() * step, step)
+}
+
+/// Removes floating point noise like `0.30000000000000004` by rounding to the number of decimals of the step
+fn round_to_step_precision(value: f64, step: f64) -> f64 {
+    let decimals = (-step.log10()).ceil().max(0.0) as i32;
+    let factor = 10f64.powi(decimals);
+    (value * factor).round() / factor
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules() -> InstrumentRules {
+        InstrumentRules {
+            tick_size: 0.01,
+            lot_size: 0.001,
+            min_qty: 0.01,
+            min_notional: 5.0,
+        }
+    }
+
+    #[test]
+    fn test_round_qty_down_to_lot_size() {
+        assert_eq!(rules().round_qty(0.01234567), 0.012);
+        assert_eq!(rules().round_qty(0.3), 0.3);
+        assert_eq!(rules().round_qty(0.0009), 0.0);
+    }
+
+    #[test]
+    fn test_round_price_to_tick_size() {
+        assert_eq!(rules().round_price_down(143.5678), 143.56);
+        assert_eq!(rules().round_price_up(143.5612), 143.57);
+        assert_eq!(rules().round_price_up(143.56), 143.56);
+    }
+
+    #[test]
+    fn test_is_order_allowed() {
+        assert!(rules().is_order_allowed(0.05, 143.0));
+        assert!(!rules().is_order_allowed(0.009, 1000.0));
+        assert!(!rules().is_order_allowed(0.02, 143.0));
+    }
+}
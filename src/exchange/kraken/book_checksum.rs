@@ -0,0 +1,73 @@
+use crate::exchange::order_book::OrderBook;
+
+/// Number of levels per side covered by the checksum
+const CHECKSUM_DEPTH: usize = 10;
+
+/// Number of decimals used to format prices and quantities of an asset pair
+#[derive(Clone, Debug, PartialEq)]
+pub struct KrakenPrecision {
+    pub price_decimals: usize,
+    pub qty_decimals: usize,
+}
+
+/// CRC32 checksum of the top 10 asks (ascending) followed by the top 10 bids (descending)
+/// Every level contributes its price and quantity formatted with the pair precision,
+/// without the decimal point and leading zeros
+pub fn book_checksum(orderbook: &OrderBook, precision: &KrakenPrecision) -> u32 {
+    let mut payload = String::new();
+    let asks = orderbook.asks.iter().take(CHECKSUM_DEPTH);
+    let bids = orderbook.bids.iter().rev().take(CHECKSUM_DEPTH);
+    for (price, qty) in asks.chain(bids) {
+        payload.push_str(&format_level_value(price.into_inner(), precision.price_decimals));
+        payload.push_str(&format_level_value(*qty, precision.qty_decimals));
+    }
+    crc32fast::hash(payload.as_bytes())
+}
+
+fn format_level_value(value: f64, decimals: usize) -> String {
+    format!("{:.*}", decimals, value)
+        .replace('.', "")
+        .trim_start_matches('0')
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ordered_float::OrderedFloat;
+
+    #[test]
+    fn test_format_level_value() {
+        assert_eq!(format_level_value(143.5, 2), "14350");
+        assert_eq!(format_level_value(0.05, 8), "5000000");
+        assert_eq!(format_level_value(431.0, 8), "43100000000");
+    }
+
+    /// Book of the BTC/USD checksum example of the Kraken v2 spot WebSocket documentation
+    #[test]
+    fn test_checksum_of_documented_example() {
+        let asks = [
+            (45285.2, 0.001), (45286.4, 1.54571953), (45286.6, 1.54571109), (45289.6, 1.54560911),
+            (45290.2, 0.1589066), (45291.8, 1.54553491), (45294.7, 0.04454749), (45296.1, 0.3538),
+            (45297.5, 0.09945542), (45299.5, 0.18772827),
+        ];
+        let bids = [
+            (45283.5, 0.1), (45283.4, 1.54582015), (45282.1, 0.1), (45281.0, 0.1),
+            (45280.3, 1.54592586), (45279.0, 0.0799), (45277.6, 0.03310103), (45277.5, 0.3),
+            (45277.3, 1.54602737), (45276.6, 0.15445238),
+        ];
+        let mut orderbook = OrderBook::new();
+        orderbook.asks.extend(asks.map(|(price, qty)| (OrderedFloat(price), qty)));
+        orderbook.bids.extend(bids.map(|(price, qty)| (OrderedFloat(price), qty)));
+        let precision = KrakenPrecision { price_decimals: 1, qty_decimals: 8 };
+
+        assert_eq!(book_checksum(&orderbook, &precision), 3310070434);
+    }
+
+    #[test]
+    fn test_checksum_of_empty_book() {
+        let precision = KrakenPrecision { price_decimals: 2, qty_decimals: 8 };
+
+        assert_eq!(book_checksum(&OrderBook::new(), &precision), crc32fast::hash(b""));
+    }
+}
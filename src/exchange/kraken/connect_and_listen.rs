@@ -0,0 +1,106 @@
+use std::env;
+use std::sync::atomic::{AtomicBool, Ordering};
+use async_trait::async_trait;
+use tokio::sync::mpsc::Sender;
+use tokio_tungstenite::tungstenite::protocol::Message;
+use crate::exchange::exchange_update::ExchangeUpdate;
+use crate::exchange::kraken::kraken_exchange::KrakenExchange;
+use crate::exchange::kraken::ws_book::{BookMessage, EBookMessageType};
+use crate::exchange::kraken::ws_method::{ChannelMessage, MethodRequest, MethodResponse};
+use crate::exchange::reconnect_policy::ReconnectPolicy;
+use crate::exchange::ws_connection::ws_connection_manager::run_ws_connection;
+use crate::exchange::ws_connection::ws_protocol::{EHeartbeat, EWsMessage, WsProtocol};
+use crate::trading_pair::ETradingPair;
+
+/// Number of book levels per side to subscribe to
+const BOOK_DEPTH: usize = 25;
+
+/// Kraken v2 protocol: JSON method requests, `ping` method heartbeat and checksummed book channel
+struct KrakenWsProtocol<'a> {
+    exchange: &'a KrakenExchange,
+    symbol: String,
+    update_sender: &'a Sender<ExchangeUpdate>,
+    /// Set after a checksum mismatch, updates are ignored until a new snapshot arrives
+    awaiting_snapshot: AtomicBool,
+}
+
+#[async_trait]
+impl WsProtocol for KrakenWsProtocol<'_> {
+    fn name(&self) -> String {
+        self.exchange.name.clone()
+    }
+
+    fn url(&self) -> String {
+        env::var("KRAKEN_WS_URL").expect("KRAKEN_WS_URL must be set")
+    }
+
+    async fn on_connecting(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.awaiting_snapshot.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn subscribe_messages(&self) -> Result<Vec<Message>, Box<dyn std::error::Error>> {
+        let subscribe = MethodRequest::subscribe_book(&self.symbol, BOOK_DEPTH);
+        Ok(vec![Message::Text(serde_json::to_string(&subscribe)?)])
+    }
+
+    fn resubscribe_messages(&self) -> Result<Vec<Message>, Box<dyn std::error::Error>> {
+        // Subscribing twice is rejected, the book must be unsubscribed first
+        let unsubscribe = MethodRequest::unsubscribe_book(&self.symbol, BOOK_DEPTH);
+        let subscribe = MethodRequest::subscribe_book(&self.symbol, BOOK_DEPTH);
+        Ok(vec![
+            Message::Text(serde_json::to_string(&unsubscribe)?),
+            Message::Text(serde_json::to_string(&subscribe)?),
+        ])
+    }
+
+    fn heartbeat(&self) -> EHeartbeat {
+        EHeartbeat::Text(serde_json::to_string(&MethodRequest::ping()).expect("Ping message is serializable"))
+    }
+
+    async fn handle_text(&self, text: &str) -> EWsMessage {
+        if let Ok(book_message) = serde_json::from_str::<BookMessage>(text) {
+            if book_message.message_type == EBookMessageType::Snapshot {
+                self.awaiting_snapshot.store(false, Ordering::SeqCst);
+            } else if self.awaiting_snapshot.load(Ordering::SeqCst) {
+                return EWsMessage::Handled;
+            }
+            if !self.exchange.process_book_message(self.update_sender, book_message, BOOK_DEPTH).await {
+                self.awaiting_snapshot.store(true, Ordering::SeqCst);
+                return EWsMessage::Resubscribe;
+            }
+        } else if let Ok(response) = serde_json::from_str::<MethodResponse>(text) {
+            if response.method == "pong" {
+                return EWsMessage::Pong;
+            }
+            if let Some(error) = response.error {
+                eprintln!("[ERROR][Kraken] {} failed: {}", response.method, error);
+            } else {
+                println!("[INFO][Kraken] {} succeeded", response.method);
+            }
+        } else if serde_json::from_str::<ChannelMessage>(text)
+            .is_ok_and(|message| message.channel == "heartbeat" || message.channel == "status") {
+            // Heartbeat and status messages carry no book data
+        } else {
+            return EWsMessage::Unknown;
+        }
+        EWsMessage::Handled
+    }
+}
+
+impl KrakenExchange {
+    /// Maintain the WebSocket connection until the reconnect budget is exhausted
+    pub async fn connect_and_listen(
+        &self,
+        trading_pair: &ETradingPair,
+        order_book_update_sender: &Sender<ExchangeUpdate>,
+    ) {
+        let protocol = KrakenWsProtocol {
+            exchange: self,
+            symbol: Self::symbol(trading_pair),
+            update_sender: order_book_update_sender,
+            awaiting_snapshot: AtomicBool::new(true),
+        };
+        run_ws_connection(&protocol, ReconnectPolicy::default(), order_book_update_sender).await;
+    }
+}
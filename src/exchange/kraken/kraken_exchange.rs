@@ -0,0 +1,81 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use tokio::sync::mpsc::Sender;
+use tokio::sync::RwLock;
+use crate::exchange::exchange::Exchange;
+use crate::exchange::exchange_update::ExchangeUpdate;
+use crate::exchange::instrument_rules::InstrumentRules;
+use crate::exchange::kraken::book_checksum::KrakenPrecision;
+use crate::exchange::order_book::OrderBook;
+use crate::trading_pair::ETradingPair;
+
+pub struct KrakenExchange {
+    pub(crate) name: String,
+    pub(crate) orderbook: Arc<RwLock<OrderBook>>,
+    pub(crate) instrument_rules: RwLock<Option<InstrumentRules>>,
+    /// Precision of the pair, required to validate book checksums
+    pub(crate) precision: RwLock<Option<KrakenPrecision>>,
+}
+
+impl KrakenExchange {
+    /// Returns the Kraken WebSocket symbol of the trading pair
+    pub(crate) fn symbol(trading_pair: &ETradingPair) -> String {
+        match trading_pair {
+            ETradingPair::BtcUsdc => "BTC/USDC".to_string(),
+            ETradingPair::SolUsdc => "SOL/USDC".to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl Exchange for KrakenExchange {
+    fn new() -> Self {
+        KrakenExchange {
+            name: "Kraken".to_string(),
+            orderbook: Arc::new(RwLock::new(OrderBook::new())),
+            instrument_rules: RwLock::new(None),
+            precision: RwLock::new(None),
+        }
+    }
+
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    async fn start(&self, trading_pair: ETradingPair, update_sender: Sender<ExchangeUpdate>) {
+        let symbol = Self::symbol(&trading_pair);
+        let asset_pair = match self.load_asset_pair(&symbol).await {
+            Ok(pair) => {
+                println!("[INFO][Kraken] Loaded asset pair {}: {:?}", symbol, pair);
+                Some(pair)
+            }
+            Err(e) => {
+                eprintln!("[ERROR][Kraken] Failed to load asset pair {}, book checksums will not be validated: {}", symbol, e);
+                None
+            }
+        };
+        if let Some(pair) = asset_pair {
+            *self.precision.write().await = Some(KrakenPrecision {
+                price_decimals: pair.pair_decimals,
+                qty_decimals: pair.lot_decimals,
+            });
+            *self.instrument_rules.write().await = Some(InstrumentRules {
+                tick_size: pair.tick_size,
+                lot_size: 10f64.powi(-(pair.lot_decimals as i32)),
+                min_qty: pair.ordermin,
+                min_notional: pair.costmin,
+            });
+        }
+
+        println!("[INFO][Kraken] Starting exchange websocket...");
+        self.connect_and_listen(&trading_pair, &update_sender).await;
+    }
+
+    fn get_order_book(&self) -> Arc<RwLock<OrderBook>> {
+        self.orderbook.clone()
+    }
+
+    async fn get_instrument_rules(&self) -> Option<InstrumentRules> {
+        self.instrument_rules.read().await.clone()
+    }
+}
@@ -0,0 +1,25 @@
+use std::env;
+use crate::exchange::kraken::kraken_exchange::KrakenExchange;
+use crate::exchange::kraken::rest_asset_pairs::{AssetPair, AssetPairsResponse};
+
+impl KrakenExchange {
+    /// Load the precision and trading rules of the pair from the AssetPairs REST endpoint
+    pub(crate) async fn load_asset_pair(&self, symbol: &str) -> Result<AssetPair, Box<dyn std::error::Error>> {
+        let url = env::var("KRAKEN_REST_URL").expect("KRAKEN_REST_URL must be set");
+        let response = reqwest::Client::new()
+            .get(format!("{}/0/public/AssetPairs", url.trim_end_matches('/')))
+            .query(&[("pair", symbol)])
+            .send()
+            .await?
+            .json::<AssetPairsResponse>()
+            .await?;
+
+        if !response.error.is_empty() {
+            return Err(format!("Kraken AssetPairs error: {}", response.error.join(", ")).into());
+        }
+        response.result
+            .into_values()
+            .find(|pair| pair.wsname == symbol)
+            .ok_or_else(|| format!("Asset pair {} not found on Kraken", symbol).into())
+    }
+}
@@ -0,0 +1,8 @@
+pub mod kraken_exchange;
+pub mod connect_and_listen;
+pub mod ws_book;
+pub mod ws_method;
+pub mod rest_asset_pairs;
+pub mod book_checksum;
+mod process_book_update;
+mod load_asset_pair;
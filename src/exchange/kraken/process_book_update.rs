@@ -0,0 +1,184 @@
+use std::collections::BTreeMap;
+use chrono::Utc;
+use ordered_float::OrderedFloat;
+use tokio::sync::mpsc::Sender;
+use crate::exchange::exchange_update::ExchangeUpdate;
+use crate::exchange::kraken::book_checksum::{book_checksum, KrakenPrecision};
+use crate::exchange::kraken::kraken_exchange::KrakenExchange;
+use crate::exchange::kraken::ws_book::{BookData, BookLevel, BookMessage, EBookMessageType};
+use crate::exchange::order_book::{OrderBook, TPrice, TVolume};
+use crate::exchange::send_orderbook_update::send_orderbook_update;
+
+impl KrakenExchange {
+    /// Apply a book message to the local order book and validate it against the checksum
+    /// Returns false if the local book does not match the checksum and has been cleared
+    pub(crate) async fn process_book_message(
+        &self,
+        update_sender: &Sender<ExchangeUpdate>,
+        message: BookMessage,
+        depth: usize,
+    ) -> bool {
+        let precision = self.precision.read().await.clone();
+        let mut orderbook = self.orderbook.write().await;
+        let mut is_consistent = true;
+        for data in &message.data {
+            is_consistent = apply_book_data(&mut orderbook, &message.message_type, data, depth, precision.as_ref());
+            if !is_consistent {
+                *orderbook = OrderBook::new();
+                break;
+            }
+        }
+
+        // *** Notify subscribers about the updated orderbook ***
+        send_orderbook_update(
+            update_sender,
+            &self.name,
+            &orderbook.get_best_bid(),
+            &orderbook.get_best_ask(),
+            orderbook.last_updated,
+        ).await;
+        is_consistent
+    }
+}
+
+/// Apply the book data and check the result, the checksum is skipped if the pair precision is unknown
+fn apply_book_data(
+    orderbook: &mut OrderBook,
+    message_type: &EBookMessageType,
+    data: &BookData,
+    depth: usize,
+    precision: Option<&KrakenPrecision>,
+) -> bool {
+    if *message_type == EBookMessageType::Snapshot {
+        *orderbook = OrderBook::new();
+    }
+    apply_levels(&mut orderbook.bids, &data.bids);
+    apply_levels(&mut orderbook.asks, &data.asks);
+
+    // *** Levels pushed out of the subscribed depth are not updated anymore ***
+    while orderbook.bids.len() > depth {
+        orderbook.bids.pop_first();
+    }
+    while orderbook.asks.len() > depth {
+        orderbook.asks.pop_last();
+    }
+
+    orderbook.sequence += 1;
+    orderbook.last_updated = data.timestamp.unwrap_or_else(Utc::now);
+
+    match precision {
+        Some(precision) => {
+            let checksum = book_checksum(orderbook, precision);
+            if checksum != data.checksum {
+                println!("[WARNING][Kraken] Checksum mismatch: local {}, received {}", checksum, data.checksum);
+                return false;
+            }
+            true
+        }
+        None => true,
+    }
+}
+
+fn apply_levels(side: &mut BTreeMap<TPrice, TVolume>, levels: &[BookLevel]) {
+    for level in levels {
+        if level.qty == 0.0 {
+            side.remove(&OrderedFloat(level.price));
+        } else {
+            side.insert(OrderedFloat(level.price), level.qty);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEPTH: usize = 25;
+
+    /// Synthetic `SOL/USDC` snapshot in the v2 book format with 11 levels per side, the checksum is computed
+    /// from the documented algorithm, not captured from Kraken
+    const SNAPSHOT_FIXTURE: &str = r#"
+    {
+        "channel":"book",
+        "type":"snapshot",
+        "data":[
+            {
+                "symbol":"SOL/USDC",
+                "bids":[
+                    {"price":143.52,"qty":431.0},{"price":143.51,"qty":2.5},{"price":143.50,"qty":10.0},
+                    {"price":143.45,"qty":0.1},{"price":143.40,"qty":7.0},{"price":143.33,"qty":5.5},
+                    {"price":143.30,"qty":60.0},{"price":143.25,"qty":1.0},{"price":143.20,"qty":8.8},
+                    {"price":143.10,"qty":12.0},{"price":143.00,"qty":50.0}
+                ],
+                "asks":[
+                    {"price":143.55,"qty":12.5},{"price":143.56,"qty":0.7},{"price":143.60,"qty":31.2},
+                    {"price":143.61,"qty":1.05},{"price":143.65,"qty":4.0},{"price":143.70,"qty":100.0},
+                    {"price":143.72,"qty":0.25},{"price":143.80,"qty":9.9},{"price":143.81,"qty":3.0},
+                    {"price":143.90,"qty":15.0},{"price":144.00,"qty":22.0}
+                ],
+                "checksum":865678129
+            }
+        ]
+    }
+    "#;
+
+    /// Update removing the best ask, which moves the 11th ask into the checksum
+    const UPDATE_FIXTURE: &str = r#"
+    {
+        "channel":"book",
+        "type":"update",
+        "data":[
+            {
+                "symbol":"SOL/USDC",
+                "bids":[{"price":143.53,"qty":1.2}],
+                "asks":[{"price":143.55,"qty":0}],
+                "checksum":4190257337,
+                "timestamp":"2024-08-22T09:10:00.123456Z"
+            }
+        ]
+    }
+    "#;
+
+    fn precision() -> KrakenPrecision {
+        KrakenPrecision { price_decimals: 2, qty_decimals: 8 }
+    }
+
+    fn apply_fixture(orderbook: &mut OrderBook, fixture: &str) -> bool {
+        let message = serde_json::from_str::<BookMessage>(fixture).unwrap();
+        message.data.iter().all(|data| apply_book_data(orderbook, &message.message_type, data, DEPTH, Some(&precision())))
+    }
+
+    #[test]
+    fn test_snapshot_and_update_match_checksum() {
+        let mut orderbook = OrderBook::new();
+
+        assert!(apply_fixture(&mut orderbook, SNAPSHOT_FIXTURE));
+        assert!(apply_fixture(&mut orderbook, UPDATE_FIXTURE));
+        assert_eq!(orderbook.get_best_bid(), Some(OrderedFloat(143.53)));
+        assert_eq!(orderbook.get_best_ask(), Some(OrderedFloat(143.56)));
+    }
+
+    #[test]
+    fn test_missed_update_fails_checksum() {
+        let mut orderbook = OrderBook::new();
+        assert!(apply_fixture(&mut orderbook, SNAPSHOT_FIXTURE));
+
+        // The bid level of the update was lost
+        let corrupted = UPDATE_FIXTURE.replace(r#""bids":[{"price":143.53,"qty":1.2}]"#, r#""bids":[]"#);
+
+        assert!(!apply_fixture(&mut orderbook, &corrupted));
+    }
+
+    #[test]
+    fn test_snapshot_replaces_book_and_truncates_to_depth() {
+        let mut orderbook = OrderBook::new();
+        orderbook.bids.insert(OrderedFloat(1.0), 1.0);
+        let message = serde_json::from_str::<BookMessage>(SNAPSHOT_FIXTURE).unwrap();
+
+        assert!(apply_book_data(&mut orderbook, &message.message_type, &message.data[0], 5, None));
+        assert_eq!(orderbook.bids.len(), 5);
+        assert_eq!(orderbook.asks.len(), 5);
+        assert_eq!(orderbook.get_best_bid(), Some(OrderedFloat(143.52)));
+        assert_eq!(orderbook.asks.last_key_value(), Some((&OrderedFloat(143.65), &4.0)));
+    }
+}
@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use serde::{Deserialize, Deserializer};
+
+/// Response of the `/0/public/AssetPairs` endpoint
+#[derive(Debug, Deserialize)]
+pub struct AssetPairsResponse {
+    /// Error messages, empty on success
+    pub error: Vec<String>,
+    /// Asset pairs keyed by the pair name
+    #[serde(default)]
+    pub result: HashMap<String, AssetPair>,
+}
+
+/// Represents the trading rules of a single asset pair
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+pub struct AssetPair {
+    /// Alternate pair name
+    pub altname: String,
+    /// Pair name used by the WebSocket API
+    pub wsname: String,
+    /// Number of decimals of the price
+    pub pair_decimals: usize,
+    /// Number of decimals of the volume
+    pub lot_decimals: usize,
+    /// Minimum order volume
+    #[serde(deserialize_with = "deserialize_f64_from_str")]
+    pub ordermin: f64,
+    /// Minimum order cost in quote currency
+    #[serde(deserialize_with = "deserialize_f64_from_str")]
+    pub costmin: f64,
+    /// The step to increase/reduce order price
+    #[serde(deserialize_with = "deserialize_f64_from_str")]
+    pub tick_size: f64,
+}
+
+fn deserialize_f64_from_str<'de, D>(deserializer: D) -> Result<f64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    f64::from_str(&s).map_err(serde::de::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn test_asset_pairs_deserialization() {
+        let json_data = r#"
+        {
+            "error":[],
+            "result":{
+                "SOLUSDC":{
+                    "altname":"SOLUSDC",
+                    "wsname":"SOL/USDC",
+                    "aclass_base":"currency",
+                    "base":"SOL",
+                    "aclass_quote":"currency",
+                    "quote":"USDC",
+                    "cost_decimals":5,
+                    "pair_decimals":2,
+                    "lot_decimals":8,
+                    "lot_multiplier":1,
+                    "ordermin":"0.02",
+                    "costmin":"0.5",
+                    "tick_size":"0.01",
+                    "status":"online"
+                }
+            }
+        }
+        "#;
+
+        let result = serde_json::from_str::<AssetPairsResponse>(json_data);
+
+        assert!(result.is_ok(), "Failed to parse JSON: {:?}", result.err());
+
+        let response = result.unwrap();
+        assert!(response.error.is_empty());
+        let pair = &response.result["SOLUSDC"];
+        assert_eq!(pair.wsname, "SOL/USDC");
+        assert_eq!(pair.pair_decimals, 2);
+        assert_eq!(pair.lot_decimals, 8);
+        assert_eq!(pair.ordermin, 0.02);
+        assert_eq!(pair.costmin, 0.5);
+        assert_eq!(pair.tick_size, 0.01);
+    }
+}
@@ -0,0 +1,109 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+/// Represents a message of the v2 `book` channel
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+pub struct BookMessage {
+    /// Channel name, always `book`
+    pub channel: String,
+    /// Snapshot or update
+    #[serde(rename = "type")]
+    pub message_type: EBookMessageType,
+    pub data: Vec<BookData>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum EBookMessageType {
+    /// The full book up to the subscribed depth, the local book must be replaced
+    Snapshot,
+    /// Changed levels, the local book must be updated and truncated to the subscribed depth
+    Update,
+}
+
+/// Book data of a single symbol
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+pub struct BookData {
+    /// Symbol name, e.g. `SOL/USDC`
+    pub symbol: String,
+    pub bids: Vec<BookLevel>,
+    pub asks: Vec<BookLevel>,
+    /// CRC32 checksum of the top 10 levels of the book after this message has been applied
+    pub checksum: u32,
+    /// The timestamp of the order book update, only present in updates
+    pub timestamp: Option<DateTime<Utc>>,
+}
+
+/// Represents a price level in the order book, zero quantity means the level must be removed
+#[derive(Debug, Deserialize)]
+pub struct BookLevel {
+    pub price: f64,
+    pub qty: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn test_book_snapshot_deserialization() {
+        let json_data = r#"
+        {
+            "channel":"book",
+            "type":"snapshot",
+            "data":[
+                {
+                    "symbol":"SOL/USDC",
+                    "bids":[{"price":143.52,"qty":431.0},{"price":143.51,"qty":2.5}],
+                    "asks":[{"price":143.55,"qty":12.5}],
+                    "checksum":865678129
+                }
+            ]
+        }
+        "#;
+
+        let result = serde_json::from_str::<BookMessage>(json_data);
+
+        assert!(result.is_ok(), "Failed to parse JSON: {:?}", result.err());
+
+        let message = result.unwrap();
+        assert_eq!(message.message_type, EBookMessageType::Snapshot);
+        let data = &message.data[0];
+        assert_eq!(data.symbol, "SOL/USDC");
+        assert_eq!(data.bids.len(), 2);
+        assert_eq!(data.asks[0].price, 143.55);
+        assert_eq!(data.asks[0].qty, 12.5);
+        assert_eq!(data.checksum, 865678129);
+        assert!(data.timestamp.is_none());
+    }
+
+    #[test]
+    fn test_book_update_deserialization() {
+        let json_data = r#"
+        {
+            "channel":"book",
+            "type":"update",
+            "data":[
+                {
+                    "symbol":"SOL/USDC",
+                    "bids":[{"price":143.53,"qty":1.2}],
+                    "asks":[{"price":143.55,"qty":0}],
+                    "checksum":4190257337,
+                    "timestamp":"2024-08-22T09:10:00.123456Z"
+                }
+            ]
+        }
+        "#;
+
+        let message = serde_json::from_str::<BookMessage>(json_data).unwrap();
+
+        assert_eq!(message.message_type, EBookMessageType::Update);
+        let data = &message.data[0];
+        assert_eq!(data.asks[0].qty, 0.0);
+        assert_eq!(data.checksum, 4190257337);
+        assert_eq!(data.timestamp.unwrap().timestamp_millis(), 1724317800123);
+    }
+}
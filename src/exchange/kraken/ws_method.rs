@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+
+/// Request sent to the v2 WebSocket API, e.g. `subscribe`, `unsubscribe` or `ping`
+#[derive(Debug, Serialize)]
+pub struct MethodRequest {
+    pub method: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub params: Option<BookParams>,
+}
+
+/// Parameters of the `book` channel subscription
+#[derive(Debug, Serialize)]
+pub struct BookParams {
+    pub channel: String,
+    pub symbol: Vec<String>,
+    /// Number of levels per side
+    pub depth: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snapshot: Option<bool>,
+}
+
+impl MethodRequest {
+    pub fn ping() -> Self {
+        MethodRequest { method: "ping".to_string(), params: None }
+    }
+
+    pub fn subscribe_book(symbol: &str, depth: usize) -> Self {
+        MethodRequest {
+            method: "subscribe".to_string(),
+            params: Some(BookParams {
+                channel: "book".to_string(),
+                symbol: vec![symbol.to_string()],
+                depth,
+                snapshot: Some(true),
+            }),
+        }
+    }
+
+    pub fn unsubscribe_book(symbol: &str, depth: usize) -> Self {
+        MethodRequest {
+            method: "unsubscribe".to_string(),
+            params: Some(BookParams {
+                channel: "book".to_string(),
+                symbol: vec![symbol.to_string()],
+                depth,
+                snapshot: None,
+            }),
+        }
+    }
+}
+
+/// Response to a method request
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+pub struct MethodResponse {
+    pub method: String,
+    /// Missing for `pong`
+    pub success: Option<bool>,
+    pub error: Option<String>,
+}
+
+/// Any channel message, used for `heartbeat` and `status` messages which carry no book data
+#[derive(Debug, Deserialize)]
+pub struct ChannelMessage {
+    pub channel: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn test_subscribe_book_serialization() {
+        let request = MethodRequest::subscribe_book("SOL/USDC", 25);
+
+        assert_eq!(
+            serde_json::to_string(&request).unwrap(),
+            r#"{"method":"subscribe","params":{"channel":"book","symbol":["SOL/USDC"],"depth":25,"snapshot":true}}"#
+        );
+    }
+
+    #[test]
+    fn test_method_response_deserialization() {
+        let pong = r#"{"method":"pong","time_in":"2024-08-22T09:10:00.000000Z","time_out":"2024-08-22T09:10:00.000010Z"}"#;
+        let subscribe = r#"{"method":"subscribe","result":{"channel":"book","depth":25,"snapshot":true,"symbol":"SOL/USDC"},"success":true,"time_in":"2024-08-22T09:10:00.000000Z","time_out":"2024-08-22T09:10:00.000010Z"}"#;
+
+        let pong = serde_json::from_str::<MethodResponse>(pong).unwrap();
+        let subscribe = serde_json::from_str::<MethodResponse>(subscribe).unwrap();
+
+        assert_eq!(pong.method, "pong");
+        assert_eq!(subscribe.method, "subscribe");
+        assert_eq!(subscribe.success, Some(true));
+    }
+}
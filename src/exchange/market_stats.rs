@@ -0,0 +1,16 @@
+/// Market statistics an exchange reports besides its order book
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MarketStats {
+    /// Last trade price
+    pub last_px: Option<f64>,
+    /// Fixing price used for derivatives settlement
+    pub fixing_px: Option<f64>,
+    /// Volatility over the last day
+    pub day_sigma: Option<f64>,
+    /// Volatility over the last hour
+    pub hour_sigma: Option<f64>,
+    /// Base currency volume of the day
+    pub day_volume: Option<f64>,
+    /// Number of trades of the day
+    pub day_trades: Option<u64>,
+}
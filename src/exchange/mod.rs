@@ -1,6 +1,20 @@
 pub mod exchange;
 pub mod bybit;
+pub mod binance;
+pub mod kraken;
+pub mod okx;
 pub mod exchange_update;
 pub mod order_book;
+pub mod trade_tape;
+pub mod instrument_rules;
+pub mod market_stats;
+pub mod connection_stats;
+pub mod reconnect_policy;
+pub mod ws_connection;
 pub mod dexnow;
-mod send_orderbook_update;
\ No newline at end of file
+pub mod raydium;
+pub mod orca;
+pub mod solana;
+mod send_orderbook_update;
+mod send_trade_update;
+mod send_connection_update;
@@ -0,0 +1,106 @@
+use std::env;
+use std::time::Duration;
+use async_trait::async_trait;
+use tokio::sync::mpsc::Sender;
+use tokio_tungstenite::tungstenite::protocol::Message;
+use crate::exchange::exchange_update::ExchangeUpdate;
+use crate::exchange::okx::okx_exchange::OkxExchange;
+use crate::exchange::okx::ws_books::BooksUpdate;
+use crate::exchange::okx::ws_event::{EventResponse, OperationRequest};
+use crate::exchange::reconnect_policy::ReconnectPolicy;
+use crate::exchange::ws_connection::ws_connection_manager::run_ws_connection;
+use crate::exchange::ws_connection::ws_protocol::{EHeartbeat, EWsMessage, WsProtocol};
+use crate::trading_pair::ETradingPair;
+
+/// Order book channel: 400 levels, snapshot followed by incremental updates
+const BOOK_CHANNEL: &str = "books";
+/// The server closes connections without traffic for 30 seconds
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(15);
+const PONG_TIMEOUT: Duration = Duration::from_secs(25);
+
+/// OKX public protocol: JSON operations and plain text `ping`/`pong` heartbeat
+struct OkxWsProtocol<'a> {
+    exchange: &'a OkxExchange,
+    inst_id: String,
+    update_sender: &'a Sender<ExchangeUpdate>,
+}
+
+#[async_trait]
+impl WsProtocol for OkxWsProtocol<'_> {
+    fn name(&self) -> String {
+        self.exchange.name.clone()
+    }
+
+    fn url(&self) -> String {
+        env::var("OKX_WS_URL").expect("OKX_WS_URL must be set")
+    }
+
+    async fn on_connecting(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.exchange.book.lock().await.reset();
+        Ok(())
+    }
+
+    fn subscribe_messages(&self) -> Result<Vec<Message>, Box<dyn std::error::Error>> {
+        let subscribe = OperationRequest::new("subscribe", BOOK_CHANNEL, &self.inst_id);
+        Ok(vec![Message::Text(serde_json::to_string(&subscribe)?)])
+    }
+
+    fn resubscribe_messages(&self) -> Result<Vec<Message>, Box<dyn std::error::Error>> {
+        // A new subscription starts with a snapshot
+        let unsubscribe = OperationRequest::new("unsubscribe", BOOK_CHANNEL, &self.inst_id);
+        let subscribe = OperationRequest::new("subscribe", BOOK_CHANNEL, &self.inst_id);
+        Ok(vec![
+            Message::Text(serde_json::to_string(&unsubscribe)?),
+            Message::Text(serde_json::to_string(&subscribe)?),
+        ])
+    }
+
+    fn heartbeat(&self) -> EHeartbeat {
+        EHeartbeat::Text("ping".to_string())
+    }
+
+    fn heartbeat_interval(&self) -> Duration {
+        HEARTBEAT_INTERVAL
+    }
+
+    fn pong_timeout(&self) -> Duration {
+        PONG_TIMEOUT
+    }
+
+    async fn handle_text(&self, text: &str) -> EWsMessage {
+        if text == "pong" {
+            return EWsMessage::Pong;
+        }
+        if let Ok(books_update) = serde_json::from_str::<BooksUpdate>(text) {
+            if let Err(e) = self.exchange.process_books_update(self.update_sender, books_update).await {
+                println!("[WARNING][OKX] Order book is out of sync: {}", e);
+                return EWsMessage::Resubscribe;
+            }
+        } else if let Ok(event) = serde_json::from_str::<EventResponse>(text) {
+            if event.event == "error" {
+                eprintln!("[ERROR][OKX] Error {:?}: {:?}", event.code, event.msg);
+            } else {
+                println!("[INFO][OKX] {}: {:?}", event.event, event.arg);
+            }
+        } else {
+            return EWsMessage::Unknown;
+        }
+        EWsMessage::Handled
+    }
+}
+
+impl OkxExchange {
+    /// Maintain the WebSocket connection until the reconnect budget is exhausted
+    pub async fn connect_and_listen(
+        &self,
+        trading_pair: &ETradingPair,
+        order_book_update_sender: &Sender<ExchangeUpdate>,
+    ) {
+        let protocol = OkxWsProtocol {
+            exchange: self,
+            inst_id: Self::inst_id(trading_pair),
+            update_sender: order_book_update_sender,
+        };
+        run_ws_connection(&protocol, ReconnectPolicy::default(), order_book_update_sender).await;
+    }
+}
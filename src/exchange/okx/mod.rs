@@ -0,0 +1,6 @@
+pub mod okx_exchange;
+pub mod connect_and_listen;
+pub mod ws_books;
+pub mod ws_event;
+pub mod okx_book;
+mod process_books_update;
@@ -0,0 +1,211 @@
+use std::collections::BTreeMap;
+use ordered_float::OrderedFloat;
+use thiserror::Error;
+use crate::exchange::okx::ws_books::{BooksData, BooksLevel, EBooksAction};
+use crate::exchange::order_book::{OrderBook, TPrice};
+
+/// Number of levels per side covered by the checksum
+const CHECKSUM_DEPTH: usize = 25;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum OkxBookError {
+    #[error("Update received before a snapshot")]
+    NotSynchronized,
+    #[error("Sequence gap: expected previous sequence {expected}, received {received}")]
+    SequenceGap { expected: i64, received: i64 },
+    #[error("Checksum mismatch: local {local}, received {received}")]
+    ChecksumMismatch { local: i32, received: i32 },
+    #[error("Invalid price level: {0:?}")]
+    InvalidLevel(BooksLevel),
+}
+
+/// Local copy of the book keeping the levels as sent by the exchange,
+/// which is required to reproduce the checksum
+#[derive(Default)]
+pub struct OkxBook {
+    bids: BTreeMap<TPrice, BooksLevel>,
+    asks: BTreeMap<TPrice, BooksLevel>,
+    /// Sequence ID of the last applied message
+    seq_id: Option<i64>,
+    /// False until a snapshot has been applied
+    is_synchronized: bool,
+}
+
+impl OkxBook {
+    /// Forget the local state, a new snapshot is required
+    pub fn reset(&mut self) {
+        *self = OkxBook::default();
+    }
+
+    pub fn is_synchronized(&self) -> bool {
+        self.is_synchronized
+    }
+
+    /// Apply a snapshot or an update, the book is reset if it can not be trusted anymore
+    pub fn apply(&mut self, action: &EBooksAction, data: &BooksData) -> Result<(), OkxBookError> {
+        let result = self.try_apply(action, data);
+        if result.is_err() {
+            self.reset();
+        }
+        result
+    }
+
+    fn try_apply(&mut self, action: &EBooksAction, data: &BooksData) -> Result<(), OkxBookError> {
+        match action {
+            EBooksAction::Snapshot => {
+                self.bids.clear();
+                self.asks.clear();
+                self.is_synchronized = true;
+            }
+            EBooksAction::Update => {
+                if !self.is_synchronized {
+                    return Err(OkxBookError::NotSynchronized);
+                }
+                if let (Some(expected), Some(received)) = (self.seq_id, data.prev_seq_id) {
+                    if expected != received {
+                        return Err(OkxBookError::SequenceGap { expected, received });
+                    }
+                }
+            }
+        }
+
+        apply_levels(&mut self.bids, &data.bids)?;
+        apply_levels(&mut self.asks, &data.asks)?;
+        self.seq_id = data.seq_id;
+
+        if let Some(received) = data.checksum {
+            let local = self.checksum();
+            if local != received {
+                return Err(OkxBookError::ChecksumMismatch { local, received });
+            }
+        }
+        Ok(())
+    }
+
+    /// CRC32 of the top 25 bids and asks interleaved as `bid_price:bid_size:ask_price:ask_size:...`
+    pub fn checksum(&self) -> i32 {
+        let mut bids = self.bids.values().rev();
+        let mut asks = self.asks.values();
+        let mut parts = Vec::with_capacity(CHECKSUM_DEPTH * 2);
+        for _ in 0..CHECKSUM_DEPTH {
+            if let Some(bid) = bids.next() {
+                parts.push(format!("{}:{}", bid.price(), bid.size()));
+            }
+            if let Some(ask) = asks.next() {
+                parts.push(format!("{}:{}", ask.price(), ask.size()));
+            }
+        }
+        crc32fast::hash(parts.join(":").as_bytes()) as i32
+    }
+
+    /// Copy the levels into the shared order book
+    pub fn fill_order_book(&self, orderbook: &mut OrderBook) -> Result<(), OkxBookError> {
+        orderbook.bids = to_volumes(&self.bids)?;
+        orderbook.asks = to_volumes(&self.asks)?;
+        if let Some(seq_id) = self.seq_id {
+            orderbook.sequence = seq_id as u64;
+        }
+        Ok(())
+    }
+}
+
+fn apply_levels(side: &mut BTreeMap<TPrice, BooksLevel>, levels: &[BooksLevel]) -> Result<(), OkxBookError> {
+    for level in levels {
+        let price = parse_level_value(level.price(), level)?;
+        if parse_level_value(level.size(), level)? == 0.0 {
+            side.remove(&OrderedFloat(price));
+        } else {
+            side.insert(OrderedFloat(price), level.clone());
+        }
+    }
+    Ok(())
+}
+
+fn to_volumes(side: &BTreeMap<TPrice, BooksLevel>) -> Result<BTreeMap<TPrice, f64>, OkxBookError> {
+    side.iter()
+        .map(|(price, level)| Ok((*price, parse_level_value(level.size(), level)?)))
+        .collect()
+}
+
+fn parse_level_value(value: &str, level: &BooksLevel) -> Result<f64, OkxBookError> {
+    value.parse::<f64>().map_err(|_| OkxBookError::InvalidLevel(level.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::exchange::okx::ws_books::BooksUpdate;
+    use crate::exchange::okx::ws_books::tests::{SNAPSHOT_FIXTURE, UPDATE_FIXTURE};
+    use super::*;
+
+    fn apply_fixture(book: &mut OkxBook, fixture: &str) -> Result<(), OkxBookError> {
+        let update = serde_json::from_str::<BooksUpdate>(fixture).unwrap();
+        update.data.iter().try_for_each(|data| book.apply(&update.action, data))
+    }
+
+    #[test]
+    fn test_snapshot_and_update_match_checksum() {
+        let mut book = OkxBook::default();
+
+        assert_eq!(apply_fixture(&mut book, SNAPSHOT_FIXTURE), Ok(()));
+        assert_eq!(apply_fixture(&mut book, UPDATE_FIXTURE), Ok(()));
+
+        let mut orderbook = OrderBook::new();
+        book.fill_order_book(&mut orderbook).unwrap();
+        assert_eq!(orderbook.sequence, 101);
+        assert_eq!(orderbook.get_best_bid(), Some(OrderedFloat(143.53)));
+        assert_eq!(orderbook.get_best_ask(), Some(OrderedFloat(143.56)));
+        assert_eq!(orderbook.bids.get(&OrderedFloat(143.52)), Some(&431.0));
+    }
+
+    /// Books of the two checksum examples of the OKX order book channel documentation
+    #[test]
+    fn test_checksum_of_documented_examples() {
+        let snapshot = |bids: &str, asks: &str| format!(r#"
+        {{
+            "arg":{{"channel":"books","instId":"ETH-USDT"}},
+            "action":"snapshot",
+            "data":[{{"asks":{},"bids":{},"ts":"1597026383085","prevSeqId":-1,"seqId":1}}]
+        }}
+        "#, asks, bids);
+
+        let mut book = OkxBook::default();
+        let example = snapshot(r#"[["3366.1","7","0","3"],["3366","6","3","4"]]"#, r#"[["3366.8","9","10","3"],["3368","8","3","4"]]"#);
+        apply_fixture(&mut book, &example).unwrap();
+        assert_eq!(book.checksum(), 2413953002u32 as i32);
+
+        let mut book = OkxBook::default();
+        let example = snapshot(r#"[["3366.1","7","0","3"]]"#, r#"[["3366.8","9","10","3"],["3368","8","3","4"],["3372","8","3","4"]]"#);
+        apply_fixture(&mut book, &example).unwrap();
+        assert_eq!(book.checksum(), 831078360);
+    }
+
+    #[test]
+    fn test_update_before_snapshot_is_rejected() {
+        let mut book = OkxBook::default();
+
+        assert_eq!(apply_fixture(&mut book, UPDATE_FIXTURE), Err(OkxBookError::NotSynchronized));
+    }
+
+    #[test]
+    fn test_sequence_gap_resets_book() {
+        let mut book = OkxBook::default();
+        apply_fixture(&mut book, SNAPSHOT_FIXTURE).unwrap();
+
+        let gap = UPDATE_FIXTURE.replace(r#""prevSeqId":100"#, r#""prevSeqId":99"#);
+
+        assert_eq!(apply_fixture(&mut book, &gap), Err(OkxBookError::SequenceGap { expected: 100, received: 99 }));
+        assert!(!book.is_synchronized());
+    }
+
+    #[test]
+    fn test_checksum_mismatch_resets_book() {
+        let mut book = OkxBook::default();
+        apply_fixture(&mut book, SNAPSHOT_FIXTURE).unwrap();
+
+        // The bid level of the update was lost
+        let corrupted = UPDATE_FIXTURE.replace(r#""bids":[["143.53","1.2","0","1"]]"#, r#""bids":[]"#);
+
+        assert!(matches!(apply_fixture(&mut book, &corrupted), Err(OkxBookError::ChecksumMismatch { received: -1504590165, .. })));
+        assert!(!book.is_synchronized());
+    }
+}
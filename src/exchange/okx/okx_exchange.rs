@@ -0,0 +1,50 @@
+use std::sync::Arc;
+use async_trait::async_trait;
+use tokio::sync::mpsc::Sender;
+use tokio::sync::{Mutex, RwLock};
+use crate::exchange::exchange::Exchange;
+use crate::exchange::exchange_update::ExchangeUpdate;
+use crate::exchange::okx::okx_book::OkxBook;
+use crate::exchange::order_book::OrderBook;
+use crate::trading_pair::ETradingPair;
+
+pub struct OkxExchange {
+    pub(crate) name: String,
+    pub(crate) orderbook: Arc<RwLock<OrderBook>>,
+    /// Levels as sent by the exchange, used for sequence and checksum validation
+    pub(crate) book: Mutex<OkxBook>,
+}
+
+impl OkxExchange {
+    /// Returns the OKX instrument ID of the trading pair
+    pub(crate) fn inst_id(trading_pair: &ETradingPair) -> String {
+        match trading_pair {
+            ETradingPair::BtcUsdc => "BTC-USDC".to_string(),
+            ETradingPair::SolUsdc => "SOL-USDC".to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl Exchange for OkxExchange {
+    fn new() -> Self {
+        OkxExchange {
+            name: "OKX".to_string(),
+            orderbook: Arc::new(RwLock::new(OrderBook::new())),
+            book: Mutex::new(OkxBook::default()),
+        }
+    }
+
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    async fn start(&self, trading_pair: ETradingPair, update_sender: Sender<ExchangeUpdate>) {
+        println!("[INFO][OKX] Starting exchange websocket...");
+        self.connect_and_listen(&trading_pair, &update_sender).await;
+    }
+
+    fn get_order_book(&self) -> Arc<RwLock<OrderBook>> {
+        self.orderbook.clone()
+    }
+}
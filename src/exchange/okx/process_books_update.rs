@@ -0,0 +1,46 @@
+use chrono::DateTime;
+use tokio::sync::mpsc::Sender;
+use crate::exchange::exchange_update::ExchangeUpdate;
+use crate::exchange::okx::okx_book::OkxBookError;
+use crate::exchange::okx::okx_exchange::OkxExchange;
+use crate::exchange::okx::ws_books::{BooksUpdate, EBooksAction};
+use crate::exchange::order_book::OrderBook;
+use crate::exchange::send_orderbook_update::send_orderbook_update;
+
+impl OkxExchange {
+    /// Apply a books message to the local order book
+    /// On error the book has been cleared and a new snapshot is required
+    pub(crate) async fn process_books_update(
+        &self,
+        update_sender: &Sender<ExchangeUpdate>,
+        update: BooksUpdate,
+    ) -> Result<(), OkxBookError> {
+        let mut book = self.book.lock().await;
+        if update.action == EBooksAction::Update && !book.is_synchronized() {
+            // Updates of the previous subscription may arrive until the new snapshot
+            return Ok(());
+        }
+        let mut orderbook = self.orderbook.write().await;
+        let mut result = Ok(());
+        for data in &update.data {
+            result = book.apply(&update.action, data).and_then(|_| book.fill_order_book(&mut orderbook));
+            if result.is_err() {
+                *orderbook = OrderBook::new();
+                break;
+            }
+            if let Some(timestamp) = DateTime::from_timestamp_millis(data.ts) {
+                orderbook.last_updated = timestamp;
+            }
+        }
+
+        // *** Notify subscribers about the updated orderbook ***
+        send_orderbook_update(
+            update_sender,
+            &self.name,
+            &orderbook.get_best_bid(),
+            &orderbook.get_best_ask(),
+            orderbook.last_updated,
+        ).await;
+        result
+    }
+}
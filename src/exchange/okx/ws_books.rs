@@ -0,0 +1,167 @@
+use serde::Deserialize;
+
+/// Represents a message of the `books` channel
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+pub struct BooksUpdate {
+    pub arg: ChannelArg,
+    /// Snapshot or update, absent on channels which always push the full book (e.g. `books5`)
+    #[serde(default)]
+    pub action: EBooksAction,
+    pub data: Vec<BooksData>,
+}
+
+/// Channel and instrument of a subscription
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChannelArg {
+    pub channel: String,
+    pub inst_id: String,
+}
+
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum EBooksAction {
+    /// The full book, the local book must be replaced
+    #[default]
+    Snapshot,
+    /// Changed levels
+    Update,
+}
+
+/// Book data of a single instrument
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BooksData {
+    pub asks: Vec<BooksLevel>,
+    pub bids: Vec<BooksLevel>,
+    /// Order book generation time in milliseconds
+    #[serde(deserialize_with = "deserialize_i64_from_str")]
+    pub ts: i64,
+    /// CRC32 checksum of the top 25 levels, signed
+    pub checksum: Option<i32>,
+    /// Sequence ID of the previous message, -1 for snapshots
+    pub prev_seq_id: Option<i64>,
+    /// Sequence ID of this message, equal to `prev_seq_id` if the book did not change
+    pub seq_id: Option<i64>,
+}
+
+/// Represents a price level as sent by the exchange: `[price, size, deprecated, number of orders]`
+/// Price and size are kept as strings because the checksum is computed over the original representation
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct BooksLevel(pub String, pub String, pub String, pub String);
+
+impl BooksLevel {
+    pub fn price(&self) -> &str {
+        &self.0
+    }
+
+    pub fn size(&self) -> &str {
+        &self.1
+    }
+}
+
+fn deserialize_i64_from_str<'de, D>(deserializer: D) -> Result<i64, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    s.parse::<i64>().map_err(serde::de::Error::custom)
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+    use serde_json;
+
+    /// Synthetic `SOL-USDC` snapshot in the `books` format, the checksum is computed from the documented
+    /// algorithm, not captured from OKX
+    pub(in crate::exchange::okx) const SNAPSHOT_FIXTURE: &str = r#"
+    {
+        "arg":{"channel":"books","instId":"SOL-USDC"},
+        "action":"snapshot",
+        "data":[
+            {
+                "asks":[["143.55","12.5","0","1"],["143.56","0.7","0","1"]],
+                "bids":[["143.52","431","0","3"],["143.51","2.5","0","1"],["143.5","10","0","2"]],
+                "ts":"1724318672920",
+                "checksum":1884510137,
+                "prevSeqId":-1,
+                "seqId":100
+            }
+        ]
+    }
+    "#;
+
+    /// Update following the snapshot: a new best bid and the best ask removed
+    pub(in crate::exchange::okx) const UPDATE_FIXTURE: &str = r#"
+    {
+        "arg":{"channel":"books","instId":"SOL-USDC"},
+        "action":"update",
+        "data":[
+            {
+                "asks":[["143.55","0","0","0"]],
+                "bids":[["143.53","1.2","0","1"]],
+                "ts":"1724318673020",
+                "checksum":-1504590165,
+                "prevSeqId":100,
+                "seqId":101
+            }
+        ]
+    }
+    "#;
+
+    #[test]
+    fn test_books_snapshot_deserialization() {
+        let result = serde_json::from_str::<BooksUpdate>(SNAPSHOT_FIXTURE);
+
+        assert!(result.is_ok(), "Failed to parse JSON: {:?}", result.err());
+
+        let update = result.unwrap();
+        assert_eq!(update.arg.channel, "books");
+        assert_eq!(update.arg.inst_id, "SOL-USDC");
+        assert_eq!(update.action, EBooksAction::Snapshot);
+        let data = &update.data[0];
+        assert_eq!(data.bids[0].price(), "143.52");
+        assert_eq!(data.bids[0].size(), "431");
+        assert_eq!(data.ts, 1724318672920);
+        assert_eq!(data.checksum, Some(1884510137));
+        assert_eq!(data.prev_seq_id, Some(-1));
+        assert_eq!(data.seq_id, Some(100));
+    }
+
+    #[test]
+    fn test_books_update_deserialization() {
+        let update = serde_json::from_str::<BooksUpdate>(UPDATE_FIXTURE).unwrap();
+
+        assert_eq!(update.action, EBooksAction::Update);
+        assert_eq!(update.data[0].asks[0].size(), "0");
+        assert_eq!(update.data[0].checksum, Some(-1504590165));
+        assert_eq!(update.data[0].prev_seq_id, Some(100));
+    }
+
+    #[test]
+    fn test_books5_deserialization() {
+        let json_data = r#"
+        {
+            "arg":{"channel":"books5","instId":"SOL-USDC"},
+            "data":[
+                {
+                    "asks":[["143.55","12.5","0","1"]],
+                    "bids":[["143.52","431","0","3"]],
+                    "instId":"SOL-USDC",
+                    "ts":"1724318672920",
+                    "seqId":100
+                }
+            ]
+        }
+        "#;
+
+        let update = serde_json::from_str::<BooksUpdate>(json_data).unwrap();
+
+        assert_eq!(update.action, EBooksAction::Snapshot);
+        assert_eq!(update.data[0].checksum, None);
+        assert_eq!(update.data[0].prev_seq_id, None);
+    }
+}
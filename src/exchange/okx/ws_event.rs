@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+use crate::exchange::okx::ws_books::ChannelArg;
+
+/// Subscribe or unsubscribe request
+#[derive(Debug, Serialize)]
+pub struct OperationRequest {
+    pub op: String,
+    pub args: Vec<OperationArg>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OperationArg {
+    pub channel: String,
+    pub inst_id: String,
+}
+
+impl OperationRequest {
+    pub fn new(op: &str, channel: &str, inst_id: &str) -> Self {
+        OperationRequest {
+            op: op.to_string(),
+            args: vec![OperationArg {
+                channel: channel.to_string(),
+                inst_id: inst_id.to_string(),
+            }],
+        }
+    }
+}
+
+/// Response to an operation request
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+pub struct EventResponse {
+    /// `subscribe`, `unsubscribe` or `error`
+    pub event: String,
+    pub arg: Option<ChannelArg>,
+    pub code: Option<String>,
+    pub msg: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json;
+
+    #[test]
+    fn test_subscribe_serialization() {
+        let request = OperationRequest::new("subscribe", "books", "SOL-USDC");
+
+        assert_eq!(
+            serde_json::to_string(&request).unwrap(),
+            r#"{"op":"subscribe","args":[{"channel":"books","instId":"SOL-USDC"}]}"#
+        );
+    }
+
+    #[test]
+    fn test_event_response_deserialization() {
+        let subscribed = r#"{"event":"subscribe","arg":{"channel":"books","instId":"SOL-USDC"},"connId":"a4d3ae55"}"#;
+        let error = r#"{"event":"error","code":"60012","msg":"Invalid request","connId":"a4d3ae55"}"#;
+
+        let subscribed = serde_json::from_str::<EventResponse>(subscribed).unwrap();
+        let error = serde_json::from_str::<EventResponse>(error).unwrap();
+
+        assert_eq!(subscribed.event, "subscribe");
+        assert_eq!(subscribed.arg.unwrap().inst_id, "SOL-USDC");
+        assert_eq!(error.event, "error");
+        assert_eq!(error.code.as_deref(), Some("60012"));
+    }
+}
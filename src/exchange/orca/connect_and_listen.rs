@@ -0,0 +1,182 @@
+use std::env;
+use async_trait::async_trait;
+use solana_sdk::commitment_config::CommitmentLevel;
+use solana_sdk::pubkey::Pubkey;
+use tokio::sync::mpsc::Sender;
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::protocol::Message;
+use crate::exchange::exchange_update::ExchangeUpdate;
+use crate::exchange::orca::load_pool::TickArrayWindow;
+use crate::exchange::orca::orca_exchange::OrcaExchange;
+use crate::exchange::reconnect_policy::ReconnectPolicy;
+use crate::exchange::solana::pubsub_client::{EPubSubMessage, ESubscriptionTarget, PubSubClient};
+use crate::exchange::ws_connection::ws_connection_manager::run_ws_connection;
+use crate::exchange::ws_connection::ws_protocol::{EHeartbeat, EWsMessage, WsProtocol};
+
+/// Accounts of the pool the driver subscribes to
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum EWhirlpoolAccount {
+    Whirlpool,
+    TickArray(i32),
+}
+
+/// Solana PubSub protocol for a whirlpool and the tick arrays around its current price
+/// When the price moves to another tick array the window is reloaded on its own task, the tick arrays
+/// are replaced and resubscribed by the first notification after the reload
+struct OrcaWsProtocol<'a> {
+    exchange: &'a OrcaExchange,
+    base_mint: &'a Pubkey,
+    update_sender: &'a Sender<ExchangeUpdate>,
+    /// Tick array accounts of the current window
+    tick_array_accounts: std::sync::Mutex<Vec<(Pubkey, i32)>>,
+    /// Subscriptions to the whirlpool and the tick arrays of the current window
+    pubsub: PubSubClient<EWhirlpoolAccount>,
+    /// Unsubscribe requests of the tick arrays which have left the window
+    stale_messages: std::sync::Mutex<Vec<Message>>,
+    /// Tick arrays being reloaded after the price left the window
+    window_request: std::sync::Mutex<Option<JoinHandle<Result<TickArrayWindow, String>>>>,
+}
+
+impl OrcaWsProtocol<'_> {
+    fn tick_array_subscribe_messages(&self) -> Result<Vec<Message>, Box<dyn std::error::Error>> {
+        let tick_array_accounts = self.tick_array_accounts.lock().unwrap().clone();
+        let mut messages = Vec::with_capacity(tick_array_accounts.len());
+        for (account, start_tick_index) in tick_array_accounts {
+            messages.push(self.pubsub.subscribe(ESubscriptionTarget::Account(account), EWhirlpoolAccount::TickArray(start_tick_index))?);
+        }
+        Ok(messages)
+    }
+
+    /// Start reloading the tick arrays around the current price, unless a reload is running
+    async fn request_tick_array_window(&self) {
+        let Some(whirlpool) = self.exchange.pool.lock().await.whirlpool.clone() else {
+            eprintln!("[ERROR][Orca] Failed to reload tick arrays: Whirlpool is not loaded");
+            return;
+        };
+        let mut request = self.window_request.lock().unwrap();
+        if request.is_none() {
+            println!("[INFO][Orca] Price left the loaded tick arrays, reloading them...");
+            *request = Some(self.exchange.spawn_tick_array_window(whirlpool));
+        }
+    }
+
+    /// Reloaded tick arrays, once the reload has finished
+    fn take_tick_array_window(&self) -> Option<JoinHandle<Result<TickArrayWindow, String>>> {
+        let mut request = self.window_request.lock().unwrap();
+        if request.as_ref().is_some_and(|request| request.is_finished()) {
+            request.take()
+        } else {
+            None
+        }
+    }
+
+    /// Replace the tick arrays with the reloaded window and mark the old subscriptions as stale
+    async fn move_tick_array_window(&self, request: JoinHandle<Result<TickArrayWindow, String>>) -> Result<(), String> {
+        let window = request.await.map_err(|e| e.to_string())??;
+        let tick_array_accounts = self.exchange.install_tick_array_window(window).await;
+        *self.tick_array_accounts.lock().unwrap() = tick_array_accounts;
+
+        let stale_messages = self.pubsub
+            .unsubscribe_where(|account| matches!(account, EWhirlpoolAccount::TickArray(_)))
+            .map_err(|e| e.to_string())?;
+        self.stale_messages.lock().unwrap().extend(stale_messages);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl WsProtocol for OrcaWsProtocol<'_> {
+    fn name(&self) -> String {
+        self.exchange.name.clone()
+    }
+
+    fn url(&self) -> String {
+        env::var("ORCA_WS_URL").expect("ORCA_WS_URL must be set")
+    }
+
+    async fn on_connecting(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let tick_array_accounts = self.exchange.load_pool(self.base_mint).await?;
+        self.exchange.process_pool_update(self.update_sender, 0).await;
+
+        self.pubsub.clear();
+        self.pubsub.add(ESubscriptionTarget::Account(self.exchange.whirlpool_account), EWhirlpoolAccount::Whirlpool);
+        for (account, start_tick_index) in &tick_array_accounts {
+            self.pubsub.add(ESubscriptionTarget::Account(*account), EWhirlpoolAccount::TickArray(*start_tick_index));
+        }
+        *self.tick_array_accounts.lock().unwrap() = tick_array_accounts;
+        self.stale_messages.lock().unwrap().clear();
+        if let Some(request) = self.window_request.lock().unwrap().take() {
+            request.abort();
+        }
+        println!("[INFO][Orca] Connecting to Solana WebSocket to listen whirlpool {}...", self.exchange.whirlpool_account);
+        Ok(())
+    }
+
+    fn subscribe_messages(&self) -> Result<Vec<Message>, Box<dyn std::error::Error>> {
+        Ok(self.pubsub.subscribe_messages()?)
+    }
+
+    fn resubscribe_messages(&self) -> Result<Vec<Message>, Box<dyn std::error::Error>> {
+        // Only the tick arrays change, the whirlpool subscription stays
+        let mut messages: Vec<Message> = self.stale_messages.lock().unwrap().drain(..).collect();
+        messages.extend(self.tick_array_subscribe_messages()?);
+        Ok(messages)
+    }
+
+    fn heartbeat(&self) -> EHeartbeat {
+        EHeartbeat::PingFrame
+    }
+
+    async fn handle_text(&self, text: &str) -> EWsMessage {
+        let (kind, slot, data) = match self.pubsub.handle_text(text) {
+            Some(EPubSubMessage::Account { key, slot, data }) => (key, slot, data),
+            // Subscribe and unsubscribe responses, and notifications of stale tick array subscriptions
+            Some(EPubSubMessage::Subscribed { .. } | EPubSubMessage::Unsubscribed | EPubSubMessage::Stale) => return EWsMessage::Handled,
+            Some(EPubSubMessage::Program { .. } | EPubSubMessage::Slot { .. }) | None => return EWsMessage::Unknown,
+        };
+        match kind {
+            EWhirlpoolAccount::Whirlpool => match self.exchange.process_whirlpool_update(&data).await {
+                Ok(true) => self.request_tick_array_window().await,
+                Ok(false) => {}
+                Err(e) => eprintln!("[ERROR][Orca] Failed to decode whirlpool account: {}", e),
+            },
+            EWhirlpoolAccount::TickArray(start_tick_index) => {
+                if let Err(e) = self.exchange.process_tick_array_update(&data).await {
+                    eprintln!("[ERROR][Orca] Failed to decode tick array {}: {}", start_tick_index, e);
+                }
+            }
+        }
+        let mut resubscribe = false;
+        if let Some(request) = self.take_tick_array_window() {
+            match self.move_tick_array_window(request).await {
+                Ok(()) => resubscribe = true,
+                Err(e) => eprintln!("[ERROR][Orca] Failed to reload tick arrays: {}", e),
+            }
+        }
+        self.exchange.process_pool_update(self.update_sender, slot).await;
+        if resubscribe {
+            return EWsMessage::Resubscribe;
+        }
+        EWsMessage::Handled
+    }
+}
+
+impl OrcaExchange {
+    /// Listen to the whirlpool and its tick arrays until the reconnect budget is exhausted
+    pub async fn connect_and_listen(
+        &self,
+        base_mint: &Pubkey,
+        update_sender: &Sender<ExchangeUpdate>,
+    ) {
+        let protocol = OrcaWsProtocol {
+            exchange: self,
+            base_mint,
+            update_sender,
+            tick_array_accounts: std::sync::Mutex::new(Vec::new()),
+            pubsub: PubSubClient::new(CommitmentLevel::Confirmed),
+            stale_messages: std::sync::Mutex::new(Vec::new()),
+            window_request: std::sync::Mutex::new(None),
+        };
+        run_ws_connection(&protocol, ReconnectPolicy::default(), update_sender).await;
+    }
+}
@@ -0,0 +1,110 @@
+use std::collections::BTreeMap;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use tokio::task::JoinHandle;
+use crate::exchange::orca::orca_exchange::{OrcaExchange, WHIRLPOOL_PROGRAM_ID};
+use crate::exchange::orca::tick_array::{Tick, TickArray, TICK_ARRAY_SIZE};
+use crate::exchange::orca::whirlpool::Whirlpool;
+use crate::exchange::solana::token_account::decode_mint_decimals;
+
+/// Number of tick arrays loaded on each side of the tick array with the current price
+const TICK_ARRAYS_AROUND: i32 = 2;
+
+impl OrcaExchange {
+    /// Load the pool, the token decimals and the tick arrays around the current price over HTTP
+    /// Returns the tick array accounts to subscribe to
+    pub(crate) async fn load_pool(&self, base_mint: &Pubkey) -> Result<Vec<(Pubkey, i32)>, Box<dyn std::error::Error>> {
+        let whirlpool_account = self.connection.get_account(&self.whirlpool_account).await?;
+        let whirlpool = Whirlpool::decode(&whirlpool_account.data)?;
+
+        let inverted = if whirlpool.token_mint_a == *base_mint {
+            false
+        } else if whirlpool.token_mint_b == *base_mint {
+            true
+        } else {
+            return Err(format!("Whirlpool {} does not trade mint {}", self.whirlpool_account, base_mint).into());
+        };
+
+        let mints = self.connection.get_multiple_accounts(&[whirlpool.token_mint_a, whirlpool.token_mint_b]).await?;
+        let [Some(mint_a), Some(mint_b)] = mints.as_slice() else {
+            return Err(format!("Token mints of whirlpool {} not found", self.whirlpool_account).into());
+        };
+        let decimals_a = decode_mint_decimals(&mint_a.data)?;
+        let decimals_b = decode_mint_decimals(&mint_b.data)?;
+
+        let tick_array_accounts = self.load_tick_arrays(&whirlpool).await?;
+
+        let mut pool = self.pool.lock().await;
+        pool.whirlpool = Some(whirlpool);
+        pool.decimals_a = decimals_a;
+        pool.decimals_b = decimals_b;
+        pool.inverted = inverted;
+        Ok(tick_array_accounts)
+    }
+
+    /// Replace the loaded tick arrays with the ones around the current price of the pool
+    /// Returns the tick array accounts to subscribe to
+    pub(crate) async fn load_tick_arrays(&self, whirlpool: &Whirlpool) -> Result<Vec<(Pubkey, i32)>, Box<dyn std::error::Error>> {
+        let window = fetch_tick_array_window(&self.connection, self.whirlpool_account, whirlpool).await.map_err(|e| e.to_string())?;
+        Ok(self.install_tick_array_window(window).await)
+    }
+
+    /// Load the tick arrays around the current price of the pool on their own task, so the notifications keep being read
+    pub(crate) fn spawn_tick_array_window(&self, whirlpool: Whirlpool) -> JoinHandle<Result<TickArrayWindow, String>> {
+        let connection = self.connection.clone();
+        let whirlpool_account = self.whirlpool_account;
+        tokio::spawn(async move {
+            fetch_tick_array_window(&connection, whirlpool_account, &whirlpool).await.map_err(|e| e.to_string())
+        })
+    }
+
+    /// Replace the loaded tick arrays with the window
+    /// Returns the tick array accounts to subscribe to
+    pub(crate) async fn install_tick_array_window(&self, window: TickArrayWindow) -> Vec<(Pubkey, i32)> {
+        let mut pool = self.pool.lock().await;
+        pool.tick_arrays = window.tick_arrays;
+        pool.tick_arrays_center = window.center;
+        window.accounts
+    }
+}
+
+/// Tick arrays around a price, loaded before they replace the ones of the pool
+pub(crate) struct TickArrayWindow {
+    /// Start tick index of the tick array with the price
+    center: i32,
+    /// Tick array accounts with their start tick index
+    accounts: Vec<(Pubkey, i32)>,
+    tick_arrays: BTreeMap<i32, TickArray>,
+}
+
+async fn fetch_tick_array_window(
+    connection: &RpcClient,
+    whirlpool_account: Pubkey,
+    whirlpool: &Whirlpool,
+) -> Result<TickArrayWindow, Box<dyn std::error::Error + Send + Sync>> {
+    let ticks_in_array = TICK_ARRAY_SIZE * whirlpool.tick_spacing as i32;
+    let center = TickArray::start_tick_index_of(whirlpool.tick_current_index, whirlpool.tick_spacing);
+    let accounts: Vec<(Pubkey, i32)> = (-TICK_ARRAYS_AROUND..=TICK_ARRAYS_AROUND)
+        .map(|i| center + i * ticks_in_array)
+        .map(|start_tick_index| {
+            (TickArray::address(&WHIRLPOOL_PROGRAM_ID, &whirlpool_account, start_tick_index), start_tick_index)
+        })
+        .collect();
+
+    let addresses: Vec<Pubkey> = accounts.iter().map(|(address, _)| *address).collect();
+    let tick_array_accounts = connection.get_multiple_accounts(&addresses).await?;
+
+    let mut tick_arrays = BTreeMap::new();
+    for ((_, start_tick_index), account) in accounts.iter().zip(tick_array_accounts) {
+        // A tick array which has not been created yet has no initialized ticks
+        let tick_array = match account {
+            Some(account) => TickArray::decode(&account.data)?,
+            None => TickArray {
+                start_tick_index: *start_tick_index,
+                ticks: vec![Tick::default(); TICK_ARRAY_SIZE as usize],
+            },
+        };
+        tick_arrays.insert(*start_tick_index, tick_array);
+    }
+    Ok(TickArrayWindow { center, accounts, tick_arrays })
+}
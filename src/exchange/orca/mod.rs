@@ -0,0 +1,9 @@
+pub mod orca_exchange;
+pub mod connect_and_listen;
+pub mod whirlpool;
+pub mod tick_array;
+pub mod tick_math;
+pub mod swap_math;
+pub mod whirlpool_state;
+mod load_pool;
+mod process_pool_update;
@@ -0,0 +1,60 @@
+use std::env;
+use std::str::FromStr;
+use std::sync::Arc;
+use async_trait::async_trait;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey;
+use solana_sdk::pubkey::Pubkey;
+use tokio::sync::mpsc::Sender;
+use tokio::sync::{Mutex, RwLock};
+use crate::exchange::exchange::Exchange;
+use crate::exchange::exchange_update::ExchangeUpdate;
+use crate::exchange::orca::whirlpool_state::WhirlpoolState;
+use crate::exchange::order_book::OrderBook;
+use crate::exchange::solana::token_mint::base_mint;
+use crate::trading_pair::ETradingPair;
+
+/// Orca Whirlpools program
+pub(crate) const WHIRLPOOL_PROGRAM_ID: Pubkey = pubkey!("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc");
+
+pub struct OrcaExchange {
+    pub(crate) name: String,
+    pub(crate) orderbook: Arc<RwLock<OrderBook>>,
+    pub(crate) connection: Arc<RpcClient>,
+    /// Whirlpool account of the pool
+    pub(crate) whirlpool_account: Pubkey,
+    pub(crate) pool: Mutex<WhirlpoolState>,
+}
+
+#[async_trait]
+impl Exchange for OrcaExchange {
+    fn new() -> Self {
+        let rpc_client = RpcClient::new(env::var("ORCA_RPC_URL").expect("ORCA_RPC_URL must be set"));
+        let whirlpool_account = Pubkey::from_str(&env::var("ORCA_WHIRLPOOL_PK").expect("ORCA_WHIRLPOOL_PK must be set"))
+            .expect("ORCA_WHIRLPOOL_PK must be a valid public key");
+        OrcaExchange {
+            name: "Orca".to_string(),
+            orderbook: Arc::new(RwLock::new(OrderBook::new())),
+            connection: Arc::new(rpc_client),
+            whirlpool_account,
+            pool: Mutex::new(WhirlpoolState::default()),
+        }
+    }
+
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    async fn start(&self, trading_pair: ETradingPair, update_sender: Sender<ExchangeUpdate>) {
+        let Some(base_mint) = base_mint(&trading_pair) else {
+            eprintln!("[ERROR][Orca] Trading pair {} is not supported", trading_pair);
+            return;
+        };
+        println!("[INFO][Orca] Starting pool subscription...");
+        self.connect_and_listen(&base_mint, &update_sender).await;
+    }
+
+    fn get_order_book(&self) -> Arc<RwLock<OrderBook>> {
+        self.orderbook.clone()
+    }
+}
@@ -0,0 +1,63 @@
+use chrono::Utc;
+use ordered_float::OrderedFloat;
+use tokio::sync::mpsc::Sender;
+use crate::exchange::exchange_update::ExchangeUpdate;
+use crate::exchange::orca::orca_exchange::OrcaExchange;
+use crate::exchange::orca::tick_array::TickArray;
+use crate::exchange::orca::whirlpool::Whirlpool;
+use crate::exchange::order_book::OrderBook;
+use crate::exchange::send_orderbook_update::send_orderbook_update;
+use crate::exchange::solana::account_data::AccountDecodeError;
+
+/// Number of price levels per side
+const POOL_LEVELS: usize = 50;
+
+impl OrcaExchange {
+    /// Store the changed whirlpool account
+    /// Returns true if the price has left the tick array window and the tick arrays must be reloaded
+    pub(crate) async fn process_whirlpool_update(&self, data: &[u8]) -> Result<bool, AccountDecodeError> {
+        let whirlpool = Whirlpool::decode(data)?;
+        let mut pool = self.pool.lock().await;
+        let center = TickArray::start_tick_index_of(whirlpool.tick_current_index, whirlpool.tick_spacing);
+        let is_window_moved = center != pool.tick_arrays_center;
+        pool.whirlpool = Some(whirlpool);
+        Ok(is_window_moved)
+    }
+
+    /// Store the changed tick array account
+    pub(crate) async fn process_tick_array_update(&self, data: &[u8]) -> Result<(), AccountDecodeError> {
+        let tick_array = TickArray::decode(data)?;
+        let mut pool = self.pool.lock().await;
+        // Notifications of unsubscribed tick arrays may still arrive
+        if pool.tick_arrays.contains_key(&tick_array.start_tick_index) {
+            pool.tick_arrays.insert(tick_array.start_tick_index, tick_array);
+        }
+        Ok(())
+    }
+
+    /// Rebuild the order book from the pool liquidity
+    pub(crate) async fn process_pool_update(&self, update_sender: &Sender<ExchangeUpdate>, slot: u64) {
+        let (bids, asks) = self.pool.lock().await.price_levels(POOL_LEVELS);
+        let mut orderbook = self.orderbook.write().await;
+        // Zero slot means the pool has been loaded over HTTP and the order book starts over
+        if slot != 0 && slot < orderbook.sequence {
+            println!("[WARNING][Orca] Skipping outdated update: received slot {}, current slot {}", slot, orderbook.sequence);
+            return;
+        }
+
+        *orderbook = OrderBook::new();
+        orderbook.bids.extend(bids.into_iter().map(|(price, qty)| (OrderedFloat(price), qty)));
+        orderbook.asks.extend(asks.into_iter().map(|(price, qty)| (OrderedFloat(price), qty)));
+        orderbook.sequence = slot;
+        orderbook.last_updated = Utc::now();
+
+        // *** Notify subscribers about the updated orderbook ***
+        send_orderbook_update(
+            update_sender,
+            &self.name,
+            &orderbook.get_best_bid(),
+            &orderbook.get_best_ask(),
+            orderbook.last_updated,
+        ).await;
+    }
+}
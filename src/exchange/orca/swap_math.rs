@@ -0,0 +1,166 @@
+use ethnum::U256;
+
+/// Fee rates are expressed in hundredths of a basis point
+pub const FEE_RATE_DENOMINATOR: u128 = 1_000_000;
+
+/// Result of a swap within a range of constant liquidity
+#[derive(Debug, Clone, PartialEq)]
+pub struct SwapStep {
+    /// Input amount without the fee
+    pub amount_in: u128,
+    pub amount_out: u128,
+    pub fee_amount: u128,
+    pub next_sqrt_price: u128,
+}
+
+/// Amount of token A between two square root prices: `L * (sqrt_1 - sqrt_0) / (sqrt_1 * sqrt_0)`
+pub fn amount_delta_a(sqrt_price_0: u128, sqrt_price_1: u128, liquidity: u128, round_up: bool) -> u128 {
+    let (lower, upper) = (sqrt_price_0.min(sqrt_price_1), sqrt_price_0.max(sqrt_price_1));
+    if lower == 0 {
+        return 0;
+    }
+    let numerator = (U256::from(liquidity) * U256::from(upper - lower)) << 64;
+    let denominator = U256::from(upper) * U256::from(lower);
+    to_u128(div(numerator, denominator, round_up))
+}
+
+/// Amount of token B between two square root prices: `L * (sqrt_1 - sqrt_0)`
+pub fn amount_delta_b(sqrt_price_0: u128, sqrt_price_1: u128, liquidity: u128, round_up: bool) -> u128 {
+    let (lower, upper) = (sqrt_price_0.min(sqrt_price_1), sqrt_price_0.max(sqrt_price_1));
+    let product = U256::from(liquidity) * U256::from(upper - lower);
+    to_u128(div(product, U256::ONE << 64, round_up))
+}
+
+/// Square root price after adding token A (the price goes down), rounded up
+pub fn next_sqrt_price_from_a_input(sqrt_price: u128, liquidity: u128, amount: u128) -> u128 {
+    if amount == 0 {
+        return sqrt_price;
+    }
+    let liquidity_shifted = U256::from(liquidity) << 64;
+    let numerator = liquidity_shifted * U256::from(sqrt_price);
+    let denominator = liquidity_shifted + U256::from(amount) * U256::from(sqrt_price);
+    to_u128(div(numerator, denominator, true))
+}
+
+/// Square root price after adding token B (the price goes up), rounded down
+pub fn next_sqrt_price_from_b_input(sqrt_price: u128, liquidity: u128, amount: u128) -> u128 {
+    if liquidity == 0 {
+        return sqrt_price;
+    }
+    let delta = (U256::from(amount) << 64) / U256::from(liquidity);
+    to_u128(U256::from(sqrt_price) + delta)
+}
+
+/// Swap an exact input amount (including the fee) towards the target square root price
+pub fn compute_swap_step(
+    amount_remaining: u128,
+    fee_rate: u16,
+    liquidity: u128,
+    sqrt_price_current: u128,
+    sqrt_price_target: u128,
+    a_to_b: bool,
+) -> SwapStep {
+    let fee_rate = fee_rate as u128;
+    let amount_less_fee = to_u128(
+        U256::from(amount_remaining) * U256::from(FEE_RATE_DENOMINATOR - fee_rate) / U256::from(FEE_RATE_DENOMINATOR)
+    );
+    let amount_to_target = if a_to_b {
+        amount_delta_a(sqrt_price_target, sqrt_price_current, liquidity, true)
+    } else {
+        amount_delta_b(sqrt_price_current, sqrt_price_target, liquidity, true)
+    };
+
+    let (amount_in, next_sqrt_price, fee_amount) = if amount_less_fee >= amount_to_target {
+        let fee_amount = to_u128(div(
+            U256::from(amount_to_target) * U256::from(fee_rate),
+            U256::from(FEE_RATE_DENOMINATOR - fee_rate),
+            true,
+        ));
+        (amount_to_target, sqrt_price_target, fee_amount)
+    } else {
+        let next_sqrt_price = if a_to_b {
+            next_sqrt_price_from_a_input(sqrt_price_current, liquidity, amount_less_fee)
+        } else {
+            next_sqrt_price_from_b_input(sqrt_price_current, liquidity, amount_less_fee)
+        };
+        (amount_less_fee, next_sqrt_price, amount_remaining - amount_less_fee)
+    };
+
+    let amount_out = if a_to_b {
+        amount_delta_b(next_sqrt_price, sqrt_price_current, liquidity, false)
+    } else {
+        amount_delta_a(sqrt_price_current, next_sqrt_price, liquidity, false)
+    };
+
+    SwapStep {
+        amount_in,
+        amount_out,
+        fee_amount,
+        next_sqrt_price,
+    }
+}
+
+fn div(numerator: U256, denominator: U256, round_up: bool) -> U256 {
+    let quotient = numerator / denominator;
+    if round_up && numerator % denominator != U256::ZERO {
+        quotient + 1
+    } else {
+        quotient
+    }
+}
+
+fn to_u128(value: U256) -> u128 {
+    if value > U256::from(u128::MAX) {
+        u128::MAX
+    } else {
+        value.as_u128()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::exchange::orca::tick_math::sqrt_price_from_tick_index;
+    use super::*;
+
+    const LIQUIDITY: u128 = 1_000_000_000_000;
+
+    #[test]
+    fn test_amount_deltas() {
+        let sqrt_price_0 = sqrt_price_from_tick_index(0);
+        let sqrt_price_1 = sqrt_price_from_tick_index(100);
+
+        // L * (1.0001^50 - 1) and L * (1 - 1.0001^-50)
+        let delta_b = amount_delta_b(sqrt_price_0, sqrt_price_1, LIQUIDITY, false);
+        let delta_a = amount_delta_a(sqrt_price_0, sqrt_price_1, LIQUIDITY, false);
+        assert_eq!(delta_b, 5_012_269_623);
+        assert_eq!(delta_a, 4_987_272_070);
+        assert_eq!(amount_delta_b(sqrt_price_0, sqrt_price_1, LIQUIDITY, true), delta_b + 1);
+    }
+
+    #[test]
+    fn test_swap_step_within_range() {
+        let sqrt_price = sqrt_price_from_tick_index(0);
+        let target = sqrt_price_from_tick_index(-100);
+
+        let step = compute_swap_step(1_000_000, 3000, LIQUIDITY, sqrt_price, target, true);
+
+        assert_eq!(step.amount_in + step.fee_amount, 1_000_000);
+        assert_eq!(step.fee_amount, 3_000);
+        assert!(step.next_sqrt_price < sqrt_price && step.next_sqrt_price > target);
+        // Almost no price impact at this liquidity
+        assert!(step.amount_out <= step.amount_in && step.amount_out >= step.amount_in - 2);
+    }
+
+    #[test]
+    fn test_swap_step_reaches_target() {
+        let sqrt_price = sqrt_price_from_tick_index(0);
+        let target = sqrt_price_from_tick_index(100);
+
+        let step = compute_swap_step(u64::MAX as u128, 3000, LIQUIDITY, sqrt_price, target, false);
+
+        assert_eq!(step.next_sqrt_price, target);
+        assert_eq!(step.amount_in, amount_delta_b(sqrt_price, target, LIQUIDITY, true));
+        assert_eq!(step.amount_out, amount_delta_a(sqrt_price, target, LIQUIDITY, false));
+        assert_eq!(step.fee_amount, (step.amount_in * 3000).div_ceil(997_000));
+    }
+}
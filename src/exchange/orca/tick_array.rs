@@ -0,0 +1,89 @@
+use solana_sdk::pubkey::Pubkey;
+use crate::exchange::solana::account_data::{check_size, read_i128, read_i32, read_u8, AccountDecodeError};
+
+/// Number of ticks in a tick array
+pub const TICK_ARRAY_SIZE: i32 = 88;
+
+const START_TICK_INDEX_OFFSET: usize = 8;
+const TICKS_OFFSET: usize = 12;
+const TICK_SIZE: usize = 113;
+const TICK_LIQUIDITY_NET_OFFSET: usize = 1;
+/// Size of the `TickArray` account: ticks are followed by the whirlpool address
+pub const TICK_ARRAY_ACCOUNT_SIZE: usize = TICKS_OFFSET + TICK_ARRAY_SIZE as usize * TICK_SIZE + 32;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Tick {
+    pub initialized: bool,
+    /// Liquidity added when the price crosses the tick upwards, removed when it crosses downwards
+    pub liquidity_net: i128,
+}
+
+/// A fixed range of `TICK_ARRAY_SIZE * tick_spacing` ticks of a pool
+#[derive(Clone, Debug, PartialEq)]
+pub struct TickArray {
+    pub start_tick_index: i32,
+    pub ticks: Vec<Tick>,
+}
+
+impl TickArray {
+    pub fn decode(data: &[u8]) -> Result<Self, AccountDecodeError> {
+        check_size(data, TICK_ARRAY_ACCOUNT_SIZE)?;
+        let ticks = (0..TICK_ARRAY_SIZE as usize)
+            .map(|i| {
+                let offset = TICKS_OFFSET + i * TICK_SIZE;
+                Tick {
+                    initialized: read_u8(data, offset) != 0,
+                    liquidity_net: read_i128(data, offset + TICK_LIQUIDITY_NET_OFFSET),
+                }
+            })
+            .collect();
+        Ok(TickArray {
+            start_tick_index: read_i32(data, START_TICK_INDEX_OFFSET),
+            ticks,
+        })
+    }
+
+    /// Start tick index of the tick array containing the tick
+    pub fn start_tick_index_of(tick_index: i32, tick_spacing: u16) -> i32 {
+        let ticks_in_array = TICK_ARRAY_SIZE * tick_spacing as i32;
+        tick_index.div_euclid(ticks_in_array) * ticks_in_array
+    }
+
+    /// Address of the tick array: `["tick_array", whirlpool, start tick index as a decimal string]`
+    pub fn address(program_id: &Pubkey, whirlpool: &Pubkey, start_tick_index: i32) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"tick_array", whirlpool.as_ref(), start_tick_index.to_string().as_bytes()],
+            program_id,
+        ).0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_start_tick_index_of() {
+        assert_eq!(TickArray::start_tick_index_of(0, 64), 0);
+        assert_eq!(TickArray::start_tick_index_of(5631, 64), 0);
+        assert_eq!(TickArray::start_tick_index_of(5632, 64), 5632);
+        assert_eq!(TickArray::start_tick_index_of(-1, 64), -5632);
+        assert_eq!(TickArray::start_tick_index_of(-20000, 4), -20064);
+    }
+
+    #[test]
+    fn test_decode_tick_array() {
+        let mut data = vec![0u8; TICK_ARRAY_ACCOUNT_SIZE];
+        data[START_TICK_INDEX_OFFSET..START_TICK_INDEX_OFFSET + 4].copy_from_slice(&(-5632i32).to_le_bytes());
+        let offset = TICKS_OFFSET + 3 * TICK_SIZE;
+        data[offset] = 1;
+        data[offset + 1..offset + 17].copy_from_slice(&(-42i128).to_le_bytes());
+
+        let tick_array = TickArray::decode(&data).unwrap();
+
+        assert_eq!(tick_array.start_tick_index, -5632);
+        assert_eq!(tick_array.ticks.len(), TICK_ARRAY_SIZE as usize);
+        assert_eq!(tick_array.ticks[3], Tick { initialized: true, liquidity_net: -42 });
+        assert!(!tick_array.ticks[4].initialized);
+    }
+}
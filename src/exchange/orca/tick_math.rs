@@ -0,0 +1,123 @@
+use ethnum::U256;
+
+pub const MIN_TICK_INDEX: i32 = -443636;
+pub const MAX_TICK_INDEX: i32 = 443636;
+/// Square root prices of the tick bounds in Q64.64, as defined by the Whirlpool program
+pub const MIN_SQRT_PRICE_X64: u128 = 4295048016;
+pub const MAX_SQRT_PRICE_X64: u128 = 79226673515401279992447579055;
+
+/// `1.0001^(2^i / 2)` in Q32.96 for the bits 1 to 18 of a positive tick index
+const POSITIVE_TICK_RATIOS: [u128; 18] = [
+    79236085330515764027303304731,
+    79244008939048815603706035061,
+    79259858533276714757314932305,
+    79291567232598584799939703904,
+    79355022692464371645785046466,
+    79482085999252804386437311141,
+    79736823300114093921829183326,
+    80248749790819932309965073892,
+    81282483887344747381513967011,
+    83390072131320151908154831281,
+    87770609709833776024991924138,
+    97234110755111693312479820773,
+    119332217159966728226237229890,
+    179736315981702064433883588727,
+    407748233172238350107850275304,
+    2098478828474011932436660412517,
+    55581415166113811149459800483533,
+    38992368544603139932233054999993551,
+];
+
+/// `1.0001^(-2^i / 2)` in Q64.64 for the bits 1 to 18 of the absolute value of a negative tick index
+const NEGATIVE_TICK_RATIOS: [u128; 18] = [
+    18444899583751176498,
+    18443055278223354162,
+    18439367220385604838,
+    18431993317065449817,
+    18417254355718160513,
+    18387811781193591352,
+    18329067761203520168,
+    18212142134806087854,
+    17980523815641551639,
+    17526086738831147013,
+    16651378430235024244,
+    15030750278693429944,
+    12247334978882834399,
+    8131365268884726200,
+    3584323654723342297,
+    696457651847595233,
+    26294789957452057,
+    37481735321082,
+];
+
+/// Square root price `sqrt(1.0001^tick)` in Q64.64
+/// Port of the Whirlpool program tick math: positive ticks multiply Q32.96 ratios and drop 32 bits,
+/// negative ticks multiply Q64.64 ratios, each product being truncated like on-chain
+pub fn sqrt_price_from_tick_index(tick: i32) -> u128 {
+    let tick = tick.clamp(MIN_TICK_INDEX, MAX_TICK_INDEX);
+    if tick >= 0 {
+        sqrt_price_positive_tick(tick as u32)
+    } else {
+        sqrt_price_negative_tick(tick.unsigned_abs())
+    }
+}
+
+fn sqrt_price_positive_tick(tick: u32) -> u128 {
+    let mut ratio: u128 = if tick & 1 != 0 {
+        79232123823359799118286999567
+    } else {
+        79228162514264337593543950336
+    };
+    for (bit, tick_ratio) in POSITIVE_TICK_RATIOS.iter().enumerate() {
+        if tick & (2 << bit) != 0 {
+            ratio = ((U256::from(ratio) * U256::from(*tick_ratio)) >> 96u32).as_u128();
+        }
+    }
+    ratio >> 32
+}
+
+fn sqrt_price_negative_tick(abs_tick: u32) -> u128 {
+    let mut ratio: u128 = if abs_tick & 1 != 0 {
+        18445821805675392311
+    } else {
+        18446744073709551616
+    };
+    for (bit, tick_ratio) in NEGATIVE_TICK_RATIOS.iter().enumerate() {
+        if abs_tick & (2 << bit) != 0 {
+            ratio = (ratio * tick_ratio) >> 64;
+        }
+    }
+    ratio
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sqrt_price_to_price(sqrt_price: u128) -> f64 {
+        let sqrt_price = sqrt_price as f64 / 2f64.powi(64);
+        sqrt_price * sqrt_price
+    }
+
+    #[test]
+    fn test_sqrt_price_from_tick_index() {
+        assert_eq!(sqrt_price_from_tick_index(0), 1u128 << 64);
+        assert_eq!(sqrt_price_from_tick_index(1), 18447666387855959850);
+        assert_eq!(sqrt_price_from_tick_index(-1), 18445821805675392311);
+        assert_eq!(sqrt_price_from_tick_index(12345), 34195943348800206620);
+        assert_eq!(sqrt_price_from_tick_index(-12345), 9950957148631419635);
+        assert_eq!(sqrt_price_from_tick_index(300000), 60257519765924248467716150);
+        assert_eq!(sqrt_price_from_tick_index(-300001), 5646852963751);
+        assert_eq!(sqrt_price_from_tick_index(MIN_TICK_INDEX), MIN_SQRT_PRICE_X64);
+        assert_eq!(sqrt_price_from_tick_index(MAX_TICK_INDEX), MAX_SQRT_PRICE_X64);
+    }
+
+    #[test]
+    fn test_sqrt_price_matches_float_approximation() {
+        for tick in [-20000, -12345, -100, 100, 12345, 20000] {
+            let expected = 1.0001f64.powi(tick);
+            let price = sqrt_price_to_price(sqrt_price_from_tick_index(tick));
+            assert!((price - expected).abs() / expected < 1e-12, "tick {}: {} != {}", tick, price, expected);
+        }
+    }
+}
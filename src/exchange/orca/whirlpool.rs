@@ -0,0 +1,78 @@
+use solana_sdk::pubkey::Pubkey;
+use crate::exchange::solana::account_data::{check_size, read_i32, read_pubkey, read_u128, read_u16, AccountDecodeError};
+
+/// Size of the Orca `Whirlpool` account including the 8 bytes of the account discriminator
+pub const WHIRLPOOL_SIZE: usize = 653;
+
+const TICK_SPACING_OFFSET: usize = 41;
+const FEE_RATE_OFFSET: usize = 45;
+const LIQUIDITY_OFFSET: usize = 49;
+const SQRT_PRICE_OFFSET: usize = 65;
+const TICK_CURRENT_INDEX_OFFSET: usize = 81;
+const TOKEN_MINT_A_OFFSET: usize = 101;
+const TOKEN_VAULT_A_OFFSET: usize = 133;
+const TOKEN_MINT_B_OFFSET: usize = 181;
+const TOKEN_VAULT_B_OFFSET: usize = 213;
+
+/// Fields of the Whirlpool state required for quoting
+#[derive(Clone, Debug, PartialEq)]
+pub struct Whirlpool {
+    pub tick_spacing: u16,
+    /// Swap fee in hundredths of a basis point
+    pub fee_rate: u16,
+    /// Liquidity of the current tick range
+    pub liquidity: u128,
+    /// Square root of the price (token B per token A) in Q64.64
+    pub sqrt_price: u128,
+    pub tick_current_index: i32,
+    pub token_mint_a: Pubkey,
+    pub token_vault_a: Pubkey,
+    pub token_mint_b: Pubkey,
+    pub token_vault_b: Pubkey,
+}
+
+impl Whirlpool {
+    pub fn decode(data: &[u8]) -> Result<Self, AccountDecodeError> {
+        check_size(data, WHIRLPOOL_SIZE)?;
+        Ok(Whirlpool {
+            tick_spacing: read_u16(data, TICK_SPACING_OFFSET),
+            fee_rate: read_u16(data, FEE_RATE_OFFSET),
+            liquidity: read_u128(data, LIQUIDITY_OFFSET),
+            sqrt_price: read_u128(data, SQRT_PRICE_OFFSET),
+            tick_current_index: read_i32(data, TICK_CURRENT_INDEX_OFFSET),
+            token_mint_a: read_pubkey(data, TOKEN_MINT_A_OFFSET),
+            token_vault_a: read_pubkey(data, TOKEN_VAULT_A_OFFSET),
+            token_mint_b: read_pubkey(data, TOKEN_MINT_B_OFFSET),
+            token_vault_b: read_pubkey(data, TOKEN_VAULT_B_OFFSET),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_whirlpool() {
+        let token_mint_a = Pubkey::new_unique();
+        let token_mint_b = Pubkey::new_unique();
+        let mut data = vec![0u8; WHIRLPOOL_SIZE];
+        data[TICK_SPACING_OFFSET..TICK_SPACING_OFFSET + 2].copy_from_slice(&64u16.to_le_bytes());
+        data[FEE_RATE_OFFSET..FEE_RATE_OFFSET + 2].copy_from_slice(&3000u16.to_le_bytes());
+        data[LIQUIDITY_OFFSET..LIQUIDITY_OFFSET + 16].copy_from_slice(&123_456_789u128.to_le_bytes());
+        data[SQRT_PRICE_OFFSET..SQRT_PRICE_OFFSET + 16].copy_from_slice(&(1u128 << 64).to_le_bytes());
+        data[TICK_CURRENT_INDEX_OFFSET..TICK_CURRENT_INDEX_OFFSET + 4].copy_from_slice(&(-20000i32).to_le_bytes());
+        data[TOKEN_MINT_A_OFFSET..TOKEN_MINT_A_OFFSET + 32].copy_from_slice(token_mint_a.as_ref());
+        data[TOKEN_MINT_B_OFFSET..TOKEN_MINT_B_OFFSET + 32].copy_from_slice(token_mint_b.as_ref());
+
+        let whirlpool = Whirlpool::decode(&data).unwrap();
+
+        assert_eq!(whirlpool.tick_spacing, 64);
+        assert_eq!(whirlpool.fee_rate, 3000);
+        assert_eq!(whirlpool.liquidity, 123_456_789);
+        assert_eq!(whirlpool.sqrt_price, 1u128 << 64);
+        assert_eq!(whirlpool.tick_current_index, -20000);
+        assert_eq!(whirlpool.token_mint_a, token_mint_a);
+        assert_eq!(whirlpool.token_mint_b, token_mint_b);
+    }
+}
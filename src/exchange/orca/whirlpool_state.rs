@@ -0,0 +1,244 @@
+use std::collections::BTreeMap;
+use crate::exchange::orca::swap_math::{compute_swap_step, SwapStep};
+use crate::exchange::orca::tick_array::{Tick, TickArray, TICK_ARRAY_SIZE};
+use crate::exchange::orca::tick_math::{sqrt_price_from_tick_index, MAX_SQRT_PRICE_X64, MIN_SQRT_PRICE_X64};
+use crate::exchange::orca::whirlpool::Whirlpool;
+
+/// Price level of the order book built from the pool: price and base volume
+pub type TPoolLevel = (f64, f64);
+
+/// Latest known state of the pool and the tick arrays around its current price
+#[derive(Default)]
+pub struct WhirlpoolState {
+    pub whirlpool: Option<Whirlpool>,
+    /// Loaded tick arrays by their start tick index
+    pub tick_arrays: BTreeMap<i32, TickArray>,
+    /// Start tick index of the tick array in the middle of the loaded ones
+    pub tick_arrays_center: i32,
+    pub decimals_a: u8,
+    pub decimals_b: u8,
+    /// True if token A is the quote currency of the trading pair
+    pub inverted: bool,
+}
+
+impl WhirlpoolState {
+    /// Split the liquidity into price levels between consecutive initializable ticks, up to `max_levels` per side
+    /// Each level is what a swap through its tick range gives, so walking the levels is exact
+    /// Returns bids in descending and asks in ascending price order of the trading pair
+    pub fn price_levels(&self, max_levels: usize) -> (Vec<TPoolLevel>, Vec<TPoolLevel>) {
+        let scale_a = 10f64.powi(self.decimals_a as i32);
+        let scale_b = 10f64.powi(self.decimals_b as i32);
+
+        // *** Input (with fee) and output of every tick range, in UI units ***
+        let mut a_to_b = Vec::with_capacity(max_levels);
+        self.walk(u64::MAX as u128, true, true, max_levels, |step| {
+            a_to_b.push(((step.amount_in + step.fee_amount) as f64 / scale_a, step.amount_out as f64 / scale_b));
+        });
+        let mut b_to_a = Vec::with_capacity(max_levels);
+        self.walk(u64::MAX as u128, false, true, max_levels, |step| {
+            b_to_a.push(((step.amount_in + step.fee_amount) as f64 / scale_b, step.amount_out as f64 / scale_a));
+        });
+
+        // *** Selling the base currency gives bids, buying it gives asks ***
+        let (sell_base, buy_base) = if self.inverted { (b_to_a, a_to_b) } else { (a_to_b, b_to_a) };
+        let bids = sell_base.into_iter()
+            .filter(|(amount_in, amount_out)| *amount_in > 0.0 && *amount_out > 0.0)
+            .map(|(amount_in, amount_out)| (amount_out / amount_in, amount_in))
+            .collect();
+        let asks = buy_base.into_iter()
+            .filter(|(amount_in, amount_out)| *amount_in > 0.0 && *amount_out > 0.0)
+            .map(|(amount_in, amount_out)| (amount_in / amount_out, amount_out))
+            .collect();
+        (bids, asks)
+    }
+
+    /// Swap from the current price the way the on-chain program does, until the input is spent, the loaded
+    /// tick arrays end or `max_steps` non-empty steps have been made. Steps end at initialized ticks and tick
+    /// array boundaries, where the liquidity changes, and with `every_tick` also at uninitialized ticks
+    fn walk(
+        &self,
+        amount_in: u128,
+        a_to_b: bool,
+        every_tick: bool,
+        max_steps: usize,
+        mut on_step: impl FnMut(&SwapStep),
+    ) {
+        let Some(whirlpool) = self.whirlpool.as_ref() else {
+            return;
+        };
+        let mut remaining = amount_in;
+        let mut sqrt_price = whirlpool.sqrt_price;
+        let mut tick_current = whirlpool.tick_current_index;
+        let mut liquidity = whirlpool.liquidity;
+        let mut steps = 0;
+        // Default price limit of the program, a swap never goes past the tick bounds
+        let sqrt_price_limit = if a_to_b { MIN_SQRT_PRICE_X64 } else { MAX_SQRT_PRICE_X64 };
+
+        while remaining > 0 && steps < max_steps && sqrt_price != sqrt_price_limit {
+            let Some((next_tick, tick)) = self.next_step_tick(whirlpool.tick_spacing, tick_current, a_to_b, every_tick) else {
+                break;
+            };
+            let tick_sqrt_price = sqrt_price_from_tick_index(next_tick);
+            let sqrt_price_target = if a_to_b { tick_sqrt_price.max(sqrt_price_limit) } else { tick_sqrt_price.min(sqrt_price_limit) };
+            let step = compute_swap_step(remaining, whirlpool.fee_rate, liquidity, sqrt_price, sqrt_price_target, a_to_b);
+            remaining -= step.amount_in + step.fee_amount;
+            if step.amount_in > 0 {
+                on_step(&step);
+                steps += 1;
+            }
+
+            if step.next_sqrt_price == tick_sqrt_price {
+                if tick.initialized {
+                    let liquidity_net = if a_to_b { -tick.liquidity_net } else { tick.liquidity_net };
+                    let Some(next_liquidity) = liquidity.checked_add_signed(liquidity_net) else {
+                        eprintln!("[ERROR][Orca] Invalid liquidity net at tick {}", next_tick);
+                        break;
+                    };
+                    liquidity = next_liquidity;
+                }
+                tick_current = if a_to_b { next_tick - 1 } else { next_tick };
+            }
+            sqrt_price = step.next_sqrt_price;
+        }
+    }
+
+    /// The tick where the next swap step ends: the next initialized tick, the next tick array boundary
+    /// or, with `every_tick`, the next initializable tick. None if the tick array is not loaded
+    fn next_step_tick(&self, tick_spacing: u16, tick_current: i32, a_to_b: bool, every_tick: bool) -> Option<(i32, &Tick)> {
+        let tick_spacing = tick_spacing as i32;
+        let ticks_in_array = TICK_ARRAY_SIZE * tick_spacing;
+        let mut tick_index = tick_current.div_euclid(tick_spacing) * tick_spacing;
+        if !a_to_b {
+            tick_index += tick_spacing;
+        }
+        loop {
+            let tick = self.tick(tick_index, tick_spacing)?;
+            if tick.initialized || every_tick || tick_index.rem_euclid(ticks_in_array) == 0 {
+                return Some((tick_index, tick));
+            }
+            tick_index += if a_to_b { -tick_spacing } else { tick_spacing };
+        }
+    }
+
+    fn tick(&self, tick_index: i32, tick_spacing: i32) -> Option<&Tick> {
+        let start_tick_index = TickArray::start_tick_index_of(tick_index, tick_spacing as u16);
+        let tick_array = self.tick_arrays.get(&start_tick_index)?;
+        tick_array.ticks.get(((tick_index - start_tick_index) / tick_spacing) as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_sdk::pubkey::Pubkey;
+    use super::*;
+
+    const TICK_SPACING: u16 = 64;
+    const LIQUIDITY: u128 = 10_000_000_000_000;
+    const FEE_RATE: u16 = 3000;
+
+    /// Pool at tick 0 with half of the liquidity ending at tick 128 and another half at tick -128
+    fn state() -> WhirlpoolState {
+        let ticks_in_array = TICK_ARRAY_SIZE * TICK_SPACING as i32;
+        let mut tick_arrays = BTreeMap::new();
+        for start_tick_index in [-ticks_in_array, 0, ticks_in_array] {
+            tick_arrays.insert(start_tick_index, TickArray {
+                start_tick_index,
+                ticks: vec![Tick::default(); TICK_ARRAY_SIZE as usize],
+            });
+        }
+        tick_arrays.get_mut(&0).unwrap().ticks[2] = Tick { initialized: true, liquidity_net: -(LIQUIDITY as i128) / 2 };
+        tick_arrays.get_mut(&-ticks_in_array).unwrap().ticks[86] = Tick { initialized: true, liquidity_net: LIQUIDITY as i128 / 2 };
+
+        WhirlpoolState {
+            whirlpool: Some(Whirlpool {
+                tick_spacing: TICK_SPACING,
+                fee_rate: FEE_RATE,
+                liquidity: LIQUIDITY,
+                sqrt_price: sqrt_price_from_tick_index(0),
+                tick_current_index: 0,
+                token_mint_a: Pubkey::new_unique(),
+                token_vault_a: Pubkey::new_unique(),
+                token_mint_b: Pubkey::new_unique(),
+                token_vault_b: Pubkey::new_unique(),
+            }),
+            tick_arrays,
+            tick_arrays_center: 0,
+            decimals_a: 6,
+            decimals_b: 6,
+            inverted: false,
+        }
+    }
+
+    /// Steps of a swap ending only at initialized ticks and tick array boundaries
+    fn swap_steps(state: &WhirlpoolState, amount_in: u128, a_to_b: bool) -> Vec<SwapStep> {
+        let mut steps = Vec::new();
+        state.walk(amount_in, a_to_b, false, usize::MAX, |step| steps.push(step.clone()));
+        steps
+    }
+
+    #[test]
+    fn test_swap_within_range() {
+        let state = state();
+
+        let steps = swap_steps(&state, 1_000_000, false);
+
+        let step = compute_swap_step(1_000_000, FEE_RATE, LIQUIDITY, sqrt_price_from_tick_index(0), sqrt_price_from_tick_index(128), false);
+        assert_eq!(steps, vec![step]);
+    }
+
+    #[test]
+    fn test_swap_crosses_initialized_tick() {
+        let state = state();
+        let amount_in = 100_000_000_000;
+
+        let steps = swap_steps(&state, amount_in, false);
+
+        // Full liquidity up to tick 128, then half of it up to the next tick array
+        let first = compute_swap_step(amount_in, FEE_RATE, LIQUIDITY, sqrt_price_from_tick_index(0), sqrt_price_from_tick_index(128), false);
+        assert_eq!(first.next_sqrt_price, sqrt_price_from_tick_index(128));
+        let remaining = amount_in - first.amount_in - first.fee_amount;
+        let second = compute_swap_step(remaining, FEE_RATE, LIQUIDITY / 2, first.next_sqrt_price, sqrt_price_from_tick_index(5632), false);
+        assert_eq!(steps, vec![first, second]);
+    }
+
+    #[test]
+    fn test_swap_stops_at_loaded_tick_arrays() {
+        let state = state();
+
+        let steps = swap_steps(&state, u64::MAX as u128, true);
+
+        let spent: u128 = steps.iter().map(|step| step.amount_in + step.fee_amount).sum();
+        assert!(spent < u64::MAX as u128);
+        assert_eq!(steps.last().unwrap().next_sqrt_price, sqrt_price_from_tick_index(-5632));
+    }
+
+    #[test]
+    fn test_price_levels() {
+        let state = state();
+
+        let (bids, asks) = state.price_levels(4);
+
+        assert_eq!(bids.len(), 4);
+        assert_eq!(asks.len(), 4);
+        assert!(bids[0].0 < 1.0 && asks[0].0 > 1.0);
+        assert!(bids.windows(2).all(|pair| pair[0].0 > pair[1].0));
+        assert!(asks.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        // The liquidity halves beyond ticks 128 and -128
+        assert!((asks[2].1 / asks[1].1 - 0.5).abs() < 0.01);
+        assert!((bids[2].1 / bids[1].1 - 0.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_price_levels_inverted() {
+        let mut state = state();
+        state.inverted = true;
+        state.decimals_a = 6;
+        state.decimals_b = 9;
+
+        let (bids, asks) = state.price_levels(1);
+
+        // One token B is worth 1000 tokens A in UI units
+        assert!(bids[0].0 < 1000.0 && bids[0].0 > 990.0);
+        assert!(asks[0].0 > 1000.0 && asks[0].0 < 1010.0);
+    }
+}
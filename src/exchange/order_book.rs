@@ -14,6 +14,8 @@ pub struct OrderBook {
     pub last_updated: DateTime<Utc>,
     /// Cross sequence
     pub sequence: u64,
+    /// False while the book relies on state which may still be rolled back, e.g. processed Solana slots
+    pub confirmed: bool,
 }
 
 impl OrderBook {
@@ -23,6 +25,7 @@ impl OrderBook {
             asks: BTreeMap::new(),
             last_updated: Utc::now(),
             sequence: 0,
+            confirmed: true,
         }
     }
 
@@ -0,0 +1,108 @@
+use solana_sdk::pubkey::Pubkey;
+use crate::exchange::solana::account_data::{check_size, read_pubkey, read_u64, AccountDecodeError};
+
+/// Size of the Raydium AMM v4 `AmmInfo` account
+pub const AMM_INFO_SIZE: usize = 752;
+
+const COIN_DECIMALS_OFFSET: usize = 32;
+const PC_DECIMALS_OFFSET: usize = 40;
+const SWAP_FEE_NUMERATOR_OFFSET: usize = 176;
+const SWAP_FEE_DENOMINATOR_OFFSET: usize = 184;
+const NEED_TAKE_PNL_COIN_OFFSET: usize = 192;
+const NEED_TAKE_PNL_PC_OFFSET: usize = 200;
+const COIN_VAULT_OFFSET: usize = 336;
+const PC_VAULT_OFFSET: usize = 368;
+const COIN_MINT_OFFSET: usize = 400;
+const PC_MINT_OFFSET: usize = 432;
+
+/// Fields of the Raydium AMM v4 pool state required for quoting
+/// "Coin" is the base token and "pc" the quote token of the pool
+#[derive(Clone, Debug, PartialEq)]
+pub struct AmmInfo {
+    pub coin_decimals: u64,
+    pub pc_decimals: u64,
+    pub swap_fee_numerator: u64,
+    pub swap_fee_denominator: u64,
+    /// Coin tokens in the vault which belong to the protocol, not to the pool
+    pub need_take_pnl_coin: u64,
+    /// Pc tokens in the vault which belong to the protocol, not to the pool
+    pub need_take_pnl_pc: u64,
+    pub coin_vault: Pubkey,
+    pub pc_vault: Pubkey,
+    pub coin_mint: Pubkey,
+    pub pc_mint: Pubkey,
+}
+
+impl AmmInfo {
+    pub fn decode(data: &[u8]) -> Result<Self, AccountDecodeError> {
+        check_size(data, AMM_INFO_SIZE)?;
+        Ok(AmmInfo {
+            coin_decimals: read_u64(data, COIN_DECIMALS_OFFSET),
+            pc_decimals: read_u64(data, PC_DECIMALS_OFFSET),
+            swap_fee_numerator: read_u64(data, SWAP_FEE_NUMERATOR_OFFSET),
+            swap_fee_denominator: read_u64(data, SWAP_FEE_DENOMINATOR_OFFSET),
+            need_take_pnl_coin: read_u64(data, NEED_TAKE_PNL_COIN_OFFSET),
+            need_take_pnl_pc: read_u64(data, NEED_TAKE_PNL_PC_OFFSET),
+            coin_vault: read_pubkey(data, COIN_VAULT_OFFSET),
+            pc_vault: read_pubkey(data, PC_VAULT_OFFSET),
+            coin_mint: read_pubkey(data, COIN_MINT_OFFSET),
+            pc_mint: read_pubkey(data, PC_MINT_OFFSET),
+        })
+    }
+
+    /// Swap fee charged on the input amount
+    pub fn fee_rate(&self) -> f64 {
+        if self.swap_fee_denominator == 0 {
+            return 0.0;
+        }
+        self.swap_fee_numerator as f64 / self.swap_fee_denominator as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_u64(data: &mut [u8], offset: usize, value: u64) {
+        data[offset..offset + 8].copy_from_slice(&value.to_le_bytes());
+    }
+
+    #[test]
+    fn test_decode_amm_info() {
+        let coin_vault = Pubkey::new_unique();
+        let pc_vault = Pubkey::new_unique();
+        let coin_mint = Pubkey::new_unique();
+        let pc_mint = Pubkey::new_unique();
+        let mut data = vec![0u8; AMM_INFO_SIZE];
+        write_u64(&mut data, COIN_DECIMALS_OFFSET, 9);
+        write_u64(&mut data, PC_DECIMALS_OFFSET, 6);
+        write_u64(&mut data, SWAP_FEE_NUMERATOR_OFFSET, 25);
+        write_u64(&mut data, SWAP_FEE_DENOMINATOR_OFFSET, 10_000);
+        write_u64(&mut data, NEED_TAKE_PNL_COIN_OFFSET, 1_000);
+        write_u64(&mut data, NEED_TAKE_PNL_PC_OFFSET, 2_000);
+        data[COIN_VAULT_OFFSET..COIN_VAULT_OFFSET + 32].copy_from_slice(coin_vault.as_ref());
+        data[PC_VAULT_OFFSET..PC_VAULT_OFFSET + 32].copy_from_slice(pc_vault.as_ref());
+        data[COIN_MINT_OFFSET..COIN_MINT_OFFSET + 32].copy_from_slice(coin_mint.as_ref());
+        data[PC_MINT_OFFSET..PC_MINT_OFFSET + 32].copy_from_slice(pc_mint.as_ref());
+
+        let amm_info = AmmInfo::decode(&data).unwrap();
+
+        assert_eq!(amm_info.coin_decimals, 9);
+        assert_eq!(amm_info.pc_decimals, 6);
+        assert_eq!(amm_info.fee_rate(), 0.0025);
+        assert_eq!(amm_info.need_take_pnl_coin, 1_000);
+        assert_eq!(amm_info.need_take_pnl_pc, 2_000);
+        assert_eq!(amm_info.coin_vault, coin_vault);
+        assert_eq!(amm_info.pc_vault, pc_vault);
+        assert_eq!(amm_info.coin_mint, coin_mint);
+        assert_eq!(amm_info.pc_mint, pc_mint);
+    }
+
+    #[test]
+    fn test_decode_amm_info_too_small() {
+        assert_eq!(
+            AmmInfo::decode(&[0u8; 100]),
+            Err(AccountDecodeError::AccountTooSmall { expected: AMM_INFO_SIZE, actual: 100 })
+        );
+    }
+}
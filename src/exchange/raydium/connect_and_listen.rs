@@ -0,0 +1,95 @@
+use std::env;
+use async_trait::async_trait;
+use solana_sdk::commitment_config::CommitmentLevel;
+use solana_sdk::pubkey::Pubkey;
+use tokio::sync::mpsc::Sender;
+use tokio_tungstenite::tungstenite::protocol::Message;
+use crate::exchange::exchange_update::ExchangeUpdate;
+use crate::exchange::raydium::pool_state::EPoolAccount;
+use crate::exchange::raydium::raydium_exchange::RaydiumExchange;
+use crate::exchange::reconnect_policy::ReconnectPolicy;
+use crate::exchange::solana::pubsub_client::{EPubSubMessage, ESubscriptionTarget, PubSubClient};
+use crate::exchange::ws_connection::ws_connection_manager::run_ws_connection;
+use crate::exchange::ws_connection::ws_protocol::{EHeartbeat, EWsMessage, WsProtocol};
+
+/// Solana PubSub protocol for the pool state and vault accounts of a Raydium AMM v4 pool and the cluster slots
+struct RaydiumWsProtocol<'a> {
+    exchange: &'a RaydiumExchange,
+    base_mint: &'a Pubkey,
+    update_sender: &'a Sender<ExchangeUpdate>,
+    /// Subscriptions to the pool accounts, known once the pool has been loaded
+    pubsub: PubSubClient<EPoolAccount>,
+}
+
+#[async_trait]
+impl WsProtocol for RaydiumWsProtocol<'_> {
+    fn name(&self) -> String {
+        self.exchange.name.clone()
+    }
+
+    fn url(&self) -> String {
+        env::var("RAYDIUM_WS_URL").expect("RAYDIUM_WS_URL must be set")
+    }
+
+    async fn on_connecting(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.exchange.load_pool(self.base_mint).await?;
+        self.exchange.process_pool_update(self.update_sender, 0).await;
+
+        let pool = self.exchange.pool.lock().await;
+        let amm_info = pool.amm_info.as_ref().ok_or("Pool is not loaded")?;
+        self.pubsub.clear();
+        self.pubsub.add(ESubscriptionTarget::Slot, EPoolAccount::Slot);
+        self.pubsub.add(ESubscriptionTarget::Account(self.exchange.amm_account), EPoolAccount::Amm);
+        self.pubsub.add(ESubscriptionTarget::Account(amm_info.coin_vault), EPoolAccount::CoinVault);
+        self.pubsub.add(ESubscriptionTarget::Account(amm_info.pc_vault), EPoolAccount::PcVault);
+        println!("[INFO][Raydium] Connecting to Solana WebSocket to listen pool {}...", self.exchange.amm_account);
+        Ok(())
+    }
+
+    fn subscribe_messages(&self) -> Result<Vec<Message>, Box<dyn std::error::Error>> {
+        Ok(self.pubsub.subscribe_messages()?)
+    }
+
+    fn heartbeat(&self) -> EHeartbeat {
+        EHeartbeat::PingFrame
+    }
+
+    async fn handle_text(&self, text: &str) -> EWsMessage {
+        match self.pubsub.handle_text(text) {
+            Some(EPubSubMessage::Subscribed { key, target }) => {
+                println!("[INFO][Raydium] Subscribed to {:?} {}", key, target);
+            }
+            Some(EPubSubMessage::Account { key, slot, data }) => {
+                match self.exchange.process_account_update(key, &data, slot).await {
+                    Ok(()) => self.exchange.process_pool_update(self.update_sender, slot).await,
+                    Err(e) => eprintln!("[ERROR][Raydium] Failed to decode {:?} account: {}", key, e),
+                }
+            }
+            Some(EPubSubMessage::Slot { slot, .. }) => {
+                if let Some(vault_slot) = self.exchange.process_slot(slot).await {
+                    self.exchange.process_pool_update(self.update_sender, vault_slot).await;
+                }
+            }
+            Some(EPubSubMessage::Unsubscribed | EPubSubMessage::Stale) => {}
+            Some(EPubSubMessage::Program { .. }) | None => return EWsMessage::Unknown,
+        }
+        EWsMessage::Handled
+    }
+}
+
+impl RaydiumExchange {
+    /// Listen to the pool accounts until the reconnect budget is exhausted
+    pub async fn connect_and_listen(
+        &self,
+        base_mint: &Pubkey,
+        update_sender: &Sender<ExchangeUpdate>,
+    ) {
+        let protocol = RaydiumWsProtocol {
+            exchange: self,
+            base_mint,
+            update_sender,
+            pubsub: PubSubClient::new(CommitmentLevel::Confirmed),
+        };
+        run_ws_connection(&protocol, ReconnectPolicy::default(), update_sender).await;
+    }
+}
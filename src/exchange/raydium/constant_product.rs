@@ -0,0 +1,112 @@
+/// Constant product (`x * y = k`) pool with the fee charged on the input amount
+/// Reserves are in UI units (already divided by the token decimals)
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConstantProductPool {
+    pub base_reserve: f64,
+    pub quote_reserve: f64,
+    pub fee_rate: f64,
+}
+
+/// Price level of the synthetic order book: price and base volume
+pub type TSyntheticLevel = (f64, f64);
+
+impl ConstantProductPool {
+    /// Marginal price without the fee
+    pub fn spot_price(&self) -> f64 {
+        self.quote_reserve / self.base_reserve
+    }
+
+    /// Split the liquidity into `levels` price bands per side, each `step` (relative) wider than the previous one
+    /// Every level is priced at the worst price of its band including the fee, so walking the levels
+    /// never promises more than the pool would pay
+    /// Returns bids in descending and asks in ascending price order
+    pub fn synthetic_levels(&self, levels: usize, step: f64) -> (Vec<TSyntheticLevel>, Vec<TSyntheticLevel>) {
+        let mut bids = Vec::with_capacity(levels);
+        let mut asks = Vec::with_capacity(levels);
+        if self.base_reserve <= 0.0 || self.quote_reserve <= 0.0 {
+            return (bids, asks);
+        }
+
+        let k = self.base_reserve * self.quote_reserve;
+        let spot_price = self.spot_price();
+        let mut bought = 0.0;
+        let mut sold = 0.0;
+        for i in 1..=levels {
+            // *** Buying base pushes the marginal price up to the upper edge of the band ***
+            let ask_edge = spot_price * (1.0 + step).powi(i as i32);
+            let base_out = self.base_reserve - (k / ask_edge).sqrt();
+            asks.push((ask_edge / (1.0 - self.fee_rate), base_out - bought));
+            bought = base_out;
+
+            // *** Selling base pushes the marginal price down to the lower edge of the band ***
+            let bid_edge = spot_price / (1.0 + step).powi(i as i32);
+            let base_in = ((k / bid_edge).sqrt() - self.base_reserve) / (1.0 - self.fee_rate);
+            bids.push((bid_edge * (1.0 - self.fee_rate), base_in - sold));
+            sold = base_in;
+        }
+        (bids, asks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Base tokens received for the quote input
+    fn base_out(pool: &ConstantProductPool, quote_in: f64) -> f64 {
+        let quote_in = quote_in * (1.0 - pool.fee_rate);
+        pool.base_reserve * quote_in / (pool.quote_reserve + quote_in)
+    }
+
+    /// Quote tokens received for the base input
+    fn quote_out(pool: &ConstantProductPool, base_in: f64) -> f64 {
+        let base_in = base_in * (1.0 - pool.fee_rate);
+        pool.quote_reserve * base_in / (pool.base_reserve + base_in)
+    }
+
+    fn pool() -> ConstantProductPool {
+        ConstantProductPool { base_reserve: 10_000.0, quote_reserve: 1_500_000.0, fee_rate: 0.0025 }
+    }
+
+    #[test]
+    fn test_synthetic_levels_surround_spot_price() {
+        let pool = pool();
+
+        let (bids, asks) = pool.synthetic_levels(10, 0.001);
+
+        assert_eq!(bids.len(), 10);
+        assert_eq!(asks.len(), 10);
+        assert!(bids[0].0 < pool.spot_price());
+        assert!(asks[0].0 > pool.spot_price());
+        assert!(bids.windows(2).all(|pair| pair[0].0 > pair[1].0));
+        assert!(asks.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        assert!(bids.iter().chain(asks.iter()).all(|(_, qty)| *qty > 0.0));
+    }
+
+    #[test]
+    fn test_walking_synthetic_asks_is_not_cheaper_than_swap() {
+        let pool = pool();
+        let (_, asks) = pool.synthetic_levels(5, 0.001);
+
+        let base: f64 = asks.iter().map(|(_, qty)| qty).sum();
+        let quote: f64 = asks.iter().map(|(px, qty)| px * qty).sum();
+
+        // The exact swap of the same quote amount gives at least the synthetic base volume
+        let swap_base = base_out(&pool, quote);
+        assert!(swap_base >= base);
+        assert!((swap_base - base) / base < 0.001);
+    }
+
+    #[test]
+    fn test_walking_synthetic_bids_does_not_overpay() {
+        let pool = pool();
+        let (bids, _) = pool.synthetic_levels(5, 0.001);
+
+        let base: f64 = bids.iter().map(|(_, qty)| qty).sum();
+        let quote: f64 = bids.iter().map(|(px, qty)| px * qty).sum();
+
+        let swap_quote = quote_out(&pool, base);
+        assert!(swap_quote >= quote);
+        assert!((swap_quote - quote) / quote < 0.001);
+    }
+}
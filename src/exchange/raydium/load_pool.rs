@@ -0,0 +1,35 @@
+use solana_sdk::pubkey::Pubkey;
+use crate::exchange::raydium::amm_info::AmmInfo;
+use crate::exchange::raydium::raydium_exchange::RaydiumExchange;
+use crate::exchange::solana::token_account::decode_token_amount;
+
+impl RaydiumExchange {
+    /// Load the pool and vault accounts over HTTP, notifications only arrive on changes
+    pub(crate) async fn load_pool(&self, base_mint: &Pubkey) -> Result<(), Box<dyn std::error::Error>> {
+        let amm_account = self.connection.get_account(&self.amm_account).await?;
+        let amm_info = AmmInfo::decode(&amm_account.data)?;
+
+        let inverted = if amm_info.coin_mint == *base_mint {
+            false
+        } else if amm_info.pc_mint == *base_mint {
+            true
+        } else {
+            return Err(format!("Pool {} does not trade mint {}", self.amm_account, base_mint).into());
+        };
+
+        let vaults = self.connection.get_multiple_accounts(&[amm_info.coin_vault, amm_info.pc_vault]).await?;
+        let [Some(coin_vault), Some(pc_vault)] = vaults.as_slice() else {
+            return Err(format!("Vault accounts of pool {} not found", self.amm_account).into());
+        };
+
+        let mut pool = self.pool.lock().await;
+        pool.coin_vault_amount = Some(decode_token_amount(&coin_vault.data)?);
+        pool.pc_vault_amount = Some(decode_token_amount(&pc_vault.data)?);
+        pool.coin_vault_slot = 0;
+        pool.pc_vault_slot = 0;
+        pool.settled_slot = 0;
+        pool.amm_info = Some(amm_info);
+        pool.inverted = inverted;
+        Ok(())
+    }
+}
@@ -0,0 +1,7 @@
+pub mod raydium_exchange;
+pub mod connect_and_listen;
+pub mod amm_info;
+pub mod constant_product;
+pub mod pool_state;
+mod load_pool;
+mod process_pool_update;
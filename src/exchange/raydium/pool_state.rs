@@ -0,0 +1,149 @@
+use crate::exchange::raydium::amm_info::AmmInfo;
+use crate::exchange::raydium::constant_product::ConstantProductPool;
+
+/// Accounts of the pool the driver subscribes to
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EPoolAccount {
+    Amm,
+    CoinVault,
+    PcVault,
+    /// Slots processed by the node, not an account: they settle the slots where only one vault changed
+    Slot,
+}
+
+/// Latest known state of the pool accounts
+#[derive(Default)]
+pub struct PoolState {
+    pub amm_info: Option<AmmInfo>,
+    pub coin_vault_amount: Option<u64>,
+    pub pc_vault_amount: Option<u64>,
+    /// Slot of the last coin vault update
+    pub coin_vault_slot: u64,
+    /// Slot of the last pc vault update
+    pub pc_vault_slot: u64,
+    /// Every notification of the slots up to this one has been received
+    pub settled_slot: u64,
+    /// True if the pool coin is the quote currency of the trading pair
+    pub inverted: bool,
+}
+
+impl PoolState {
+    /// Mark the slots up to the given one as complete
+    /// Returns the vault slot if it was waiting for the other vault, which has then not changed in that slot
+    pub fn settle(&mut self, slot: u64) -> Option<u64> {
+        let vault_slot = self.coin_vault_slot.max(self.pc_vault_slot);
+        let waiting = self.coin_vault_slot != self.pc_vault_slot && self.settled_slot < vault_slot;
+        self.settled_slot = self.settled_slot.max(slot);
+        (waiting && self.settled_slot >= vault_slot).then_some(vault_slot)
+    }
+
+    /// Constant product pool in trading pair orientation, once all accounts are known
+    /// A swap changes both vaults in the same slot, so while the newest vault slot is not settled
+    /// the reserves are only consistent once both vault notifications of that slot have arrived.
+    /// Transfers to a single vault (e.g. OpenBook settlements or donations) are applied once their slot is settled
+    pub fn constant_product(&self) -> Option<ConstantProductPool> {
+        let vault_slot = self.coin_vault_slot.max(self.pc_vault_slot);
+        if self.coin_vault_slot != self.pc_vault_slot && self.settled_slot < vault_slot {
+            return None;
+        }
+        let amm_info = self.amm_info.as_ref()?;
+        let coin_amount = self.coin_vault_amount?.saturating_sub(amm_info.need_take_pnl_coin);
+        let pc_amount = self.pc_vault_amount?.saturating_sub(amm_info.need_take_pnl_pc);
+        let coin_reserve = coin_amount as f64 / 10f64.powi(amm_info.coin_decimals as i32);
+        let pc_reserve = pc_amount as f64 / 10f64.powi(amm_info.pc_decimals as i32);
+
+        let (base_reserve, quote_reserve) = if self.inverted {
+            (pc_reserve, coin_reserve)
+        } else {
+            (coin_reserve, pc_reserve)
+        };
+        Some(ConstantProductPool {
+            base_reserve,
+            quote_reserve,
+            fee_rate: amm_info.fee_rate(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solana_sdk::pubkey::Pubkey;
+    use super::*;
+
+    fn amm_info() -> AmmInfo {
+        AmmInfo {
+            coin_decimals: 9,
+            pc_decimals: 6,
+            swap_fee_numerator: 25,
+            swap_fee_denominator: 10_000,
+            need_take_pnl_coin: 1_000_000_000,
+            need_take_pnl_pc: 0,
+            coin_vault: Pubkey::new_unique(),
+            pc_vault: Pubkey::new_unique(),
+            coin_mint: Pubkey::new_unique(),
+            pc_mint: Pubkey::new_unique(),
+        }
+    }
+
+    #[test]
+    fn test_constant_product_excludes_protocol_pnl() {
+        let state = PoolState {
+            amm_info: Some(amm_info()),
+            coin_vault_amount: Some(11_000_000_000),
+            pc_vault_amount: Some(1_500_000_000),
+            inverted: false,
+            ..PoolState::default()
+        };
+
+        let pool = state.constant_product().unwrap();
+
+        assert_eq!(pool.base_reserve, 10.0);
+        assert_eq!(pool.quote_reserve, 1_500.0);
+        assert_eq!(pool.spot_price(), 150.0);
+        assert_eq!(pool.fee_rate, 0.0025);
+    }
+
+    #[test]
+    fn test_constant_product_requires_all_accounts() {
+        let state = PoolState { amm_info: Some(amm_info()), coin_vault_amount: Some(1), ..PoolState::default() };
+
+        assert_eq!(state.constant_product(), None);
+    }
+
+    #[test]
+    fn test_constant_product_waits_for_both_vaults_of_a_slot() {
+        let mut state = PoolState {
+            amm_info: Some(amm_info()),
+            coin_vault_amount: Some(11_000_000_000),
+            pc_vault_amount: Some(1_500_000_000),
+            coin_vault_slot: 101,
+            pc_vault_slot: 100,
+            ..PoolState::default()
+        };
+
+        assert_eq!(state.constant_product(), None);
+
+        state.pc_vault_slot = 101;
+        assert!(state.constant_product().is_some());
+    }
+
+    #[test]
+    fn test_single_vault_change_is_applied_once_settled() {
+        let mut state = PoolState {
+            amm_info: Some(amm_info()),
+            coin_vault_amount: Some(11_000_000_000),
+            pc_vault_amount: Some(1_500_000_000),
+            coin_vault_slot: 101,
+            pc_vault_slot: 100,
+            ..PoolState::default()
+        };
+
+        assert_eq!(state.settle(100), None);
+        assert_eq!(state.constant_product(), None);
+
+        assert_eq!(state.settle(101), Some(101));
+        assert!(state.constant_product().is_some());
+        // Already settled, not reported twice
+        assert_eq!(state.settle(105), None);
+    }
+}
@@ -0,0 +1,87 @@
+use chrono::Utc;
+use ordered_float::OrderedFloat;
+use tokio::sync::mpsc::Sender;
+use crate::exchange::exchange_update::ExchangeUpdate;
+use crate::exchange::order_book::OrderBook;
+use crate::exchange::raydium::amm_info::AmmInfo;
+use crate::exchange::solana::account_data::AccountDecodeError;
+use crate::exchange::raydium::pool_state::EPoolAccount;
+use crate::exchange::raydium::raydium_exchange::RaydiumExchange;
+use crate::exchange::solana::token_account::decode_token_amount;
+use crate::exchange::send_orderbook_update::send_orderbook_update;
+
+/// Number of synthetic price levels per side
+const SYNTHETIC_LEVELS: usize = 20;
+/// Relative width of a synthetic price level
+const SYNTHETIC_LEVEL_STEP: f64 = 0.001;
+/// Slots notified by the node are processed ones, confirmed account notifications of a slot may still
+/// arrive this many slots later
+const SETTLE_SLOT_LAG: u64 = 4;
+
+impl RaydiumExchange {
+    /// Store the changed pool account, a notification of a slot settles the previous slots
+    pub(crate) async fn process_account_update(
+        &self,
+        account: EPoolAccount,
+        data: &[u8],
+        slot: u64,
+    ) -> Result<(), AccountDecodeError> {
+        let mut pool = self.pool.lock().await;
+        if let Some(vault_slot) = pool.settle(slot.saturating_sub(1)) {
+            report_single_vault_change(&self.name, vault_slot);
+        }
+        match account {
+            EPoolAccount::Amm => pool.amm_info = Some(AmmInfo::decode(data)?),
+            EPoolAccount::CoinVault => {
+                pool.coin_vault_amount = Some(decode_token_amount(data)?);
+                pool.coin_vault_slot = slot;
+            }
+            EPoolAccount::PcVault => {
+                pool.pc_vault_amount = Some(decode_token_amount(data)?);
+                pool.pc_vault_slot = slot;
+            }
+            EPoolAccount::Slot => {}
+        }
+        Ok(())
+    }
+
+    /// Settle the slots the node has moved past, returns the slot of a vault change that can now be applied alone
+    pub(crate) async fn process_slot(&self, slot: u64) -> Option<u64> {
+        let vault_slot = self.pool.lock().await.settle(slot.saturating_sub(SETTLE_SLOT_LAG))?;
+        report_single_vault_change(&self.name, vault_slot);
+        Some(vault_slot)
+    }
+
+    /// Rebuild the synthetic order book from the pool reserves
+    pub(crate) async fn process_pool_update(&self, update_sender: &Sender<ExchangeUpdate>, slot: u64) {
+        let Some(pool) = self.pool.lock().await.constant_product() else {
+            return;
+        };
+        let mut orderbook = self.orderbook.write().await;
+        // Zero slot means the pool has been loaded over HTTP and the order book starts over
+        if slot != 0 && slot < orderbook.sequence {
+            println!("[WARNING][Raydium] Skipping outdated update: received slot {}, current slot {}", slot, orderbook.sequence);
+            return;
+        }
+
+        let (bids, asks) = pool.synthetic_levels(SYNTHETIC_LEVELS, SYNTHETIC_LEVEL_STEP);
+        *orderbook = OrderBook::new();
+        orderbook.bids.extend(bids.into_iter().map(|(price, qty)| (OrderedFloat(price), qty)));
+        orderbook.asks.extend(asks.into_iter().map(|(price, qty)| (OrderedFloat(price), qty)));
+        orderbook.sequence = slot;
+        orderbook.last_updated = Utc::now();
+
+        // *** Notify subscribers about the updated orderbook ***
+        send_orderbook_update(
+            update_sender,
+            &self.name,
+            &orderbook.get_best_bid(),
+            &orderbook.get_best_ask(),
+            orderbook.last_updated,
+        ).await;
+    }
+}
+
+fn report_single_vault_change(name: &str, slot: u64) {
+    println!("[WARNING][{name}] Only one vault changed in slot {}, applying the reserves without a swap", slot);
+}
@@ -0,0 +1,56 @@
+use std::env;
+use std::str::FromStr;
+use std::sync::Arc;
+use async_trait::async_trait;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use tokio::sync::mpsc::Sender;
+use tokio::sync::{Mutex, RwLock};
+use crate::exchange::exchange::Exchange;
+use crate::exchange::exchange_update::ExchangeUpdate;
+use crate::exchange::order_book::OrderBook;
+use crate::exchange::raydium::pool_state::PoolState;
+use crate::exchange::solana::token_mint::base_mint;
+use crate::trading_pair::ETradingPair;
+
+pub struct RaydiumExchange {
+    pub(crate) name: String,
+    pub(crate) orderbook: Arc<RwLock<OrderBook>>,
+    pub(crate) connection: RpcClient,
+    /// Raydium AMM v4 pool account
+    pub(crate) amm_account: Pubkey,
+    pub(crate) pool: Mutex<PoolState>,
+}
+
+#[async_trait]
+impl Exchange for RaydiumExchange {
+    fn new() -> Self {
+        let rpc_client = RpcClient::new(env::var("RAYDIUM_RPC_URL").expect("RAYDIUM_RPC_URL must be set"));
+        let amm_account = Pubkey::from_str(&env::var("RAYDIUM_AMM_POOL_PK").expect("RAYDIUM_AMM_POOL_PK must be set"))
+            .expect("RAYDIUM_AMM_POOL_PK must be a valid public key");
+        RaydiumExchange {
+            name: "Raydium".to_string(),
+            orderbook: Arc::new(RwLock::new(OrderBook::new())),
+            connection: rpc_client,
+            amm_account,
+            pool: Mutex::new(PoolState::default()),
+        }
+    }
+
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    async fn start(&self, trading_pair: ETradingPair, update_sender: Sender<ExchangeUpdate>) {
+        let Some(base_mint) = base_mint(&trading_pair) else {
+            eprintln!("[ERROR][Raydium] Trading pair {} is not supported", trading_pair);
+            return;
+        };
+        println!("[INFO][Raydium] Starting pool subscription...");
+        self.connect_and_listen(&base_mint, &update_sender).await;
+    }
+
+    fn get_order_book(&self) -> Arc<RwLock<OrderBook>> {
+        self.orderbook.clone()
+    }
+}
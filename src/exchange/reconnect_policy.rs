@@ -0,0 +1,168 @@
+use std::time::Duration;
+use chrono::Utc;
+use rand::Rng;
+use crate::exchange::connection_stats::{ConnectionStats, EConnectionState};
+
+/// Describes how an exchange reconnects after its connection has been closed
+#[derive(Clone, Debug)]
+pub struct ReconnectPolicy {
+    /// Delay before the first reconnection attempt
+    pub initial_delay: Duration,
+    /// Upper bound of the delay between attempts
+    pub max_delay: Duration,
+    /// Factor the delay is multiplied by after every failed attempt
+    pub multiplier: f64,
+    /// Fraction of the delay that is randomized in both directions (0.0 - 1.0)
+    pub jitter: f64,
+    /// Number of consecutive failed attempts after which the circuit breaker opens
+    pub max_retries: u32,
+    /// A connection that stayed open at least this long resets the backoff
+    pub stable_after: Duration,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        ReconnectPolicy {
+            initial_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            multiplier: 2.0,
+            jitter: 0.2,
+            max_retries: 10,
+            stable_after: Duration::from_secs(30),
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// Returns the backoff delay without jitter for the given zero-based retry number
+    pub fn backoff_delay(&self, retry: u32) -> Duration {
+        let delay = self.initial_delay.as_secs_f64() * self.multiplier.powi(retry as i32);
+        Duration::from_secs_f64(delay.min(self.max_delay.as_secs_f64()))
+    }
+
+    /// Returns the backoff delay randomized by the jitter fraction
+    pub fn jittered_delay(&self, retry: u32) -> Duration {
+        let delay = self.backoff_delay(retry).as_secs_f64();
+        let jitter = if self.jitter > 0.0 {
+            rand::thread_rng().gen_range(-self.jitter..=self.jitter)
+        } else {
+            0.0
+        };
+        Duration::from_secs_f64((delay * (1.0 + jitter)).max(0.0))
+    }
+}
+
+/// Applies a reconnect policy to a single exchange connection and keeps its statistics
+pub struct Reconnector {
+    policy: ReconnectPolicy,
+    stats: ConnectionStats,
+}
+
+impl Reconnector {
+    pub(crate) fn new(policy: ReconnectPolicy) -> Self {
+        Reconnector {
+            policy,
+            stats: ConnectionStats::new(),
+        }
+    }
+
+    pub fn stats(&self) -> &ConnectionStats {
+        &self.stats
+    }
+
+    /// Must be called before every connection attempt
+    pub fn on_attempt(&mut self) {
+        self.stats.attempts += 1;
+        if self.stats.state != EConnectionState::Reconnecting {
+            self.stats.state = EConnectionState::Connecting;
+        }
+    }
+
+    /// Must be called once the connection has been established and subscribed
+    pub fn on_connected(&mut self) {
+        self.stats.successful_connections += 1;
+        self.stats.state = EConnectionState::Connected;
+        self.stats.last_connected = Some(Utc::now());
+    }
+
+    /// Must be called when the connection has been closed, with the error if there was one
+    pub fn on_disconnected(&mut self, error: Option<String>) {
+        let now = Utc::now();
+        let was_stable = self.stats.state == EConnectionState::Connected
+            && self.stats.last_connected
+                .and_then(|connected| now.signed_duration_since(connected).to_std().ok())
+                .is_some_and(|uptime| uptime >= self.policy.stable_after);
+        if was_stable {
+            self.stats.consecutive_failures = 0;
+        }
+
+        self.stats.consecutive_failures += 1;
+        if error.is_some() {
+            self.stats.failures += 1;
+            self.stats.last_error = error;
+        }
+        self.stats.last_disconnected = Some(now);
+        self.stats.state = EConnectionState::Reconnecting;
+    }
+
+    /// Returns the delay before the next attempt
+    /// or `None` if the retry budget is exhausted and the circuit breaker has opened
+    pub fn next_delay(&mut self) -> Option<Duration> {
+        if self.stats.consecutive_failures > self.policy.max_retries {
+            self.stats.state = EConnectionState::Failed;
+            return None;
+        }
+        Some(self.policy.jittered_delay(self.stats.consecutive_failures.saturating_sub(1)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> ReconnectPolicy {
+        ReconnectPolicy {
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(1),
+            multiplier: 2.0,
+            jitter: 0.0,
+            max_retries: 3,
+            stable_after: Duration::from_secs(30),
+        }
+    }
+
+    #[test]
+    fn test_backoff_delay_is_exponential_and_capped() {
+        let policy = policy();
+        assert_eq!(policy.backoff_delay(0), Duration::from_millis(100));
+        assert_eq!(policy.backoff_delay(1), Duration::from_millis(200));
+        assert_eq!(policy.backoff_delay(3), Duration::from_millis(800));
+        assert_eq!(policy.backoff_delay(10), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_jittered_delay_stays_within_bounds() {
+        let policy = ReconnectPolicy { jitter: 0.5, ..policy() };
+        for _ in 0..100 {
+            let delay = policy.jittered_delay(1);
+            assert!(delay >= Duration::from_millis(100) && delay <= Duration::from_millis(300));
+        }
+    }
+
+    #[test]
+    fn test_circuit_breaker_opens_after_retry_budget() {
+        let mut reconnector = Reconnector::new(policy());
+        for _ in 0..3 {
+            reconnector.on_attempt();
+            reconnector.on_disconnected(Some("connection refused".to_string()));
+            assert!(reconnector.next_delay().is_some());
+        }
+        reconnector.on_attempt();
+        reconnector.on_disconnected(Some("connection refused".to_string()));
+
+        assert_eq!(reconnector.next_delay(), None);
+        assert_eq!(reconnector.stats().state, EConnectionState::Failed);
+        assert_eq!(reconnector.stats().attempts, 4);
+        assert_eq!(reconnector.stats().failures, 4);
+    }
+}
@@ -0,0 +1,19 @@
+use tokio::sync::mpsc::Sender;
+use crate::exchange::connection_stats::ConnectionStats;
+use crate::exchange::exchange_update::{EUpdatePayload, ExchangeUpdate};
+
+pub async fn send_connection_update(
+    update_sender: &Sender<ExchangeUpdate>,
+    exchange_name: &String,
+    stats: &ConnectionStats,
+) {
+    update_sender.send(ExchangeUpdate {
+        exchange_name: exchange_name.clone(),
+        payload: EUpdatePayload::ConnectionStats(stats.clone()),
+    })
+        .await
+        .map_err(|e| {
+            eprintln!("[ERROR][{exchange_name}] Failed to send connection update: {}", e);
+        })
+        .unwrap_or(());
+}
@@ -1,6 +1,7 @@
+use chrono::{DateTime, Utc};
 use ordered_float::Float;
 use tokio::sync::mpsc::Sender;
-use crate::exchange::exchange_update::{BestPrices, ExchangeUpdate};
+use crate::exchange::exchange_update::{BestPrices, EUpdatePayload, ExchangeUpdate};
 use crate::exchange::order_book::TPrice;
 
 pub async fn send_orderbook_update(
@@ -8,19 +9,21 @@ pub async fn send_orderbook_update(
     exchange_name: &String,
     best_bid: &Option<TPrice>,
     best_ask: &Option<TPrice>,
+    timestamp: DateTime<Utc>,
 ) {
     let best_bid = best_bid.unwrap_or(TPrice::min_value());
     let best_ask = best_ask.unwrap_or(TPrice::max_value());
     update_sender.send(ExchangeUpdate {
         exchange_name: exchange_name.clone(),
-        best_prices: BestPrices {
+        payload: EUpdatePayload::BestPrices(BestPrices {
             best_bid,
             best_ask,
-        },
+            timestamp,
+        }),
     })
         .await
         .map_err(|e| {
             eprintln!("[ERROR][{exchange_name}] Failed to send order book update: {}", e);
         })
         .unwrap_or(());
-}
\ No newline at end of file
+}
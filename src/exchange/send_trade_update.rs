@@ -0,0 +1,19 @@
+use tokio::sync::mpsc::Sender;
+use crate::exchange::exchange_update::{EUpdatePayload, ExchangeUpdate};
+use crate::exchange::trade_tape::Trade;
+
+pub async fn send_trade_update(
+    update_sender: &Sender<ExchangeUpdate>,
+    exchange_name: &String,
+    trade: Trade,
+) {
+    update_sender.send(ExchangeUpdate {
+        exchange_name: exchange_name.clone(),
+        payload: EUpdatePayload::Trade(trade),
+    })
+        .await
+        .map_err(|e| {
+            eprintln!("[ERROR][{exchange_name}] Failed to send trade update: {}", e);
+        })
+        .unwrap_or(());
+}
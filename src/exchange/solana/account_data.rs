@@ -0,0 +1,60 @@
+use solana_sdk::pubkey::Pubkey;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum AccountDecodeError {
+    #[error("Account data too small: expected at least {expected} bytes, got {actual}")]
+    AccountTooSmall { expected: usize, actual: usize },
+    #[error("Account links are corrupted at index {index}")]
+    CorruptedLinks { index: u32 },
+}
+
+/// Make sure the account data is large enough for the layout, the readers below rely on it
+pub fn check_size(data: &[u8], expected: usize) -> Result<(), AccountDecodeError> {
+    if data.len() < expected {
+        return Err(AccountDecodeError::AccountTooSmall { expected, actual: data.len() });
+    }
+    Ok(())
+}
+
+pub fn read_u8(data: &[u8], offset: usize) -> u8 {
+    data[offset]
+}
+
+pub fn read_u16(data: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes(read_array(data, offset))
+}
+
+pub fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(read_array(data, offset))
+}
+
+pub fn read_i32(data: &[u8], offset: usize) -> i32 {
+    i32::from_le_bytes(read_array(data, offset))
+}
+
+pub fn read_u64(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(read_array(data, offset))
+}
+
+pub fn read_i64(data: &[u8], offset: usize) -> i64 {
+    i64::from_le_bytes(read_array(data, offset))
+}
+
+pub fn read_u128(data: &[u8], offset: usize) -> u128 {
+    u128::from_le_bytes(read_array(data, offset))
+}
+
+pub fn read_i128(data: &[u8], offset: usize) -> i128 {
+    i128::from_le_bytes(read_array(data, offset))
+}
+
+pub fn read_pubkey(data: &[u8], offset: usize) -> Pubkey {
+    Pubkey::new_from_array(read_array(data, offset))
+}
+
+fn read_array<const N: usize>(data: &[u8], offset: usize) -> [u8; N] {
+    let mut bytes = [0u8; N];
+    bytes.copy_from_slice(&data[offset..offset + N]);
+    bytes
+}
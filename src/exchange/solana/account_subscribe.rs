@@ -37,6 +37,12 @@ impl SubscribeMessage {
             ),
         }
     }
+
+    /// Use a custom request ID to tell apart the responses of several subscriptions on one connection
+    pub fn with_id(mut self, id: u64) -> Self {
+        self.id = id;
+        self
+    }
 }
 
 #[cfg(test)]
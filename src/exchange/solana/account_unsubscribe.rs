@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize)]
+pub struct UnsubscribeMessage {
+    jsonrpc: String,
+    id: u64,
+    method: String,
+    params: (u64,),
+}
+
+impl UnsubscribeMessage {
+    pub fn new(id: u64, subscription: u64) -> Self {
+        Self {
+            jsonrpc: "2.0".to_string(),
+            id,
+            method: "accountUnsubscribe".to_string(),
+            params: (subscription,),
+        }
+    }
+
+    /// Cancel a `programSubscribe` subscription
+    pub fn program(id: u64, subscription: u64) -> Self {
+        Self {
+            method: "programUnsubscribe".to_string(),
+            ..Self::new(id, subscription)
+        }
+    }
+
+    /// Cancel a `slotSubscribe` subscription
+    pub fn slot(id: u64, subscription: u64) -> Self {
+        Self {
+            method: "slotUnsubscribe".to_string(),
+            ..Self::new(id, subscription)
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+pub struct UnsubscriptionResponse {
+    pub jsonrpc: String,
+    /// True if the subscription has been removed
+    pub result: bool,
+    pub id: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_unsubscribe_message_serialization() {
+        let message = UnsubscribeMessage::new(7, 23784);
+
+        let serialized = serde_json::to_value(&message).unwrap();
+
+        let expected = json!({
+            "jsonrpc": "2.0",
+            "id": 7,
+            "method": "accountUnsubscribe",
+            "params": [23784]
+        });
+
+        assert_eq!(serialized, expected);
+    }
+}
@@ -0,0 +1,14 @@
+use solana_sdk::commitment_config::CommitmentLevel;
+
+/// Read the commitment level from the variable (`processed`, `confirmed` or `finalized`), confirmed by default
+pub fn commitment_from_env(name: &str, variable: &str) -> CommitmentLevel {
+    match std::env::var(variable).ok().as_deref() {
+        Some("processed") => CommitmentLevel::Processed,
+        Some("finalized") => CommitmentLevel::Finalized,
+        Some("confirmed") | None => CommitmentLevel::Confirmed,
+        Some(other) => {
+            eprintln!("[WARNING][{name}] Unknown {} '{}', using 'confirmed'", variable, other);
+            CommitmentLevel::Confirmed
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use std::fmt;
+use std::time::Duration;
+
+/// Weight of a new sample in the average latencies
+const LATENCY_SMOOTHING: f64 = 0.2;
+
+/// Health and latency of one RPC or WebSocket endpoint
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EndpointStats {
+    /// Host of the endpoint, the path and query are left out as they often carry an API key
+    pub label: String,
+    /// Successful health checks or subscriptions
+    pub successes: u64,
+    /// Failed health checks or lost connections
+    pub failures: u64,
+    pub last_error: Option<String>,
+    /// Average round trip of the health checks or subscriptions
+    pub latency: Option<Duration>,
+    /// Notifications of a slot the endpoint delivered first, in race mode
+    pub first_deliveries: u64,
+    /// Notifications of a slot another endpoint had already delivered, in race mode
+    pub late_deliveries: u64,
+    /// Average delay of the late deliveries behind the first endpoint
+    pub lag: Option<Duration>,
+}
+
+impl EndpointStats {
+    pub fn new(url: &str) -> Self {
+        Self { label: endpoint_label(url), ..Self::default() }
+    }
+
+    pub fn record_success(&mut self, latency: Duration) {
+        self.successes += 1;
+        self.latency = Some(smooth(self.latency, latency));
+    }
+
+    pub fn record_failure(&mut self, error: String) {
+        self.failures += 1;
+        self.last_error = Some(error);
+    }
+
+    pub fn record_first_delivery(&mut self) {
+        self.first_deliveries += 1;
+    }
+
+    pub fn record_late_delivery(&mut self, delay: Duration) {
+        self.late_deliveries += 1;
+        self.lag = Some(smooth(self.lag, delay));
+    }
+}
+
+impl fmt::Display for EndpointStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} ok, {} failed", self.label, self.successes, self.failures)?;
+        if let Some(latency) = self.latency {
+            write!(f, ", latency {} ms", latency.as_millis())?;
+        }
+        if self.first_deliveries + self.late_deliveries > 0 {
+            write!(f, ", first on {}/{} notifications", self.first_deliveries, self.first_deliveries + self.late_deliveries)?;
+        }
+        if let Some(lag) = self.lag {
+            write!(f, ", {} ms behind when late", lag.as_millis())?;
+        }
+        if let Some(error) = &self.last_error {
+            write!(f, ", last error: {}", error)?;
+        }
+        Ok(())
+    }
+}
+
+fn smooth(average: Option<Duration>, sample: Duration) -> Duration {
+    match average {
+        Some(average) => average.mul_f64(1.0 - LATENCY_SMOOTHING) + sample.mul_f64(LATENCY_SMOOTHING),
+        None => sample,
+    }
+}
+
+/// Host and port of the URL, without the scheme, path and query
+pub fn endpoint_label(url: &str) -> String {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    rest.split(['/', '?']).next().unwrap_or(rest).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoint_label_drops_path_and_query() {
+        assert_eq!(endpoint_label("https://mainnet.helius-rpc.com/?api-key=secret"), "mainnet.helius-rpc.com");
+        assert_eq!(endpoint_label("wss://example.com:8900/v1/key"), "example.com:8900");
+        assert_eq!(endpoint_label("localhost:8899"), "localhost:8899");
+    }
+
+    #[test]
+    fn test_latencies_are_averaged() {
+        let mut stats = EndpointStats::new("https://api.devnet.solana.com/");
+        stats.record_success(Duration::from_millis(100));
+        assert_eq!(stats.latency, Some(Duration::from_millis(100)));
+        stats.record_success(Duration::from_millis(200));
+        assert_eq!(stats.latency, Some(Duration::from_millis(120)));
+        stats.record_failure("timeout".to_string());
+        assert_eq!((stats.successes, stats.failures), (2, 1));
+        stats.record_late_delivery(Duration::from_millis(50));
+        stats.record_first_delivery();
+        assert_eq!(stats.to_string(), "api.devnet.solana.com: 2 ok, 1 failed, latency 120 ms, first on 1/2 notifications, 50 ms behind when late, last error: timeout");
+    }
+}
@@ -0,0 +1,30 @@
+/// Read the comma separated endpoints of the list variable, or the single endpoint of the other variable
+pub fn endpoint_urls_from_env(list_variable: &str, single_variable: &str) -> Vec<String> {
+    let urls = std::env::var(list_variable).map(|list| parse_endpoint_urls(&list)).unwrap_or_default();
+    if !urls.is_empty() {
+        return urls;
+    }
+    std::env::var(single_variable).map(|url| parse_endpoint_urls(&url)).unwrap_or_default()
+}
+
+fn parse_endpoint_urls(list: &str) -> Vec<String> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|url| !url.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_endpoint_urls() {
+        assert_eq!(
+            parse_endpoint_urls("https://a.example.com/, https://b.example.com/?api-key=k,"),
+            vec!["https://a.example.com/".to_string(), "https://b.example.com/?api-key=k".to_string()]
+        );
+        assert!(parse_endpoint_urls(" ").is_empty());
+    }
+}
@@ -0,0 +1,18 @@
+pub(crate) mod account_notification;
+pub(crate) mod subscription_response;
+pub(crate) mod account_subscribe;
+pub(crate) mod slot_subscribe;
+pub(crate) mod account_data;
+pub(crate) mod token_account;
+pub(crate) mod account_unsubscribe;
+pub(crate) mod token_mint;
+pub(crate) mod program_subscribe;
+pub(crate) mod program_notification;
+pub(crate) mod pubsub_client;
+pub(crate) mod commitment;
+pub(crate) mod slot_clock;
+pub(crate) mod endpoint_stats;
+pub(crate) mod endpoint_urls;
+pub(crate) mod rpc_pool;
+pub(crate) mod ws_endpoints;
+pub(crate) mod slot_dedup;
@@ -0,0 +1,29 @@
+use serde::Deserialize;
+use crate::exchange::solana::account_notification::{Context, Value};
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+pub struct ProgramNotification {
+    pub jsonrpc: String,
+    pub method: String,
+    pub params: Params,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Params {
+    pub result: Result,
+    pub subscription: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Result {
+    pub context: Context,
+    pub value: ProgramAccount,
+}
+
+/// Changed account of the program
+#[derive(Debug, Deserialize)]
+pub struct ProgramAccount {
+    pub pubkey: String,
+    pub account: Value,
+}
@@ -0,0 +1,87 @@
+use serde::Serialize;
+use solana_sdk::commitment_config::CommitmentLevel;
+use solana_sdk::pubkey::Pubkey;
+
+/// Filter of the program accounts a `programSubscribe` subscription is notified about
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[allow(dead_code)]
+pub enum EProgramFilter {
+    /// Accounts with exactly this data length
+    DataSize(u64),
+    /// Accounts whose data at the offset matches the base58 encoded bytes
+    Memcmp { offset: usize, bytes: String },
+}
+
+#[derive(Serialize)]
+pub struct ProgramSubscribeMessage {
+    jsonrpc: String,
+    id: u64,
+    method: String,
+    params: Params,
+}
+
+type TProgramKey = String;
+
+#[derive(Serialize)]
+struct SubscribeConfig {
+    encoding: String,
+    commitment: CommitmentLevel,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    filters: Vec<EProgramFilter>,
+}
+
+#[derive(Serialize)]
+struct Params(TProgramKey, SubscribeConfig);
+
+impl ProgramSubscribeMessage {
+    pub fn new(id: u64, program_id: &Pubkey, filters: Vec<EProgramFilter>, commitment_level: CommitmentLevel) -> Self {
+        Self {
+            jsonrpc: "2.0".to_string(),
+            id,
+            method: "programSubscribe".to_string(),
+            params: Params(
+                program_id.to_string(),
+                SubscribeConfig {
+                    encoding: "base64".to_string(),
+                    commitment: commitment_level,
+                    filters,
+                },
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_program_subscribe_message_serialization() {
+        let program_id = Pubkey::new_unique();
+        let filters = vec![
+            EProgramFilter::DataSize(120),
+            EProgramFilter::Memcmp { offset: 0, bytes: "5".to_string() },
+        ];
+
+        let message = ProgramSubscribeMessage::new(4, &program_id, filters, CommitmentLevel::Processed);
+
+        assert_eq!(serde_json::to_value(&message).unwrap(), json!({
+            "jsonrpc": "2.0",
+            "id": 4,
+            "method": "programSubscribe",
+            "params": [
+                program_id.to_string(),
+                {
+                    "encoding": "base64",
+                    "commitment": "processed",
+                    "filters": [
+                        { "dataSize": 120 },
+                        { "memcmp": { "offset": 0, "bytes": "5" } }
+                    ]
+                }
+            ]
+        }));
+    }
+}
@@ -0,0 +1,334 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use solana_sdk::commitment_config::CommitmentLevel;
+use solana_sdk::pubkey::Pubkey;
+use tokio_tungstenite::tungstenite::protocol::Message;
+use crate::exchange::solana::account_notification::AccountNotification;
+use crate::exchange::solana::account_subscribe::SubscribeMessage;
+use crate::exchange::solana::account_unsubscribe::{UnsubscribeMessage, UnsubscriptionResponse};
+use crate::exchange::solana::program_notification::ProgramNotification;
+use crate::exchange::solana::program_subscribe::{EProgramFilter, ProgramSubscribeMessage};
+use crate::exchange::solana::slot_subscribe::{SlotInfo, SlotNotification, SlotSubscribeMessage};
+use crate::exchange::solana::subscription_response::SubscriptionResponse;
+
+/// What a subscription listens to
+#[derive(Clone, Debug, PartialEq)]
+#[allow(dead_code)]
+pub enum ESubscriptionTarget {
+    /// `accountSubscribe` to one account
+    Account(Pubkey),
+    /// `programSubscribe` to every account of the program matching the filters
+    Program(Pubkey, Vec<EProgramFilter>),
+    /// `slotSubscribe` to the slots processed by the node
+    Slot,
+}
+
+impl fmt::Display for ESubscriptionTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ESubscriptionTarget::Account(account) => write!(f, "account {}", account),
+            ESubscriptionTarget::Program(program_id, _) => write!(f, "program {}", program_id),
+            ESubscriptionTarget::Slot => write!(f, "slots"),
+        }
+    }
+}
+
+/// Message of the PubSub connection routed to the key of its subscription
+#[derive(Debug, PartialEq)]
+pub enum EPubSubMessage<K> {
+    /// The node confirmed the subscribe request
+    Subscribed { key: K, target: ESubscriptionTarget },
+    /// The node confirmed an unsubscribe request
+    Unsubscribed,
+    /// New data of a subscribed account
+    Account { key: K, slot: u64, data: Vec<u8> },
+    /// New data of an account of a subscribed program
+    Program { key: K, slot: u64, account: Pubkey, data: Vec<u8> },
+    /// The node started processing a slot, `root` is its newest rooted slot
+    Slot { key: K, slot: u64, root: u64 },
+    /// Response or notification of a subscription the client no longer keeps
+    Stale,
+}
+
+/// Solana PubSub subscriptions multiplexed over one WebSocket connection
+/// Every subscribe request gets its own ID, the subscription ID of the response is used to route the
+/// notifications to the key given by the driver. The subscriptions are kept across connections, so
+/// `subscribe_messages` restores all of them after a reconnect
+pub struct PubSubClient<K> {
+    commitment: CommitmentLevel,
+    next_request_id: AtomicU64,
+    /// Every subscription of the client
+    targets: Mutex<Vec<(ESubscriptionTarget, K)>>,
+    /// Subscriptions by the ID of their subscribe request, until the node responds
+    pending: Mutex<HashMap<u64, (ESubscriptionTarget, K)>>,
+    /// Subscriptions by the subscription ID returned by the node
+    active: Mutex<HashMap<u64, (ESubscriptionTarget, K)>>,
+}
+
+impl<K: Copy> PubSubClient<K> {
+    pub fn new(commitment: CommitmentLevel) -> Self {
+        Self {
+            commitment,
+            next_request_id: AtomicU64::new(0),
+            targets: Mutex::new(Vec::new()),
+            pending: Mutex::new(HashMap::new()),
+            active: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Keep a subscription, it is requested by the next `subscribe_messages`
+    pub fn add(&self, target: ESubscriptionTarget, key: K) {
+        self.targets.lock().unwrap().push((target, key));
+    }
+
+    /// Forget every subscription, e.g. before the accounts of a driver are reloaded
+    pub fn clear(&self) {
+        self.targets.lock().unwrap().clear();
+        self.pending.lock().unwrap().clear();
+        self.active.lock().unwrap().clear();
+    }
+
+    /// Number of subscriptions kept by the client
+    pub fn len(&self) -> usize {
+        self.targets.lock().unwrap().len()
+    }
+
+    /// Subscribe requests for every subscription, sent on each new connection
+    /// Subscription IDs of the previous connection are dropped, the node assigns new ones
+    pub fn subscribe_messages(&self) -> Result<Vec<Message>, serde_json::Error> {
+        self.pending.lock().unwrap().clear();
+        self.active.lock().unwrap().clear();
+        let targets = self.targets.lock().unwrap().clone();
+        targets.into_iter().map(|(target, key)| self.request(target, key)).collect()
+    }
+
+    /// Keep a subscription and return its subscribe request for the current connection
+    pub fn subscribe(&self, target: ESubscriptionTarget, key: K) -> Result<Message, serde_json::Error> {
+        self.add(target.clone(), key);
+        self.request(target, key)
+    }
+
+    /// Drop the subscriptions whose key matches and return the unsubscribe requests of the confirmed ones
+    /// Their notifications are reported as `EPubSubMessage::Stale` until the node confirms
+    pub fn unsubscribe_where(&self, matches: impl Fn(&K) -> bool) -> Result<Vec<Message>, serde_json::Error> {
+        self.targets.lock().unwrap().retain(|(_, key)| !matches(key));
+        self.pending.lock().unwrap().retain(|_, (_, key)| !matches(key));
+        let mut active = self.active.lock().unwrap();
+        let removed: Vec<(u64, ESubscriptionTarget)> = active.iter()
+            .filter(|(_, (_, key))| matches(key))
+            .map(|(subscription, (target, _))| (*subscription, target.clone()))
+            .collect();
+        let mut messages = Vec::with_capacity(removed.len());
+        for (subscription, target) in removed {
+            active.remove(&subscription);
+            let id = self.next_request_id.fetch_add(1, Ordering::SeqCst);
+            let message = match target {
+                ESubscriptionTarget::Account(_) => UnsubscribeMessage::new(id, subscription),
+                ESubscriptionTarget::Program(..) => UnsubscribeMessage::program(id, subscription),
+                ESubscriptionTarget::Slot => UnsubscribeMessage::slot(id, subscription),
+            };
+            messages.push(Message::Text(serde_json::to_string(&message)?));
+        }
+        Ok(messages)
+    }
+
+    /// Route a text message of the connection, `None` if it is not a PubSub message
+    pub fn handle_text(&self, text: &str) -> Option<EPubSubMessage<K>> {
+        if let Ok(response) = serde_json::from_str::<SubscriptionResponse>(text) {
+            let Some((target, key)) = self.pending.lock().unwrap().remove(&response.id) else {
+                return Some(EPubSubMessage::Stale);
+            };
+            self.active.lock().unwrap().insert(response.result, (target.clone(), key));
+            Some(EPubSubMessage::Subscribed { key, target })
+        } else if serde_json::from_str::<UnsubscriptionResponse>(text).is_ok() {
+            Some(EPubSubMessage::Unsubscribed)
+        } else if let Ok(notification) = serde_json::from_str::<AccountNotification>(text) {
+            let Some(key) = self.key(notification.params.subscription) else {
+                return Some(EPubSubMessage::Stale);
+            };
+            Some(EPubSubMessage::Account {
+                key,
+                slot: notification.params.result.context.slot,
+                data: notification.params.result.value.data,
+            })
+        } else if let Ok(notification) = serde_json::from_str::<ProgramNotification>(text) {
+            let Some(key) = self.key(notification.params.subscription) else {
+                return Some(EPubSubMessage::Stale);
+            };
+            let value = notification.params.result.value;
+            Some(EPubSubMessage::Program {
+                key,
+                slot: notification.params.result.context.slot,
+                account: Pubkey::from_str(&value.pubkey).ok()?,
+                data: value.account.data,
+            })
+        } else if let Ok(notification) = serde_json::from_str::<SlotNotification>(text) {
+            let Some(key) = self.key(notification.params.subscription) else {
+                return Some(EPubSubMessage::Stale);
+            };
+            let SlotInfo { slot, root, .. } = notification.params.result;
+            Some(EPubSubMessage::Slot { key, slot, root })
+        } else {
+            None
+        }
+    }
+
+    fn key(&self, subscription: u64) -> Option<K> {
+        self.active.lock().unwrap().get(&subscription).map(|(_, key)| *key)
+    }
+
+    fn request(&self, target: ESubscriptionTarget, key: K) -> Result<Message, serde_json::Error> {
+        let id = self.next_request_id.fetch_add(1, Ordering::SeqCst);
+        let text = match &target {
+            ESubscriptionTarget::Account(account) => {
+                serde_json::to_string(&SubscribeMessage::new(account, self.commitment).with_id(id))?
+            }
+            ESubscriptionTarget::Program(program_id, filters) => {
+                serde_json::to_string(&ProgramSubscribeMessage::new(id, program_id, filters.clone(), self.commitment))?
+            }
+            ESubscriptionTarget::Slot => serde_json::to_string(&SlotSubscribeMessage::new(id))?,
+        };
+        self.pending.lock().unwrap().insert(id, (target, key));
+        Ok(Message::Text(text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    fn request_id(message: &Message) -> u64 {
+        let value: Value = serde_json::from_str(message.to_text().unwrap()).unwrap();
+        value["id"].as_u64().unwrap()
+    }
+
+    fn account_notification(subscription: u64, slot: u64) -> String {
+        json!({
+            "jsonrpc": "2.0",
+            "method": "accountNotification",
+            "params": {
+                "result": {
+                    "context": { "slot": slot },
+                    "value": {
+                        "data": ["AQID", "base64"],
+                        "executable": false,
+                        "lamports": 1,
+                        "owner": Pubkey::default().to_string(),
+                        "rentEpoch": 0,
+                        "space": 3
+                    }
+                },
+                "subscription": subscription
+            }
+        }).to_string()
+    }
+
+    fn subscription_response(id: u64, subscription: u64) -> String {
+        json!({ "jsonrpc": "2.0", "result": subscription, "id": id }).to_string()
+    }
+
+    #[test]
+    fn test_notifications_are_routed_by_subscription() {
+        let client = PubSubClient::new(CommitmentLevel::Confirmed);
+        let first = Pubkey::new_unique();
+        client.add(ESubscriptionTarget::Account(first), 'a');
+        client.add(ESubscriptionTarget::Account(Pubkey::new_unique()), 'b');
+        let messages = client.subscribe_messages().unwrap();
+
+        // The node may answer in any order
+        client.handle_text(&subscription_response(request_id(&messages[1]), 20));
+        let subscribed = client.handle_text(&subscription_response(request_id(&messages[0]), 10));
+
+        assert_eq!(subscribed, Some(EPubSubMessage::Subscribed { key: 'a', target: ESubscriptionTarget::Account(first) }));
+        assert_eq!(client.handle_text(&account_notification(20, 7)), Some(EPubSubMessage::Account { key: 'b', slot: 7, data: vec![1, 2, 3] }));
+        assert_eq!(client.handle_text(&account_notification(10, 8)), Some(EPubSubMessage::Account { key: 'a', slot: 8, data: vec![1, 2, 3] }));
+        assert_eq!(client.handle_text(&account_notification(30, 8)), Some(EPubSubMessage::Stale));
+        assert_eq!(client.handle_text("{\"unknown\":true}"), None);
+    }
+
+    #[test]
+    fn test_subscriptions_are_restored_after_reconnect() {
+        let client = PubSubClient::new(CommitmentLevel::Confirmed);
+        client.add(ESubscriptionTarget::Account(Pubkey::new_unique()), 'a');
+        let first = client.subscribe_messages().unwrap();
+        client.handle_text(&subscription_response(request_id(&first[0]), 10));
+
+        let restored = client.subscribe_messages().unwrap();
+        client.handle_text(&subscription_response(request_id(&restored[0]), 11));
+
+        assert_eq!(restored.len(), 1);
+        assert_ne!(request_id(&restored[0]), request_id(&first[0]));
+        // Subscription IDs of the previous connection are no longer routed
+        assert_eq!(client.handle_text(&account_notification(10, 1)), Some(EPubSubMessage::Stale));
+        assert!(matches!(client.handle_text(&account_notification(11, 1)), Some(EPubSubMessage::Account { key: 'a', .. })));
+    }
+
+    #[test]
+    fn test_unsubscribe_where() {
+        let client = PubSubClient::new(CommitmentLevel::Confirmed);
+        client.add(ESubscriptionTarget::Account(Pubkey::new_unique()), 1);
+        client.add(ESubscriptionTarget::Program(Pubkey::new_unique(), Vec::new()), 2);
+        let messages = client.subscribe_messages().unwrap();
+        client.handle_text(&subscription_response(request_id(&messages[0]), 10));
+        client.handle_text(&subscription_response(request_id(&messages[1]), 20));
+
+        let unsubscribe = client.unsubscribe_where(|key| *key == 2).unwrap();
+
+        let value: Value = serde_json::from_str(unsubscribe[0].to_text().unwrap()).unwrap();
+        assert_eq!(value["method"], "programUnsubscribe");
+        assert_eq!(value["params"], json!([20]));
+        assert_eq!(client.len(), 1);
+    }
+
+    #[test]
+    fn test_slot_notification() {
+        let client = PubSubClient::new(CommitmentLevel::Confirmed);
+        let message = client.subscribe(ESubscriptionTarget::Slot, 's').unwrap();
+        client.handle_text(&subscription_response(request_id(&message), 9));
+
+        let notification = json!({
+            "jsonrpc": "2.0",
+            "method": "slotNotification",
+            "params": { "result": { "parent": 74, "root": 42, "slot": 75 }, "subscription": 9 }
+        }).to_string();
+
+        assert_eq!(client.handle_text(&notification), Some(EPubSubMessage::Slot { key: 's', slot: 75, root: 42 }));
+    }
+
+    #[test]
+    fn test_program_notification() {
+        let client = PubSubClient::new(CommitmentLevel::Processed);
+        let program_id = Pubkey::new_unique();
+        let account = Pubkey::new_unique();
+        let message = client.subscribe(ESubscriptionTarget::Program(program_id, vec![EProgramFilter::DataSize(3)]), ()).unwrap();
+        client.handle_text(&subscription_response(request_id(&message), 5));
+
+        let notification = json!({
+            "jsonrpc": "2.0",
+            "method": "programNotification",
+            "params": {
+                "result": {
+                    "context": { "slot": 42 },
+                    "value": {
+                        "pubkey": account.to_string(),
+                        "account": {
+                            "data": ["AQID", "base64"],
+                            "executable": false,
+                            "lamports": 1,
+                            "owner": program_id.to_string(),
+                            "rentEpoch": 0,
+                            "space": 3
+                        }
+                    }
+                },
+                "subscription": 5
+            }
+        }).to_string();
+
+        assert_eq!(client.handle_text(&notification), Some(EPubSubMessage::Program { key: (), slot: 42, account, data: vec![1, 2, 3] }));
+    }
+}
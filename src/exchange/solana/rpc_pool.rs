@@ -0,0 +1,132 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use futures::future::join_all;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use thiserror::Error;
+use crate::exchange::solana::endpoint_stats::EndpointStats;
+
+/// Endpoints not answering the health check within this duration are considered down
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Error, Debug)]
+pub enum RpcPoolError {
+    #[error("No healthy RPC endpoint out of {0}")]
+    NoHealthyEndpoint(usize),
+}
+
+/// RPC clients of redundant endpoints, the requests go to the active one
+/// The health check probes every endpoint with `getSlot` and fails over when the active one does not answer
+pub struct RpcPool {
+    clients: Vec<RpcClient>,
+    active: AtomicUsize,
+    stats: Mutex<Vec<EndpointStats>>,
+}
+
+impl RpcPool {
+    /// Pool of the endpoints in order of preference, there must be at least one
+    pub fn new(urls: Vec<String>) -> Self {
+        assert!(!urls.is_empty(), "RPC pool without endpoint");
+        Self {
+            stats: Mutex::new(urls.iter().map(|url| EndpointStats::new(url)).collect()),
+            clients: urls.into_iter().map(RpcClient::new).collect(),
+            active: AtomicUsize::new(0),
+        }
+    }
+
+    /// Client of the active endpoint
+    pub fn client(&self) -> &RpcClient {
+        &self.clients[self.active.load(Ordering::Relaxed)]
+    }
+
+    pub fn len(&self) -> usize {
+        self.clients.len()
+    }
+
+    /// Probe every endpoint, keep the active one if it is healthy and fail over to the fastest healthy one otherwise
+    pub async fn health_check(&self) -> Result<(), RpcPoolError> {
+        let probes = join_all(self.clients.iter().map(|client| async move {
+            let start = Instant::now();
+            match tokio::time::timeout(HEALTH_CHECK_TIMEOUT, client.get_slot()).await {
+                Ok(Ok(_)) => Ok(start.elapsed()),
+                Ok(Err(e)) => Err(e.to_string()),
+                Err(_) => Err(format!("no answer within {} s", HEALTH_CHECK_TIMEOUT.as_secs())),
+            }
+        })).await;
+
+        let mut stats = self.stats.lock().unwrap();
+        let mut fastest: Option<(usize, Duration)> = None;
+        for (i, probe) in probes.iter().enumerate() {
+            match probe {
+                Ok(latency) => {
+                    stats[i].record_success(*latency);
+                    if fastest.is_none_or(|(_, fastest_latency)| *latency < fastest_latency) {
+                        fastest = Some((i, *latency));
+                    }
+                }
+                Err(e) => stats[i].record_failure(e.clone()),
+            }
+        }
+        let active = self.active.load(Ordering::Relaxed);
+        if probes[active].is_ok() {
+            return Ok(());
+        }
+        let (healthy, _) = fastest.ok_or(RpcPoolError::NoHealthyEndpoint(self.clients.len()))?;
+        eprintln!("[WARNING][Solana] RPC endpoint {} is down, failing over to {}", stats[active].label, stats[healthy].label);
+        self.active.store(healthy, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Statistics of every endpoint, in order of preference
+    pub fn stats(&self) -> Vec<EndpointStats> {
+        self.stats.lock().unwrap().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use super::*;
+
+    /// Minimal JSON-RPC server answering every request with the slot
+    async fn spawn_rpc_server() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut request = [0u8; 4096];
+                let _ = stream.read(&mut request).await;
+                let body = r#"{"jsonrpc":"2.0","result":1234,"id":1}"#;
+                let response = format!("HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", body.len(), body);
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+        url
+    }
+
+    /// URL of a closed local port
+    async fn unreachable_url() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        format!("http://{}", listener.local_addr().unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_health_check_fails_over_to_healthy_endpoint() {
+        let pool = RpcPool::new(vec![unreachable_url().await, spawn_rpc_server().await]);
+        pool.health_check().await.unwrap();
+
+        assert_eq!(pool.client().url(), pool.clients[1].url());
+        let stats = pool.stats();
+        assert_eq!((stats[0].successes, stats[0].failures), (0, 1));
+        assert_eq!((stats[1].successes, stats[1].failures), (1, 0));
+        assert!(stats[1].latency.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_health_check_without_healthy_endpoint() {
+        let pool = RpcPool::new(vec![unreachable_url().await]);
+        assert!(matches!(pool.health_check().await, Err(RpcPoolError::NoHealthyEndpoint(1))));
+        assert_eq!(pool.client().url(), pool.clients[0].url());
+    }
+}
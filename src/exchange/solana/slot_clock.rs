@@ -0,0 +1,95 @@
+use chrono::{DateTime, TimeDelta, Utc};
+
+/// Nominal slot duration of the Solana cluster, used until slots have been observed
+pub const DEFAULT_SLOT_DURATION: TimeDelta = TimeDelta::milliseconds(400);
+/// Weight of a new sample in the average slot duration
+const SLOT_DURATION_SMOOTHING: f64 = 0.1;
+/// Gaps over this many slots (e.g. after a reconnect) are not used as duration samples
+const MAX_SAMPLE_SLOTS: u64 = 16;
+
+/// Estimates when slots were produced from the times the slot notifications were received
+/// The estimate lags behind the cluster by the delivery latency of the notifications
+#[derive(Debug, Clone)]
+pub struct SlotClock {
+    /// Newest slot and the time its notification was received
+    last: Option<(u64, DateTime<Utc>)>,
+    /// Average slot duration in microseconds
+    slot_duration_us: f64,
+}
+
+impl Default for SlotClock {
+    fn default() -> Self {
+        Self {
+            last: None,
+            slot_duration_us: DEFAULT_SLOT_DURATION.num_microseconds().unwrap_or_default() as f64,
+        }
+    }
+}
+
+impl SlotClock {
+    /// Record a slot notification, older and repeated slots are ignored
+    pub fn on_slot(&mut self, slot: u64, received: DateTime<Utc>) {
+        if let Some((last_slot, last_time)) = self.last {
+            if slot <= last_slot {
+                return;
+            }
+            let slots = slot - last_slot;
+            if let (true, Some(elapsed_us)) = (slots <= MAX_SAMPLE_SLOTS, (received - last_time).num_microseconds()) {
+                let sample = elapsed_us as f64 / slots as f64;
+                self.slot_duration_us += (sample - self.slot_duration_us) * SLOT_DURATION_SMOOTHING;
+            }
+        }
+        self.last = Some((slot, received));
+    }
+
+    /// Newest slot of the cluster
+    pub fn current_slot(&self) -> Option<u64> {
+        self.last.map(|(slot, _)| slot)
+    }
+
+    pub fn slot_duration(&self) -> TimeDelta {
+        TimeDelta::microseconds(self.slot_duration_us as i64)
+    }
+
+    /// Estimated production time of the slot, `None` before the first slot notification
+    pub fn slot_time(&self, slot: u64) -> Option<DateTime<Utc>> {
+        let (last_slot, last_time) = self.last?;
+        let slots = slot as i64 - last_slot as i64;
+        Some(last_time + TimeDelta::microseconds((self.slot_duration_us * slots as f64) as i64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slot_time_of_older_slot() {
+        let start = Utc::now();
+        let mut clock = SlotClock::default();
+        clock.on_slot(100, start);
+        clock.on_slot(101, start + TimeDelta::milliseconds(400));
+
+        assert_eq!(clock.current_slot(), Some(101));
+        assert_eq!(clock.slot_time(96), Some(start - TimeDelta::milliseconds(1600)));
+    }
+
+    #[test]
+    fn test_slot_duration_follows_the_cluster() {
+        let start = Utc::now();
+        let mut clock = SlotClock::default();
+        for i in 0..100 {
+            clock.on_slot(i, start + TimeDelta::milliseconds(500 * i as i64));
+        }
+        // A long gap after a reconnect is not a duration sample
+        clock.on_slot(1000, start + TimeDelta::seconds(3600));
+
+        let duration = clock.slot_duration().num_milliseconds();
+        assert!((495..=500).contains(&duration), "slot duration {} ms", duration);
+    }
+
+    #[test]
+    fn test_slot_time_unknown_before_first_slot() {
+        assert_eq!(SlotClock::default().slot_time(1), None);
+    }
+}
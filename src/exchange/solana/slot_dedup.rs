@@ -0,0 +1,62 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How a notification relates to the ones already received for the same key
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EDelivery {
+    /// First notification of a newer slot, to be applied
+    First,
+    /// The slot was already delivered by another connection, this long ago
+    Late(Duration),
+    /// Older than the last applied slot
+    Stale,
+}
+
+/// Deduplicates by slot the notifications of the same subscriptions raced over several connections
+/// Only the first notification of a newer slot is applied, so with processed commitment a fork switch
+/// is only seen once the new fork overtakes the slot of the abandoned one
+#[derive(Debug)]
+pub struct SlotDedup<K> {
+    /// Last applied slot of every key and the time its first notification was received
+    applied: Mutex<HashMap<K, (u64, Instant)>>,
+}
+
+impl<K> Default for SlotDedup<K> {
+    fn default() -> Self {
+        Self { applied: Mutex::new(HashMap::new()) }
+    }
+}
+
+impl<K: Hash + Eq> SlotDedup<K> {
+    pub fn claim(&self, key: K, slot: u64, received: Instant) -> EDelivery {
+        let mut applied = self.applied.lock().unwrap();
+        match applied.get(&key) {
+            Some(&(applied_slot, first_received)) if slot == applied_slot => {
+                EDelivery::Late(received.saturating_duration_since(first_received))
+            }
+            Some(&(applied_slot, _)) if slot < applied_slot => EDelivery::Stale,
+            _ => {
+                applied.insert(key, (slot, received));
+                EDelivery::First
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_claim_applies_first_notification_of_a_slot() {
+        let dedup = SlotDedup::default();
+        let start = Instant::now();
+        assert_eq!(dedup.claim("book", 10, start), EDelivery::First);
+        assert_eq!(dedup.claim("book", 10, start + Duration::from_millis(30)), EDelivery::Late(Duration::from_millis(30)));
+        assert_eq!(dedup.claim("trace", 10, start), EDelivery::First);
+        assert_eq!(dedup.claim("book", 9, start), EDelivery::Stale);
+        assert_eq!(dedup.claim("book", 11, start), EDelivery::First);
+    }
+}
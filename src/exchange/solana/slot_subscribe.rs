@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize)]
+pub struct SlotSubscribeMessage {
+    jsonrpc: String,
+    id: u64,
+    method: String,
+}
+
+impl SlotSubscribeMessage {
+    pub fn new(id: u64) -> Self {
+        Self {
+            jsonrpc: "2.0".to_string(),
+            id,
+            method: "slotSubscribe".to_string(),
+        }
+    }
+}
+
+/// Notification sent when the node starts processing a slot
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+pub struct SlotNotification {
+    pub jsonrpc: String,
+    pub method: String,
+    pub params: Params,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Params {
+    pub result: SlotInfo,
+    pub subscription: u64,
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+pub struct SlotInfo {
+    pub parent: u64,
+    pub root: u64,
+    pub slot: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_slot_subscribe_message_serialization() {
+        let message = SlotSubscribeMessage::new(3);
+
+        assert_eq!(serde_json::to_value(&message).unwrap(), json!({
+            "jsonrpc": "2.0",
+            "id": 3,
+            "method": "slotSubscribe"
+        }));
+    }
+}
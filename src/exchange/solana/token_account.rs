@@ -0,0 +1,18 @@
+use crate::exchange::solana::account_data::{check_size, read_u64, read_u8, AccountDecodeError};
+
+/// Offset of the amount in an SPL token account: mint (32) and owner (32) come first
+const TOKEN_ACCOUNT_AMOUNT_OFFSET: usize = 64;
+/// Offset of the decimals in an SPL mint account: mint authority (36) and supply (8) come first
+const MINT_DECIMALS_OFFSET: usize = 44;
+
+/// Decode the token amount of an SPL token account
+pub fn decode_token_amount(data: &[u8]) -> Result<u64, AccountDecodeError> {
+    check_size(data, TOKEN_ACCOUNT_AMOUNT_OFFSET + 8)?;
+    Ok(read_u64(data, TOKEN_ACCOUNT_AMOUNT_OFFSET))
+}
+
+/// Decode the decimals of an SPL mint account
+pub fn decode_mint_decimals(data: &[u8]) -> Result<u8, AccountDecodeError> {
+    check_size(data, MINT_DECIMALS_OFFSET + 1)?;
+    Ok(read_u8(data, MINT_DECIMALS_OFFSET))
+}
@@ -0,0 +1,14 @@
+use solana_sdk::pubkey;
+use solana_sdk::pubkey::Pubkey;
+use crate::trading_pair::ETradingPair;
+
+/// Wrapped SOL mint
+const WSOL_MINT: Pubkey = pubkey!("So11111111111111111111111111111111111111112");
+
+/// Returns the mainnet mint of the base currency of the trading pair
+pub fn base_mint(trading_pair: &ETradingPair) -> Option<Pubkey> {
+    match trading_pair {
+        ETradingPair::BtcUsdc => None,
+        ETradingPair::SolUsdc => Some(WSOL_MINT),
+    }
+}
@@ -0,0 +1,91 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use crate::exchange::solana::endpoint_stats::EndpointStats;
+use crate::exchange::solana::slot_dedup::EDelivery;
+
+/// Redundant PubSub endpoints, a single connection uses the active one and moves to the next one when it is lost
+pub struct WsEndpoints {
+    urls: Vec<String>,
+    active: AtomicUsize,
+    stats: Mutex<Vec<EndpointStats>>,
+}
+
+impl WsEndpoints {
+    /// Endpoints in order of preference
+    pub fn new(urls: Vec<String>) -> Self {
+        Self {
+            stats: Mutex::new(urls.iter().map(|url| EndpointStats::new(url)).collect()),
+            urls,
+            active: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.urls.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.urls.is_empty()
+    }
+
+    pub fn url(&self, endpoint: usize) -> &str {
+        &self.urls[endpoint]
+    }
+
+    pub fn label(&self, endpoint: usize) -> String {
+        self.stats.lock().unwrap()[endpoint].label.clone()
+    }
+
+    pub fn active(&self) -> usize {
+        self.active.load(Ordering::Relaxed)
+    }
+
+    /// Move from the lost endpoint to the next one, unless another connection already did
+    pub fn fail_over(&self, lost: usize) {
+        let next = (lost + 1) % self.urls.len();
+        if next != lost && self.active.compare_exchange(lost, next, Ordering::Relaxed, Ordering::Relaxed).is_ok() {
+            eprintln!("[WARNING][Solana] PubSub endpoint {} lost, failing over to {}", self.label(lost), self.label(next));
+        }
+    }
+
+    /// Round trip of the subscription requests of a new connection
+    pub fn record_subscribed(&self, endpoint: usize, latency: Duration) {
+        self.stats.lock().unwrap()[endpoint].record_success(latency);
+    }
+
+    pub fn record_failure(&self, endpoint: usize, error: String) {
+        self.stats.lock().unwrap()[endpoint].record_failure(error);
+    }
+
+    pub fn record_delivery(&self, endpoint: usize, delivery: EDelivery) {
+        let mut stats = self.stats.lock().unwrap();
+        match delivery {
+            EDelivery::First => stats[endpoint].record_first_delivery(),
+            EDelivery::Late(delay) => stats[endpoint].record_late_delivery(delay),
+            EDelivery::Stale => {}
+        }
+    }
+
+    /// Statistics of every endpoint, in order of preference
+    pub fn stats(&self) -> Vec<EndpointStats> {
+        self.stats.lock().unwrap().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fail_over_moves_to_next_endpoint_once() {
+        let endpoints = WsEndpoints::new(vec!["wss://a.example.com/".to_string(), "wss://b.example.com/".to_string()]);
+        endpoints.fail_over(0);
+        assert_eq!(endpoints.active(), 1);
+        // A second report of the same lost endpoint does not move past the new one
+        endpoints.fail_over(0);
+        assert_eq!(endpoints.active(), 1);
+        endpoints.fail_over(1);
+        assert_eq!(endpoints.url(endpoints.active()), "wss://a.example.com/");
+    }
+}
@@ -0,0 +1,105 @@
+use std::collections::VecDeque;
+use chrono::{DateTime, TimeDelta, Utc};
+use crate::exchange::order_book::{TPrice, TVolume};
+
+/// Side of the taker (aggressor) of a trade
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EAggressorSide {
+    Buy,
+    Sell,
+}
+
+/// A single public trade printed by an exchange
+#[derive(Clone, Debug)]
+pub struct Trade {
+    pub price: TPrice,
+    pub size: TVolume,
+    pub side: EAggressorSide,
+    /// The timestamp from the match engine when the trade was filled
+    pub timestamp: DateTime<Utc>,
+    /// Solana slot the trade was executed in, for on-chain exchanges
+    pub slot: Option<u64>,
+}
+
+/// A rolling tape of the most recent public trades of a single market pair
+/// Trades older than `max_age` (relative to the newest trade) are evicted
+pub struct TradeTape {
+    trades: VecDeque<Trade>,
+    max_age: TimeDelta,
+}
+
+impl TradeTape {
+    pub(crate) fn new(max_age: TimeDelta) -> Self {
+        TradeTape {
+            trades: VecDeque::new(),
+            max_age,
+        }
+    }
+
+    /// Appends a trade to the tape and evicts trades that fell out of the retention window
+    pub fn push(&mut self, trade: Trade) {
+        let newest = trade.timestamp;
+        self.trades.push_back(trade);
+        while let Some(oldest) = self.trades.front() {
+            if newest.signed_duration_since(oldest.timestamp) <= self.max_age {
+                break;
+            }
+            self.trades.pop_front();
+        }
+    }
+
+    /// Returns the most recent trade
+    pub fn last_trade(&self) -> Option<&Trade> {
+        self.trades.back()
+    }
+
+    /// Returns the total traded volume over the `window` preceding `now`
+    pub fn realized_volume(&self, window: TimeDelta, now: DateTime<Utc>) -> TVolume {
+        self.trades
+            .iter()
+            .rev()
+            .take_while(|trade| now.signed_duration_since(trade.timestamp) <= window)
+            .map(|trade| trade.size)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use ordered_float::OrderedFloat;
+    use super::*;
+
+    fn trade_at(ms: i64, size: f64) -> Trade {
+        Trade {
+            price: OrderedFloat(100.0),
+            size,
+            side: EAggressorSide::Buy,
+            timestamp: Utc.timestamp_millis_opt(ms).unwrap(),
+            slot: None,
+        }
+    }
+
+    #[test]
+    fn test_trade_tape_evicts_old_trades() {
+        let mut tape = TradeTape::new(TimeDelta::seconds(10));
+        tape.push(trade_at(1_000, 1.0));
+        tape.push(trade_at(5_000, 2.0));
+        tape.push(trade_at(12_000, 3.0));
+
+        assert_eq!(tape.trades.len(), 2);
+        assert_eq!(tape.last_trade().unwrap().size, 3.0);
+    }
+
+    #[test]
+    fn test_trade_tape_realized_volume() {
+        let mut tape = TradeTape::new(TimeDelta::seconds(60));
+        tape.push(trade_at(1_000, 1.0));
+        tape.push(trade_at(5_000, 2.0));
+        tape.push(trade_at(9_000, 3.0));
+
+        let now = Utc.timestamp_millis_opt(10_000).unwrap();
+        assert_eq!(tape.realized_volume(TimeDelta::seconds(6), now), 5.0);
+        assert_eq!(tape.realized_volume(TimeDelta::seconds(60), now), 6.0);
+    }
+}
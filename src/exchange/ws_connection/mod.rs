@@ -0,0 +1,2 @@
+pub mod ws_protocol;
+pub mod ws_connection_manager;
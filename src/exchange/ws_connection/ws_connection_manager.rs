@@ -0,0 +1,201 @@
+use std::time::Instant;
+use futures_util::{SinkExt, StreamExt};
+use tokio::sync::mpsc::Sender;
+use tokio::time::{interval, sleep};
+use tokio_tungstenite::{connect_async, tungstenite::protocol::Message};
+use crate::exchange::exchange_update::ExchangeUpdate;
+use crate::exchange::reconnect_policy::{ReconnectPolicy, Reconnector};
+use crate::exchange::send_connection_update::send_connection_update;
+use crate::exchange::ws_connection::ws_protocol::{EHeartbeat, EWsMessage, WsProtocol};
+
+/// Keeps a WebSocket connection described by the protocol alive, reconnecting according to the policy
+/// Returns once the retry budget is exhausted and the circuit breaker has opened
+pub async fn run_ws_connection<P: WsProtocol>(
+    protocol: &P,
+    policy: ReconnectPolicy,
+    update_sender: &Sender<ExchangeUpdate>,
+) {
+    let name = protocol.name();
+    let mut reconnector = Reconnector::new(policy);
+
+    loop {
+        println!("[INFO][{name}] Connecting to WebSocket {}...", protocol.url());
+        reconnector.on_attempt();
+        send_connection_update(update_sender, &name, reconnector.stats()).await;
+
+        let error = match run_session(protocol, &mut reconnector, update_sender).await {
+            Ok(_) => {
+                println!("[WARNING][{name}] WebSocket connection has been closed");
+                None
+            }
+            Err(e) => {
+                eprintln!("[ERROR][{name}] Error in WebSocket connection: {:?}", e);
+                Some(e.to_string())
+            }
+        };
+
+        // If we're here, it means the connection was closed or an error occurred
+        // Wait according to the reconnect policy before attempting to reconnect
+        protocol.on_disconnected(error.as_deref());
+        reconnector.on_disconnected(error);
+        let delay = reconnector.next_delay();
+        send_connection_update(update_sender, &name, reconnector.stats()).await;
+        match delay {
+            Some(delay) => {
+                sleep(delay).await;
+                println!("[INFO][{name}] Attempting to reconnect to WebSocket (attempt {})...", reconnector.stats().attempts + 1);
+            }
+            None => {
+                eprintln!("[ERROR][{name}] Reconnect budget exhausted, marking exchange as failed");
+                return;
+            }
+        }
+    }
+}
+
+/// Connect, subscribe and process messages until the connection is lost
+async fn run_session<P: WsProtocol>(
+    protocol: &P,
+    reconnector: &mut Reconnector,
+    update_sender: &Sender<ExchangeUpdate>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let name = protocol.name();
+    protocol.on_connecting().await?;
+
+    let (ws_stream, _) = connect_async(protocol.url()).await?;
+    let (mut write, mut read) = ws_stream.split();
+
+    let subscribe_messages = protocol.subscribe_messages()?;
+    for message in subscribe_messages {
+        write.send(message).await?;
+    }
+    reconnector.on_connected();
+    send_connection_update(update_sender, &name, reconnector.stats()).await;
+
+    let heartbeat = protocol.heartbeat();
+    let pong_timeout = protocol.pong_timeout();
+    let mut ping_interval = interval(protocol.heartbeat_interval());
+    let mut last_pong = Instant::now();
+    let mut ping_msg: u8 = 0;
+
+    loop {
+        tokio::select! {
+            _ = ping_interval.tick() => {
+                match &heartbeat {
+                    EHeartbeat::Text(ping) => write.send(Message::Text(ping.clone())).await?,
+                    EHeartbeat::PingFrame => {
+                        ping_msg = ping_msg.wrapping_add(1);
+                        write.send(Message::Ping(vec![ping_msg])).await?;
+                    }
+                }
+            }
+            message = read.next() => {
+                let Some(message) = message else {
+                    return Ok(());
+                };
+                match message? {
+                    Message::Text(text) => {
+                        match protocol.handle_text(&text).await {
+                            EWsMessage::Handled => {}
+                            EWsMessage::Pong => last_pong = Instant::now(),
+                            EWsMessage::Resubscribe => {
+                                println!("[INFO][{name}] Resubscribing...");
+                                let resubscribe_messages = protocol.resubscribe_messages()?;
+                                for message in resubscribe_messages {
+                                    write.send(message).await?;
+                                }
+                            }
+                            EWsMessage::Unknown => println!("[WARNING][{name}] Received unknown message: {}", text),
+                        }
+                    }
+                    Message::Pong(response) if response.first() == Some(&ping_msg) => {
+                        last_pong = Instant::now();
+                    }
+                    Message::Close(_) => return Ok(()),
+                    _ => {}
+                }
+            }
+        }
+
+        if last_pong.elapsed() > pong_timeout {
+            println!("[ERROR][{name}] No pong received within timeout, reconnecting...");
+            return Ok(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+    use std::time::Duration;
+    use async_trait::async_trait;
+    use tokio::net::TcpListener;
+    use tokio::sync::mpsc;
+    use tokio_tungstenite::accept_async;
+    use crate::exchange::exchange_update::EUpdatePayload;
+    use crate::exchange::connection_stats::EConnectionState;
+    use super::*;
+
+    struct TestProtocol {
+        url: String,
+        received: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl WsProtocol for TestProtocol {
+        fn name(&self) -> String {
+            "Test".to_string()
+        }
+
+        fn url(&self) -> String {
+            self.url.clone()
+        }
+
+        fn subscribe_messages(&self) -> Result<Vec<Message>, Box<dyn std::error::Error>> {
+            Ok(vec![Message::Text("subscribe".to_string())])
+        }
+
+        fn heartbeat(&self) -> EHeartbeat {
+            EHeartbeat::PingFrame
+        }
+
+        async fn handle_text(&self, text: &str) -> EWsMessage {
+            self.received.lock().unwrap().push(text.to_string());
+            EWsMessage::Handled
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_ws_connection_subscribes_and_dispatches_messages() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws_stream = accept_async(stream).await.unwrap();
+            let subscribe = ws_stream.next().await.unwrap().unwrap();
+            ws_stream.send(Message::Text("update".to_string())).await.unwrap();
+            ws_stream.close(None).await.unwrap();
+            subscribe
+        });
+
+        let protocol = TestProtocol { url, received: Mutex::new(Vec::new()) };
+        let policy = ReconnectPolicy { max_retries: 0, ..ReconnectPolicy::default() };
+        let (tx, mut rx) = mpsc::channel(100);
+        tokio::time::timeout(Duration::from_secs(5), run_ws_connection(&protocol, policy, &tx))
+            .await
+            .expect("Connection manager did not stop after the retry budget");
+
+        assert_eq!(server.await.unwrap(), Message::Text("subscribe".to_string()));
+        assert_eq!(*protocol.received.lock().unwrap(), vec!["update".to_string()]);
+
+        let mut states = Vec::new();
+        while let Ok(update) = rx.try_recv() {
+            if let EUpdatePayload::ConnectionStats(stats) = update.payload {
+                states.push(stats.state);
+            }
+        }
+        assert_eq!(states.first(), Some(&EConnectionState::Connecting));
+        assert!(states.contains(&EConnectionState::Connected));
+        assert_eq!(states.last(), Some(&EConnectionState::Failed));
+    }
+}
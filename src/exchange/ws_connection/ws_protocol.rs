@@ -0,0 +1,69 @@
+use std::time::Duration;
+use async_trait::async_trait;
+use tokio_tungstenite::tungstenite::protocol::Message;
+
+const DEFAULT_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(1);
+const DEFAULT_PONG_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How the connection is kept alive
+pub enum EHeartbeat {
+    /// Text message sent on every heartbeat interval, the protocol recognizes the response in `handle_text`
+    Text(String),
+    /// WebSocket Ping frame with a counter payload, answered by a Pong frame with the same payload
+    PingFrame,
+}
+
+/// Result of handling an incoming text message
+#[derive(Debug, PartialEq)]
+pub enum EWsMessage {
+    /// The message has been processed
+    Handled,
+    /// The message is a response to the text heartbeat
+    Pong,
+    /// The local state is inconsistent, the protocol asks to resubscribe to its channels
+    Resubscribe,
+    /// The message is not recognized by the protocol
+    Unknown,
+}
+
+/// Exchange specific part of a WebSocket connection
+/// The connection manager takes care of connecting, heartbeats, pong timeouts and reconnecting
+#[async_trait]
+pub trait WsProtocol: Send + Sync {
+    /// Name of the exchange used in logs and connection updates
+    fn name(&self) -> String;
+
+    /// WebSocket endpoint to connect to
+    fn url(&self) -> String;
+
+    /// Called before every connection attempt, e.g. to load a snapshot over HTTP
+    async fn on_connecting(&self) -> Result<(), Box<dyn std::error::Error>> {
+        Ok(())
+    }
+
+    /// Called after every lost connection, with the error if it failed, e.g. to move to another endpoint
+    fn on_disconnected(&self, _error: Option<&str>) {}
+
+    /// Messages sent right after the connection has been established
+    fn subscribe_messages(&self) -> Result<Vec<Message>, Box<dyn std::error::Error>>;
+
+    /// Messages sent when the protocol asks to resubscribe, e.g. after a failed checksum
+    fn resubscribe_messages(&self) -> Result<Vec<Message>, Box<dyn std::error::Error>> {
+        self.subscribe_messages()
+    }
+
+    /// Heartbeat style of the protocol
+    fn heartbeat(&self) -> EHeartbeat;
+
+    fn heartbeat_interval(&self) -> Duration {
+        DEFAULT_HEARTBEAT_INTERVAL
+    }
+
+    /// The connection is considered lost if no pong was received within this duration
+    fn pong_timeout(&self) -> Duration {
+        DEFAULT_PONG_TIMEOUT
+    }
+
+    /// Decode and process an incoming text message
+    async fn handle_text(&self, text: &str) -> EWsMessage;
+}
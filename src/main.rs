@@ -1,10 +1,19 @@
+#![allow(clippy::module_inception)]
+
+use std::env;
 use std::sync::Arc;
 use dotenv::dotenv;
 use strum::IntoEnumIterator;
 use crate::arbitrage_manager::arbitrage_manager::ArbitrageManager;
+use crate::exchange::binance::binance_exchange::BinanceExchange;
 use crate::exchange::bybit::bybit_exchange::BybitExchange;
 use crate::exchange::dexnow::dexnow_exchange::DexnowExchange;
+use crate::exchange::dexnow::list_instruments::EListFormat;
 use crate::exchange::exchange::Exchange;
+use crate::exchange::kraken::kraken_exchange::KrakenExchange;
+use crate::exchange::okx::okx_exchange::OkxExchange;
+use crate::exchange::orca::orca_exchange::OrcaExchange;
+use crate::exchange::raydium::raydium_exchange::RaydiumExchange;
 use crate::trading_pair::ETradingPair;
 
 mod arbitrage_manager;
@@ -14,6 +23,22 @@ mod trading_pair;
 #[tokio::main]
 async fn main() {
     dotenv().ok();
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        None => {}
+        Some("list-instruments") => {
+            let format = if args.iter().any(|arg| arg == "--json") { EListFormat::Json } else { EListFormat::Table };
+            match DexnowExchange::new().list_instruments(format).await {
+                Ok(listing) => println!("{}", listing),
+                Err(e) => eprintln!("[ERROR][DEXnow] Failed to list instruments: {}", e),
+            }
+            return;
+        }
+        Some(command) => {
+            eprintln!("[ERROR] Unknown command: {}. Available commands: list-instruments [--json]", command);
+            return;
+        }
+    }
     for pair in ETradingPair::iter() {
         println!("Supported currency: {}", pair);
     }
@@ -21,6 +46,34 @@ async fn main() {
     let bybit_exchange = Arc::new(BybitExchange::new());
     let dexnow_exchange = Arc::new(DexnowExchange::new());
     arbitrage_manager.add_exchange(bybit_exchange);
+    for futures_exchange in dexnow_exchange.futures_exchanges() {
+        arbitrage_manager.add_exchange(futures_exchange);
+    }
     arbitrage_manager.add_exchange(dexnow_exchange);
+    // The other venues are opt-in, they only run once their variables are set
+    if is_configured("Binance", &["BINANCE_WS_URL", "BINANCE_REST_URL"]) {
+        arbitrage_manager.add_exchange(Arc::new(BinanceExchange::new()));
+    }
+    if is_configured("Kraken", &["KRAKEN_WS_URL", "KRAKEN_REST_URL"]) {
+        arbitrage_manager.add_exchange(Arc::new(KrakenExchange::new()));
+    }
+    if is_configured("OKX", &["OKX_WS_URL"]) {
+        arbitrage_manager.add_exchange(Arc::new(OkxExchange::new()));
+    }
+    if is_configured("Raydium", &["RAYDIUM_RPC_URL", "RAYDIUM_WS_URL", "RAYDIUM_AMM_POOL_PK"]) {
+        arbitrage_manager.add_exchange(Arc::new(RaydiumExchange::new()));
+    }
+    if is_configured("Orca", &["ORCA_RPC_URL", "ORCA_WS_URL", "ORCA_WHIRLPOOL_PK"]) {
+        arbitrage_manager.add_exchange(Arc::new(OrcaExchange::new()));
+    }
     arbitrage_manager.run().await;
 }
+
+/// True if every variable of the venue is set, otherwise the venue is skipped
+fn is_configured(venue: &str, variables: &[&str]) -> bool {
+    let missing: Vec<&str> = variables.iter().copied().filter(|variable| env::var(variable).is_err()).collect();
+    if !missing.is_empty() {
+        println!("[INFO][{venue}] Not configured, skipping (missing {})", missing.join(", "));
+    }
+    missing.is_empty()
+}
@@ -2,6 +2,23 @@ use strum_macros::{Display, EnumIter};
 
 #[derive(Clone, Debug, Display, EnumIter)]
 pub enum ETradingPair {
-    BtcUsdc, // Not supported by DEXnow
+    BtcUsdc, // Not listed on DEXnow devnet
     SolUsdc
-}
\ No newline at end of file
+}
+
+impl ETradingPair {
+    /// Symbol of the traded asset
+    pub fn asset_symbol(&self) -> &'static str {
+        match self {
+            ETradingPair::BtcUsdc => "BTC",
+            ETradingPair::SolUsdc => "SOL",
+        }
+    }
+
+    /// Symbol of the quote currency
+    pub fn quote_symbol(&self) -> &'static str {
+        match self {
+            ETradingPair::BtcUsdc | ETradingPair::SolUsdc => "USDC",
+        }
+    }
+}